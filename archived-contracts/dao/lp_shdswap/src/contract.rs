@@ -92,7 +92,7 @@ pub fn instantiate(
         token_a: token_a.clone(),
         token_b: token_b.clone(),
         liquidity_token: pair_info.liquidity_token.clone(),
-        staking_contract: Some(Contract::default()),
+        staking_contract: msg.staking_contract.clone(),
         //staking_info.staking_contract.clone(),
         // TODO: query reward token from staking contract
         reward_token: None,
@@ -172,6 +172,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query::config(deps)?),
+        QueryMsg::ImpermanentLoss {} => to_binary(&query::impermanent_loss(deps)?),
         QueryMsg::Adapter(adapter) => match adapter {
             adapter::SubQueryMsg::Balance { asset } => {
                 let asset = deps.api.addr_validate(&asset)?;
@@ -1,4 +1,4 @@
-use crate::storage::*;
+use crate::{query, storage::*};
 use shade_protocol::{
     c_std::{
         to_binary,
@@ -26,7 +26,7 @@ pub fn receive(
     info: MessageInfo,
     _sender: Addr,
     _from: Addr,
-    _amount: Uint128,
+    amount: Uint128,
     _msg: Option<Binary>,
 ) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
@@ -50,6 +50,17 @@ pub fn receive(
         desired_token = config.token_b;
         println!("{}", desired_token.address);
     } else if info.sender == config.liquidity_token.address {
+        // Track what the LP was worth on entry, to report impermanent loss against
+        let (entry_a, entry_b) = ENTRY_AMOUNTS.may_load(deps.storage)?.unwrap_or_default();
+        let token_a = config.token_a.address.clone();
+        let token_b = config.token_b.address.clone();
+        ENTRY_AMOUNTS.save(
+            deps.storage,
+            &(
+                entry_a + query::lp_value(deps.as_ref(), &config, &token_a, amount)?,
+                entry_b + query::lp_value(deps.as_ref(), &config, &token_b, amount)?,
+            ),
+        )?;
         // TODO: stake lp tokens & exit
     } else {
         // TODO: send to treasury, non-pair rewards token
@@ -203,7 +214,7 @@ pub fn unbond(
         /* Pull LP token out of rewards contract
          * Hold for claiming
          */
-    } else if vec![config.token_a.address, config.token_b.address].contains(&asset) {
+    } else if asset == config.token_a.address || asset == config.token_b.address {
         /* Pull LP from rewards
          * Split LP into tokens A & B
          * Mark requested token for claim
@@ -212,6 +223,35 @@ pub fn unbond(
         return Err(StdError::generic_err("Unrecognized Asset"));
     }
 
+    /* Can't unbond more than the position holds on top of what is already unbonding,
+     * token A, token B and the LP token are all backed by the same LP so compare in LP
+     */
+    let position = query::lp_position(deps.as_ref(), &config)?;
+    let unbonding = query::lp_unbonding(deps.as_ref(), &config)?;
+    let lp_amount = query::lp_amount(deps.as_ref(), &config, &asset, amount)?;
+    if unbonding + lp_amount > position {
+        return Err(StdError::generic_err(format!(
+            "Cannot unbond {}, only {} LP is available",
+            amount,
+            position.saturating_sub(unbonding)
+        )));
+    }
+
+    // Entry amounts shrink with the share of the remaining LP leaving the position
+    if let Some((entry_a, entry_b)) = ENTRY_AMOUNTS.may_load(deps.storage)? {
+        let remaining = position.saturating_sub(unbonding);
+        if !remaining.is_zero() {
+            let kept = remaining.saturating_sub(lp_amount);
+            ENTRY_AMOUNTS.save(
+                deps.storage,
+                &(
+                    entry_a.multiply_ratio(kept, remaining),
+                    entry_b.multiply_ratio(kept, remaining),
+                ),
+            )?;
+        }
+    }
+
     UNBONDING.update(deps.storage, asset.clone(), |u| -> StdResult<Uint128> {
         Ok(u.unwrap_or_else(|| Uint128::zero()) + amount)
     })?;
@@ -1,5 +1,6 @@
 use shade_protocol::c_std::{
     Addr,
+    Decimal,
     Deps,
     StdError,
     StdResult,
@@ -7,10 +8,14 @@ use shade_protocol::c_std::{
 };
 
 use shade_protocol::{
-    contract_interfaces::dao::{
-        adapter,
-        lp_shdswap::{get_supported_asset, is_supported_asset, QueryAnswer},
+    contract_interfaces::{
+        dao::{
+            adapter,
+            lp_shdswap::{get_supported_asset, is_supported_asset, Config, QueryAnswer},
+        },
+        dex::shadeswap,
     },
+    utils::Query,
 };
 
 use shade_protocol::snip20::helpers::balance_query;
@@ -23,11 +28,145 @@ pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
+/* Compares the held LP against having kept the tokens it was entered with,
+ * both valued in token_b at the current pool price
+ */
+pub fn impermanent_loss(deps: Deps) -> StdResult<QueryAnswer> {
+    let config = CONFIG.load(deps.storage)?;
+    let (entry_a, entry_b) = ENTRY_AMOUNTS.may_load(deps.storage)?.unwrap_or_default();
+
+    let pair_info: shadeswap::PairInfoResponse =
+        shadeswap::PairQuery::GetPairInfo {}.query(&deps.querier, &config.pair)?;
+
+    // LP being unbonded already left the position, entry amounts are reduced with it
+    let lp_amount = lp_position(deps, &config)?.saturating_sub(lp_unbonding(deps, &config)?);
+    let lp_a = lp_value(deps, &config, &config.token_a.address, lp_amount)?;
+    let lp_b = lp_value(deps, &config, &config.token_b.address, lp_amount)?;
+
+    let (held_value, lp_value) = if pair_info.amount_0.is_zero() {
+        (entry_b, lp_b)
+    } else {
+        (
+            entry_a.multiply_ratio(pair_info.amount_1, pair_info.amount_0) + entry_b,
+            lp_a.multiply_ratio(pair_info.amount_1, pair_info.amount_0) + lp_b,
+        )
+    };
+
+    let loss = if held_value.is_zero() || lp_value >= held_value {
+        Decimal::zero()
+    } else {
+        Decimal::one() - Decimal::from_ratio(lp_value, held_value)
+    };
+
+    Ok(QueryAnswer::ImpermanentLoss {
+        held_value,
+        lp_value,
+        loss,
+    })
+}
+
 pub fn rewards(_deps: Deps) -> StdResult<Uint128> {
     //TODO: query pending rewards from rewards contract
     Ok(Uint128::zero())
 }
 
+/// LP tokens held by this contract
+pub fn lp_balance(deps: Deps, config: &Config) -> StdResult<Uint128> {
+    balance_query(
+        &deps.querier,
+        SELF_ADDRESS.load(deps.storage)?,
+        VIEWING_KEY.load(deps.storage)?,
+        &config.liquidity_token,
+    )
+}
+
+/// LP tokens staked in the rewards contract
+pub fn lp_staked(deps: Deps, config: &Config) -> StdResult<Uint128> {
+    let staking_contract = match &config.staking_contract {
+        Some(contract) => contract,
+        None => return Ok(Uint128::zero()),
+    };
+
+    match (shadeswap::StakingQuery::GetStakerLpTokenInfo {
+        key: VIEWING_KEY.load(deps.storage)?,
+        staker: SELF_ADDRESS.load(deps.storage)?,
+    }
+    .query(&deps.querier, staking_contract)?)
+    {
+        shadeswap::StakingQueryResponse::StakerLpTokenInfo {
+            staked_lp_token, ..
+        } => Ok(staked_lp_token),
+    }
+}
+
+/// All LP in the position, held or staked
+pub fn lp_position(deps: Deps, config: &Config) -> StdResult<Uint128> {
+    Ok(lp_balance(deps, config)? + lp_staked(deps, config)?)
+}
+
+/// Underlying amount of a pair token that `lp_amount` redeems for
+/// at the live pool reserves
+pub fn lp_value(
+    deps: Deps,
+    config: &Config,
+    asset: &Addr,
+    lp_amount: Uint128,
+) -> StdResult<Uint128> {
+    let pair_info: shadeswap::PairInfoResponse =
+        shadeswap::PairQuery::GetPairInfo {}.query(&deps.querier, &config.pair)?;
+
+    if pair_info.total_liquidity.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    // token_a & token_b are token_0 & token_1 of the pair, see instantiate
+    let reserve = if *asset == config.token_a.address {
+        pair_info.amount_0
+    } else {
+        pair_info.amount_1
+    };
+
+    Ok(lp_amount.multiply_ratio(reserve, pair_info.total_liquidity))
+}
+
+/// LP that redeems for `amount` of a pair token, the inverse of `lp_value`
+pub fn lp_amount(deps: Deps, config: &Config, asset: &Addr, amount: Uint128) -> StdResult<Uint128> {
+    if *asset == config.liquidity_token.address {
+        return Ok(amount);
+    }
+
+    let pair_info: shadeswap::PairInfoResponse =
+        shadeswap::PairQuery::GetPairInfo {}.query(&deps.querier, &config.pair)?;
+
+    let reserve = if *asset == config.token_a.address {
+        pair_info.amount_0
+    } else {
+        pair_info.amount_1
+    };
+
+    if reserve.is_zero() {
+        return Ok(Uint128::zero());
+    }
+
+    Ok(amount.multiply_ratio(pair_info.total_liquidity, reserve))
+}
+
+/// Unbondings of every supported asset, in LP
+pub fn lp_unbonding(deps: Deps, config: &Config) -> StdResult<Uint128> {
+    let mut total = Uint128::zero();
+    for asset in [
+        &config.token_a.address,
+        &config.token_b.address,
+        &config.liquidity_token.address,
+    ] {
+        let unbonding = UNBONDING
+            .may_load(deps.storage, asset.clone())?
+            .unwrap_or_default();
+        total += lp_amount(deps, config, asset, unbonding)?;
+    }
+    Ok(total)
+}
+
 pub fn balance(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
     let config = CONFIG.load(deps.storage)?;
 
@@ -38,12 +177,12 @@ pub fn balance(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
         )));
     }
 
-    let balance = Uint128::zero();
+    let mut balance = Uint128::zero();
 
-    if vec![config.token_a.address, config.token_b.address].contains(&asset) {
-        // Determine balance of LP, determine redemption value
+    if asset == config.token_a.address || asset == config.token_b.address {
+        balance = lp_value(deps, &config, &asset, lp_position(deps, &config)?)?;
     } else if config.liquidity_token.address == asset {
-        // Check LP tokens in rewards contract + balance
+        balance = lp_position(deps, &config)?;
     }
 
     Ok(adapter::QueryAnswer::Balance { amount: balance })
@@ -104,8 +243,7 @@ pub fn unbondable(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
 
     let unbonding = UNBONDING.load(deps.storage, asset.clone())?;
 
-    /* Need to check LP token redemption value
-     */
+    // Everything in the position, including LP that would need to be unstaked first
     let unbondable = match balance(deps, asset)? {
         adapter::QueryAnswer::Balance { amount } => {
             if amount < unbonding {
@@ -132,16 +270,23 @@ pub fn reserves(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
         )));
     }
 
-    let asset_contract = get_supported_asset(&config, &asset);
-
     let unbonding = UNBONDING.load(deps.storage, asset.clone())?;
 
-    let balance = balance_query(
-        &deps.querier,
-        SELF_ADDRESS.load(deps.storage)?,
-        VIEWING_KEY.load(deps.storage)?,
-        &asset_contract,
-    )?;
+    /* Only LP held by this contract can be burned right away,
+     * LP staked in the rewards contract counts towards unbondable instead
+     */
+    let balance = if asset == config.token_a.address || asset == config.token_b.address {
+        lp_value(deps, &config, &asset, lp_balance(deps, &config)?)?
+    } else if asset == config.liquidity_token.address {
+        lp_balance(deps, &config)?
+    } else {
+        balance_query(
+            &deps.querier,
+            SELF_ADDRESS.load(deps.storage)?,
+            VIEWING_KEY.load(deps.storage)?,
+            &get_supported_asset(&config, &asset),
+        )?
+    };
 
     if unbonding >= balance {
         return Ok(adapter::QueryAnswer::Reserves {
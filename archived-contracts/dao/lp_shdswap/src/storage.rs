@@ -8,3 +8,5 @@ pub const CONFIG: Item<lp_shdswap::Config> = Item::new("config");
 pub const VIEWING_KEY: Item<String> = Item::new("viewing_key");
 pub const SELF_ADDRESS: Item<Addr> = Item::new("self_address");
 pub const UNBONDING: Map<Addr, Uint128> = Map::new("unbonding");
+// token_a & token_b the deposited LP was worth when it was received
+pub const ENTRY_AMOUNTS: Item<(Uint128, Uint128)> = Item::new("entry_amounts");
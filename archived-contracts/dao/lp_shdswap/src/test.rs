@@ -44,3 +44,379 @@ pub mod tests {
     }
 }
 */
+
+use shade_protocol::{
+    c_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage},
+        to_binary,
+        Addr,
+        ContractResult,
+        Decimal,
+        OwnedDeps,
+        QuerierResult,
+        Response,
+        StdResult,
+        SystemError,
+        SystemResult,
+        Uint128,
+        WasmQuery,
+    },
+    contract_interfaces::{
+        dao::{
+            adapter,
+            lp_shdswap::{Config, QueryAnswer},
+        },
+        dex::shadeswap,
+        snip20,
+    },
+    utils::asset::Contract,
+};
+
+use crate::{execute, query, storage::*};
+
+fn contract(address: &str) -> Contract {
+    Contract {
+        address: Addr::unchecked(address),
+        code_hash: "hash".to_string(),
+    }
+}
+
+fn token_type(address: &str) -> shadeswap::TokenType {
+    shadeswap::TokenType::CustomToken {
+        contract_addr: Addr::unchecked(address),
+        token_code_hash: "hash".to_string(),
+    }
+}
+
+fn balance(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, asset: &str) -> Uint128 {
+    match query::balance(deps.as_ref(), Addr::unchecked(asset)).unwrap() {
+        adapter::QueryAnswer::Balance { amount } => amount,
+        _ => panic!("Unexpected query answer"),
+    }
+}
+
+fn reserves(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, asset: &str) -> Uint128 {
+    match query::reserves(deps.as_ref(), Addr::unchecked(asset)).unwrap() {
+        adapter::QueryAnswer::Reserves { amount } => amount,
+        _ => panic!("Unexpected query answer"),
+    }
+}
+
+fn unbondable(deps: &OwnedDeps<MockStorage, MockApi, MockQuerier>, asset: &str) -> Uint128 {
+    match query::unbondable(deps.as_ref(), Addr::unchecked(asset)).unwrap() {
+        adapter::QueryAnswer::Unbondable { amount } => amount,
+        _ => panic!("Unexpected query answer"),
+    }
+}
+
+fn unbond(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    asset: &str,
+    amount: u128,
+) -> StdResult<Response> {
+    execute::unbond(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        Addr::unchecked(asset),
+        Uint128::new(amount),
+    )
+}
+
+/* Mocked LP token that reports `lp_balance` for this contract,
+ * other snip20 balances are zero.
+ * The staking contract holds `lp_staked` LP for this contract.
+ * The pool holds `amount_0` token_a & `amount_1` token_b for 2000 LP
+ */
+fn mock_wasm(
+    lp_balance: u128,
+    lp_staked: u128,
+    amount_0: u128,
+    amount_1: u128,
+) -> impl Fn(&WasmQuery) -> QuerierResult {
+    move |query| match query {
+        WasmQuery::Smart {
+            contract_addr, msg, ..
+        } => {
+            if let Ok(shadeswap::StakingQuery::GetStakerLpTokenInfo { .. }) = from_binary(msg) {
+                return SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&shadeswap::StakingQueryResponse::StakerLpTokenInfo {
+                        staked_lp_token: Uint128::new(lp_staked),
+                        total_staked_lp_token: Uint128::new(lp_staked),
+                    })
+                    .unwrap(),
+                ));
+            }
+            if let Ok(snip20::QueryMsg::Balance { .. }) = from_binary(msg) {
+                return SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&snip20::QueryAnswer::Balance {
+                        amount: match contract_addr.as_str() {
+                            "lp_token" => Uint128::new(lp_balance),
+                            _ => Uint128::zero(),
+                        },
+                    })
+                    .unwrap(),
+                ));
+            }
+            match from_binary(msg) {
+                Ok(shadeswap::PairQuery::GetPairInfo {}) => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&shadeswap::PairInfoResponse {
+                        liquidity_token: contract("lp_token"),
+                        factory: contract("factory"),
+                        pair: shadeswap::TokenPair {
+                            token_0: token_type("token_a"),
+                            token_1: token_type("token_b"),
+                        },
+                        amount_0: Uint128::new(amount_0),
+                        amount_1: Uint128::new(amount_1),
+                        total_liquidity: Uint128::new(2_000),
+                        contract_version: 1,
+                    })
+                    .unwrap(),
+                )),
+                _ => SystemResult::Err(SystemError::Unknown {}),
+            }
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    }
+}
+
+/* Adapter state holding `lp_balance` LP,
+ * the pool holds 1000 token_a & 4000 token_b
+ */
+fn setup(lp_balance: u128) -> OwnedDeps<MockStorage, MockApi, MockQuerier> {
+    let mut deps = mock_dependencies();
+    deps.querier
+        .update_wasm(mock_wasm(lp_balance, 0, 1_000, 4_000));
+
+    CONFIG
+        .save(deps.as_mut().storage, &Config {
+            admin: Addr::unchecked("admin"),
+            treasury: Addr::unchecked("treasury"),
+            pair: contract("pair"),
+            token_a: contract("token_a"),
+            token_b: contract("token_b"),
+            liquidity_token: contract("lp_token"),
+            staking_contract: None,
+            reward_token: None,
+            split: None,
+        })
+        .unwrap();
+    SELF_ADDRESS
+        .save(deps.as_mut().storage, &Addr::unchecked("adapter"))
+        .unwrap();
+    VIEWING_KEY
+        .save(deps.as_mut().storage, &"key".to_string())
+        .unwrap();
+    for token in ["token_a", "token_b", "lp_token"] {
+        UNBONDING
+            .save(
+                deps.as_mut().storage,
+                Addr::unchecked(token),
+                &Uint128::zero(),
+            )
+            .unwrap();
+    }
+
+    deps
+}
+
+#[test]
+fn unbond_more_than_position() {
+    let mut deps = setup(100);
+    let lp_token = Addr::unchecked("lp_token");
+
+    assert!(
+        execute::unbond(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("manager", &[]),
+            lp_token.clone(),
+            Uint128::new(150),
+        )
+        .is_err()
+    );
+
+    execute::unbond(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        lp_token.clone(),
+        Uint128::new(60),
+    )
+    .unwrap();
+
+    // Only 40 is left once the first unbonding is accounted for
+    assert!(
+        execute::unbond(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("manager", &[]),
+            lp_token.clone(),
+            Uint128::new(60),
+        )
+        .is_err()
+    );
+
+    assert_eq!(
+        UNBONDING.load(deps.as_ref().storage, lp_token).unwrap(),
+        Uint128::new(60)
+    );
+}
+
+#[test]
+fn unbond_across_assets() {
+    let mut deps = setup(100);
+
+    // 30 token_a is 60 of the 100 LP
+    unbond(&mut deps, "token_a", 30).unwrap();
+
+    // 100 token_b would need 50 LP, only 40 is left
+    assert!(unbond(&mut deps, "token_b", 100).is_err());
+    unbond(&mut deps, "token_b", 80).unwrap();
+
+    // Nothing is left for the LP token itself
+    assert!(unbond(&mut deps, "lp_token", 1).is_err());
+}
+
+#[test]
+fn balance_at_pool_ratio() {
+    let deps = setup(100);
+
+    // 100 of 2000 LP is 5% of each reserve
+    assert_eq!(balance(&deps, "lp_token"), Uint128::new(100));
+    assert_eq!(balance(&deps, "token_a"), Uint128::new(50));
+    assert_eq!(balance(&deps, "token_b"), Uint128::new(200));
+}
+
+#[test]
+fn impermanent_loss_after_price_shift() {
+    let mut deps = setup(100);
+
+    execute::receive(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("lp_token", &[]),
+        Addr::unchecked("treasury"),
+        Addr::unchecked("treasury"),
+        Uint128::new(100),
+        None,
+    )
+    .unwrap();
+
+    // Nothing is lost while the ratio is unchanged
+    match query::impermanent_loss(deps.as_ref()).unwrap() {
+        QueryAnswer::ImpermanentLoss {
+            held_value,
+            lp_value,
+            loss,
+        } => {
+            assert_eq!(held_value, lp_value);
+            assert_eq!(loss, Decimal::zero());
+        }
+        _ => panic!("Unexpected query answer"),
+    }
+
+    // token_a 4x in price against token_b, same constant product
+    deps.querier.update_wasm(mock_wasm(100, 0, 500, 8_000));
+
+    match query::impermanent_loss(deps.as_ref()).unwrap() {
+        QueryAnswer::ImpermanentLoss {
+            held_value,
+            lp_value,
+            loss,
+        } => {
+            assert_eq!(held_value, Uint128::new(1_000));
+            assert_eq!(lp_value, Uint128::new(800));
+            assert_eq!(loss, Decimal::percent(20));
+        }
+        _ => panic!("Unexpected query answer"),
+    }
+}
+
+#[test]
+fn impermanent_loss_after_partial_unbond() {
+    let mut deps = setup(100);
+
+    execute::receive(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("lp_token", &[]),
+        Addr::unchecked("treasury"),
+        Addr::unchecked("treasury"),
+        Uint128::new(100),
+        None,
+    )
+    .unwrap();
+
+    execute::unbond(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        Addr::unchecked("lp_token"),
+        Uint128::new(40),
+    )
+    .unwrap();
+
+    // 40% of the LP left, so 40% of what it was entered with did too
+    assert_eq!(
+        ENTRY_AMOUNTS.load(deps.as_ref().storage).unwrap(),
+        (Uint128::new(30), Uint128::new(120))
+    );
+
+    // token_a 4x in price against token_b, same constant product
+    deps.querier.update_wasm(mock_wasm(100, 0, 500, 8_000));
+
+    // The remaining 60 LP against the remaining entry, the loss is unchanged by the unbond
+    match query::impermanent_loss(deps.as_ref()).unwrap() {
+        QueryAnswer::ImpermanentLoss {
+            held_value,
+            lp_value,
+            loss,
+        } => {
+            assert_eq!(held_value, Uint128::new(600));
+            assert_eq!(lp_value, Uint128::new(480));
+            assert_eq!(loss, Decimal::percent(20));
+        }
+        _ => panic!("Unexpected query answer"),
+    }
+}
+
+#[test]
+fn reserves_within_unbondable() {
+    let mut deps = setup(100);
+
+    execute::unbond(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("manager", &[]),
+        Addr::unchecked("token_a"),
+        Uint128::new(30),
+    )
+    .unwrap();
+
+    // Underlying token_a is 50, 30 of it is already unbonding
+    assert_eq!(reserves(&deps, "token_a"), Uint128::new(20));
+    assert_eq!(reserves(&deps, "token_b"), Uint128::new(200));
+    assert_eq!(reserves(&deps, "lp_token"), Uint128::new(100));
+
+    for asset in ["token_a", "token_b", "lp_token"] {
+        assert!(reserves(&deps, asset) <= unbondable(&deps, asset));
+    }
+}
+
+#[test]
+fn staked_lp_unbondable_not_reserves() {
+    let mut deps = setup(100);
+    deps.querier.update_wasm(mock_wasm(100, 60, 1_000, 4_000));
+    let mut config = CONFIG.load(deps.as_ref().storage).unwrap();
+    config.staking_contract = Some(contract("staking"));
+    CONFIG.save(deps.as_mut().storage, &config).unwrap();
+
+    // 160 LP in the position, only the 100 held by the adapter can be burned right away
+    assert_eq!(unbondable(&deps, "lp_token"), Uint128::new(160));
+    assert_eq!(reserves(&deps, "lp_token"), Uint128::new(100));
+    assert_eq!(unbondable(&deps, "token_a"), Uint128::new(80));
+    assert_eq!(reserves(&deps, "token_a"), Uint128::new(50));
+}
@@ -12,6 +12,7 @@ use crate::{
         profile::{try_add_profile, try_set_profile},
         proposal::{
             try_cancel,
+            try_cancel_proposal,
             try_claim_funding,
             try_receive_funding,
             try_receive_vote,
@@ -48,7 +49,7 @@ use shade_protocol::{
         QueryMsg,
         MSG_VARIABLE,
     },
-    governance::{errors::Error, AuthQuery, QueryData, RuntimeState},
+    governance::{errors::Error, AuthQuery, QueryData, RuntimeState, SchemaVersion},
     query_auth::helpers::{authenticate_permit, authenticate_vk, PermitAuthentication},
     snip20::helpers::register_receive,
     utils::{
@@ -63,6 +64,9 @@ use shade_protocol::{
 // Used to pad up responses for better privacy.
 pub const RESPONSE_BLOCK_SIZE: usize = 256;
 
+/// Storage layout written by this code
+pub const SCHEMA_VERSION: u64 = 1;
+
 #[shd_entry_point]
 pub fn instantiate(
     deps: DepsMut,
@@ -100,6 +104,7 @@ pub fn instantiate(
         funding_token: msg.funding_token.clone(),
         migrated_from,
         migrated_to: None,
+        voting_period_bounds: None,
     }
     .save(deps.storage)?;
 
@@ -192,6 +197,7 @@ pub fn instantiate(
 
     // Set runtime
     RuntimeState::Normal.save(deps.storage)?;
+    SchemaVersion(SCHEMA_VERSION).save(deps.storage)?;
 
     Ok(Response::new()
         .add_submessages(messages)
@@ -206,6 +212,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
     match msg {
         ExecuteMsg::Trigger { .. } // Will be deprecated
         | ExecuteMsg::Cancel { .. } // Will also be deprecated
+        | ExecuteMsg::CancelProposal { .. } // Gets halted
         | ExecuteMsg::Update { .. } // Gets halted 
         | ExecuteMsg::Receive { .. } // Gets halted
         | ExecuteMsg::ClaimFunding { .. } // Gets halted
@@ -226,6 +233,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 treasury,
                 vote_token,
                 funding_token,
+                voting_period_bounds,
                 ..
             } => try_set_config(
                 deps,
@@ -235,6 +243,7 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 treasury,
                 vote_token,
                 funding_token,
+                voting_period_bounds,
             ),
 
             ExecuteMsg::SetRuntimeState { state, .. } => {
@@ -244,6 +253,9 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             // Proposals
             ExecuteMsg::Trigger { proposal, .. } => try_trigger(deps, env, info, proposal),
             ExecuteMsg::Cancel { proposal, .. } => try_cancel(deps, env, info, proposal),
+            ExecuteMsg::CancelProposal { proposal, .. } => {
+                try_cancel_proposal(deps, env, info, proposal)
+            }
             ExecuteMsg::Update { proposal, .. } => try_update(deps, env, info, proposal),
             ExecuteMsg::Receive {
                 sender,
@@ -272,8 +284,18 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
                 title,
                 metadata,
                 msgs,
+                voting_period,
                 ..
-            } => try_assembly_proposal(deps, env, info, assembly, title, metadata, msgs),
+            } => try_assembly_proposal(
+                deps,
+                env,
+                info,
+                assembly,
+                title,
+                metadata,
+                msgs,
+                voting_period,
+            ),
 
             ExecuteMsg::AddAssembly {
                 name,
@@ -372,12 +394,18 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
 }
 
 #[shd_entry_point]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     pad_query_result(
         match msg {
             QueryMsg::TotalProposals {} => to_binary(&query::total_proposals(deps)?),
 
-            QueryMsg::Proposals { start, end } => to_binary(&query::proposals(deps, start, end)?),
+            QueryMsg::Proposals { start, end, status } => {
+                to_binary(&query::proposals(deps, &env, start, end, status)?)
+            }
+
+            QueryMsg::ProposalVotes { proposal } => {
+                to_binary(&query::proposal_votes(deps, proposal)?)
+            }
 
             QueryMsg::TotalAssemblies {} => to_binary(&query::total_assemblies(deps)?),
 
@@ -399,6 +427,8 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
 
             QueryMsg::Config {} => to_binary(&query::config(deps)?),
 
+            QueryMsg::Version {} => to_binary(&query::version(deps)?),
+
             QueryMsg::WithVK { user, key, query } => {
                 // Query VK info
                 let authenticator = Config::load(deps.storage)?.query;
@@ -1,4 +1,4 @@
-use crate::handle::authorize_assembly;
+use crate::handle::{authorize_assembly, proposal::total_voting_power};
 use shade_protocol::{
     c_std::{
         from_binary,
@@ -19,11 +19,12 @@ use shade_protocol::{
         proposal::{Proposal, ProposalMsg, Status},
         stored_id::{UserID, ID},
         vote::Vote,
+        Config,
         ExecuteAnswer,
         MSG_VARIABLE,
     },
     governance::errors::Error,
-    utils::generic_response::ResponseStatus,
+    utils::{generic_response::ResponseStatus, storage::plus::ItemStorage},
 };
 
 pub fn try_assembly_vote(
@@ -83,10 +84,19 @@ pub fn try_assembly_proposal(
     title: String,
     metadata: String,
     msgs: Option<Vec<ProposalMsg>>,
+    voting_period: Option<u64>,
 ) -> StdResult<Response> {
     // Get assembly
     let assembly_data = authorize_assembly(deps.storage, &info, assembly_id)?;
 
+    // Custom voting periods must stay within what the config allows
+    if let Some(period) = voting_period {
+        match Config::load(deps.storage)?.voting_period_bounds {
+            Some(bounds) if bounds.min <= period && period <= bounds.max => {}
+            _ => return Err(Error::invalid_voting_period(vec![&period.to_string()])),
+        }
+    }
+
     // Get profile
     // Check if assembly is enabled
     let profile = Profile::data(deps.storage, assembly_data.profile)?;
@@ -97,7 +107,7 @@ pub fn try_assembly_proposal(
     if let Some(vote_settings) = Profile::assembly_voting(deps.storage, assembly_data.profile)? {
         status = Status::AssemblyVote {
             start: env.block.time.seconds(),
-            end: env.block.time.seconds() + vote_settings.deadline,
+            end: env.block.time.seconds() + voting_period.unwrap_or(vote_settings.deadline),
         }
     }
     // Check if funding
@@ -113,7 +123,7 @@ pub fn try_assembly_proposal(
     {
         status = Status::Voting {
             start: env.block.time.seconds(),
-            end: env.block.time.seconds() + vote_settings.deadline,
+            end: env.block.time.seconds() + voting_period.unwrap_or(vote_settings.deadline),
         }
     }
     // Else push directly to passed
@@ -124,6 +134,12 @@ pub fn try_assembly_proposal(
         }
     }
 
+    // Update only snapshots on the way into voting, so proposals that start there take it now
+    let voting_power = match status {
+        Status::Voting { .. } => Some(total_voting_power(deps.as_ref())?),
+        _ => None,
+    };
+
     let processed_msgs: Option<Vec<ProposalMsg>>;
     if let Some(msgs) = msgs.clone() {
         let mut new_msgs = vec![];
@@ -168,6 +184,8 @@ pub fn try_assembly_proposal(
         public_vote_tally: None,
         status,
         status_history: vec![],
+        voting_power,
+        voting_period,
         funders: None,
     };
 
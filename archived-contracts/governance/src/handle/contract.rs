@@ -1,10 +1,31 @@
 use shade_protocol::{
-    c_std::{to_binary, DepsMut, Env, MessageInfo, Response, StdResult},
-    contract_interfaces::governance::{contract::AllowedContract, stored_id::ID, ExecuteAnswer},
+    c_std::{to_binary, DepsMut, Env, MessageInfo, Response, StdResult, Storage},
+    contract_interfaces::governance::{
+        contract::{AllowedContract, AllowedContractDescription},
+        stored_id::ID,
+        ExecuteAnswer,
+    },
     governance::errors::Error,
-    utils::{asset::Contract, generic_response::ResponseStatus},
+    utils::{asset::Contract, generic_response::ResponseStatus, storage::plus::MapStorage},
 };
 
+/// Errors if another contract is already registered under this name
+fn check_name_free(storage: &dyn Storage, name: &str, skip: Option<u16>) -> StdResult<()> {
+    for id in 0..=ID::contract(storage)? {
+        if Some(id) == skip {
+            continue;
+        }
+        // Migrating contracts can have IDs reserved before their data arrives
+        if let Some(desc) = AllowedContractDescription::may_load(storage, id)? {
+            if desc.name == name {
+                return Err(Error::contract_name_taken(vec![name]));
+            }
+        }
+    }
+
+    Ok(())
+}
+
 pub fn try_add_contract(
     deps: DepsMut,
     _env: Env,
@@ -14,6 +35,8 @@ pub fn try_add_contract(
     contract: Contract,
     assemblies: Option<Vec<u16>>,
 ) -> StdResult<Response> {
+    check_name_free(deps.storage, &name, None)?;
+
     let id = ID::add_contract(deps.storage)?;
 
     if let Some(ref assemblies) = assemblies {
@@ -61,6 +84,7 @@ pub fn try_set_contract(
     let mut allowed_contract = AllowedContract::load(deps.storage, id)?;
 
     if let Some(name) = name {
+        check_name_free(deps.storage, &name, Some(id))?;
         allowed_contract.name = name;
     }
 
@@ -1,6 +1,6 @@
 use shade_protocol::{
     c_std::{to_binary, Addr, DepsMut, Env, MessageInfo, Response, StdResult, Storage, SubMsg},
-    contract_interfaces::governance::{Config, ExecuteAnswer, RuntimeState},
+    contract_interfaces::governance::{Config, ExecuteAnswer, RuntimeState, VotingPeriodBounds},
     governance::{
         assembly::{Assembly, AssemblyData},
         errors::Error,
@@ -77,6 +77,7 @@ pub fn try_set_config(
     treasury: Option<Addr>,
     vote_token: Option<Contract>,
     funding_token: Option<Contract>,
+    voting_period_bounds: Option<VotingPeriodBounds>,
 ) -> StdResult<Response> {
     let mut messages = vec![];
     let mut config = Config::load(deps.storage)?;
@@ -108,6 +109,10 @@ pub fn try_set_config(
         config.query = query_auth;
     }
 
+    if let Some(bounds) = voting_period_bounds {
+        config.voting_period_bounds = Some(bounds);
+    }
+
     config.save(deps.storage)?;
     Ok(Response::new()
         .set_data(to_binary(&ExecuteAnswer::SetConfig {
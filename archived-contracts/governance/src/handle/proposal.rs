@@ -5,6 +5,7 @@ use shade_protocol::{
         to_binary,
         Addr,
         Binary,
+        Deps,
         DepsMut,
         Env,
         MessageInfo,
@@ -34,7 +35,7 @@ use shade_protocol::{
 
 pub fn try_trigger(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     proposal: u32,
 ) -> StdResult<Response> {
@@ -52,9 +53,15 @@ pub fn try_trigger(
         if let Some(prop_msgs) = proposal_msg {
             for (_i, prop_msg) in prop_msgs.iter().enumerate() {
                 let contract = AllowedContract::data(deps.storage, prop_msg.target)?.contract;
+                // Self targeted msgs use our current code hash, the stored one goes stale on upgrades
+                let code_hash = if contract.address == env.contract.address {
+                    env.contract.code_hash.clone()
+                } else {
+                    contract.code_hash
+                };
                 let msg = WasmMsg::Execute {
                     contract_addr: contract.address.into(),
-                    code_hash: contract.code_hash,
+                    code_hash,
                     msg: prop_msg.msg.clone(),
                     funds: prop_msg.send.clone(),
                 };
@@ -99,6 +106,64 @@ pub fn try_cancel(
     })?))
 }
 
+pub fn try_cancel_proposal(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    proposal: u32,
+) -> StdResult<Response> {
+    // Governance itself can also withdraw a proposal
+    let proposer = Proposal::description(deps.storage, proposal)?.proposer;
+    if info.sender != proposer && info.sender != env.contract.address {
+        return Err(Error::not_proposer(vec![&proposal.to_string()]));
+    }
+
+    let status = Proposal::status(deps.storage, proposal)?;
+    if let Status::Funding { .. } = status {
+        assembly_state_valid(deps.storage, Proposal::assembly(deps.storage, proposal)?)?;
+
+        let mut history = Proposal::status_history(deps.storage, proposal)?;
+        history.push(status);
+        Proposal::save_status_history(deps.storage, proposal, history)?;
+        Proposal::save_status(deps.storage, proposal, Status::Canceled)?;
+    } else {
+        return Err(Error::no_funding_state(vec![]));
+    }
+
+    // Refund everyone up front, marking it as claimed
+    let mut messages = vec![];
+    let funders = Proposal::funders(deps.storage, proposal)?;
+    if !funders.is_empty() {
+        let funding_token = match Config::load(deps.storage)?.funding_token {
+            None => return Err(Error::missing_funding_token(vec![])),
+            Some(token) => token,
+        };
+
+        for funder in funders {
+            let mut funding = Proposal::funding(deps.storage, proposal, &funder)?;
+            if funding.claimed || funding.amount.is_zero() {
+                continue;
+            }
+            messages.push(send_msg(
+                funder.clone(),
+                funding.amount.into(),
+                None,
+                None,
+                None,
+                &funding_token,
+            )?);
+            funding.claimed = true;
+            Proposal::save_funding(deps.storage, proposal, &funder, funding)?;
+        }
+    }
+
+    Ok(Response::new().add_messages(messages).set_data(to_binary(
+        &ExecuteAnswer::CancelProposal {
+            status: ResponseStatus::Success,
+        },
+    )?))
+}
+
 fn validate_votes(votes: Vote, total_power: Uint128, settings: VoteProfile) -> Status {
     let tally = TalliedVotes::tally(votes);
 
@@ -138,17 +203,30 @@ fn validate_votes(votes: Vote, total_power: Uint128, settings: VoteProfile) -> S
     return new_status;
 }
 
+// Total staked on the vote token
+pub fn total_voting_power(deps: Deps) -> StdResult<Uint128> {
+    let vote_token = match Config::load(deps.storage)?.vote_token {
+        Some(token) => token,
+        None => return Err(Error::missing_voting_token(vec![])),
+    };
+
+    let query: snip20_staking::QueryAnswer =
+        snip20_staking::QueryMsg::TotalStaked {}.query(&deps.querier, &vote_token)?;
+
+    match query {
+        snip20_staking::QueryAnswer::TotalStaked { tokens, .. } => Ok(tokens.into()),
+        _ => Err(Error::unexpected_query_response(vec![])),
+    }
+}
+
 pub fn try_update(
     deps: DepsMut,
     env: Env,
     _info: MessageInfo,
     proposal: u32,
 ) -> StdResult<Response> {
-    // TODO: see if this can get cleaned up
-
     let mut history = Proposal::status_history(deps.storage, proposal)?;
     let status = Proposal::status(deps.storage, proposal)?;
-    let mut new_status: Status;
 
     let assembly = Proposal::assembly(deps.storage, proposal)?;
     let profile = Assembly::data(deps.storage, assembly)?.profile;
@@ -158,7 +236,63 @@ pub fn try_update(
 
     let mut messages = vec![];
 
-    match status.clone() {
+    let new_status = next_status(deps.as_ref(), &env, proposal)?;
+
+    if let Status::Vetoed { slash_percent } = new_status {
+        // Send the funding amount to the treasury
+        if Profile::funding(deps.storage, profile)?.is_some() {
+            // Look for the history and find funding
+            for s in history.iter() {
+                // Check if it has funding history
+                if let Status::Funding { amount, .. } = s {
+                    let send_amount = amount.multiply_ratio(100000u128, slash_percent);
+                    if send_amount != Uint128::zero() {
+                        let config = Config::load(deps.storage)?;
+                        // Update slash amount
+                        messages.push(send_msg(
+                            config.treasury.into(),
+                            Uint128::new(send_amount.u128()),
+                            None,
+                            None,
+                            None,
+                            &config.funding_token.unwrap(),
+                        )?);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+
+    if let Status::Voting { .. } = new_status {
+        let power = total_voting_power(deps.as_ref())?;
+        Proposal::save_voting_power(deps.storage, proposal, power)?;
+    }
+
+    // Add old status to history
+    history.push(status);
+    Proposal::save_status_history(deps.storage, proposal, history)?;
+    // Save new status
+    Proposal::save_status(deps.storage, proposal, new_status.clone())?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::Update {
+        status: ResponseStatus::Success,
+    })?))
+}
+
+// The status update would move the proposal to right now, errors if its current one can't be
+// updated yet
+pub fn next_status(deps: Deps, env: &Env, proposal: u32) -> StdResult<Status> {
+    // TODO: see if this can get cleaned up
+
+    let history = Proposal::status_history(deps.storage, proposal)?;
+    let status = Proposal::status(deps.storage, proposal)?;
+    let new_status: Status;
+
+    let assembly = Proposal::assembly(deps.storage, proposal)?;
+    let profile = Assembly::data(deps.storage, assembly)?.profile;
+
+    match status {
         Status::AssemblyVote { start: _, end } => {
             if end > env.block.time.seconds() {
                 return Err(Error::cannot_update(vec!["AssemblyVote", &end.to_string()]));
@@ -194,7 +328,9 @@ pub fn try_update(
                 } else if let Some(setting) = Profile::public_voting(deps.storage, profile)? {
                     vote_conclusion = Status::Voting {
                         start: env.block.time.seconds(),
-                        end: env.block.time.seconds() + setting.deadline,
+                        end: env.block.time.seconds()
+                            + Proposal::voting_period(deps.storage, proposal)?
+                                .unwrap_or(setting.deadline),
                     }
                 } else {
                     vote_conclusion = Status::Passed {
@@ -208,12 +344,13 @@ pub fn try_update(
             new_status = vote_conclusion;
         }
         Status::Funding { amount, end, .. } => {
+            let mut funding_status: Status;
             // This helps combat the possibility of the profile changing
             // before another proposal is finished
             if let Some(setting) = Profile::funding(deps.storage, profile)? {
                 // Check if deadline or funding limit reached
                 if amount >= setting.required {
-                    new_status = Status::Passed {
+                    funding_status = Status::Passed {
                         start: env.block.time.seconds(),
                         end: env.block.time.seconds()
                             + Profile::data(deps.storage, profile)?.cancel_deadline,
@@ -221,40 +358,40 @@ pub fn try_update(
                 } else if end > env.block.time.seconds() {
                     return Err(Error::cannot_update(vec!["Funding", &end.to_string()]));
                 } else {
-                    new_status = Status::Expired;
+                    funding_status = Status::Expired;
                 }
             } else {
-                new_status = Status::Passed {
+                funding_status = Status::Passed {
                     start: env.block.time.seconds(),
                     end: env.block.time.seconds()
                         + Profile::data(deps.storage, profile)?.cancel_deadline,
                 }
             }
 
-            if let Status::Passed { .. } = new_status {
+            if let Status::Passed { .. } = funding_status {
                 if let Some(setting) = Profile::public_voting(deps.storage, profile)? {
-                    new_status = Status::Voting {
+                    funding_status = Status::Voting {
                         start: env.block.time.seconds(),
-                        end: env.block.time.seconds() + setting.deadline,
+                        end: env.block.time.seconds()
+                            + Proposal::voting_period(deps.storage, proposal)?
+                                .unwrap_or(setting.deadline),
                     }
                 }
             }
+
+            new_status = funding_status;
         }
         Status::Voting { start: _, end } => {
             if end > env.block.time.seconds() {
                 return Err(Error::cannot_update(vec!["Voting", &end.to_string()]));
             }
 
-            let config = Config::load(deps.storage)?;
             let votes = Proposal::public_votes(deps.storage, proposal)?;
 
-            let query: snip20_staking::QueryAnswer = snip20_staking::QueryMsg::TotalStaked {}
-                .query(&deps.querier, &config.vote_token.unwrap())?;
-
-            // Get total staking power
-            let total_power = match query {
-                snip20_staking::QueryAnswer::TotalStaked { tokens, .. } => tokens.into(),
-                _ => return Err(Error::unexpected_query_response(vec![])),
+            // Use the power snapshotted when voting opened so late stake changes can't move quorum
+            let total_power = match Proposal::voting_power(deps.storage, proposal)? {
+                Some(power) => power,
+                None => total_voting_power(deps)?,
             };
 
             let mut vote_conclusion: Status;
@@ -266,32 +403,13 @@ pub fn try_update(
             }
 
             if let Status::Vetoed { .. } = vote_conclusion {
-                // Send the funding amount to the treasury
+                // Funded proposals lose part of their deposit
                 if let Some(profile) = Profile::funding(deps.storage, profile)? {
                     // Look for the history and find funding
-                    for s in history.iter() {
-                        // Check if it has funding history
-                        if let Status::Funding { amount, .. } = s {
-                            let loss = profile.veto_deposit_loss.clone();
-                            vote_conclusion = Status::Vetoed {
-                                slash_percent: loss,
-                            };
-
-                            let send_amount = amount.multiply_ratio(100000u128, loss);
-                            if send_amount != Uint128::zero() {
-                                let config = Config::load(deps.storage)?;
-                                // Update slash amount
-                                messages.push(send_msg(
-                                    config.treasury.into(),
-                                    Uint128::new(send_amount.u128()),
-                                    None,
-                                    None,
-                                    None,
-                                    &config.funding_token.unwrap(),
-                                )?);
-                            }
-                            break;
-                        }
+                    if history.iter().any(|s| matches!(s, Status::Funding { .. })) {
+                        vote_conclusion = Status::Vetoed {
+                            slash_percent: profile.veto_deposit_loss.clone(),
+                        };
                     }
                 }
             } else if let Status::Success = vote_conclusion {
@@ -307,15 +425,7 @@ pub fn try_update(
         _ => return Err(Error::state_update(vec![])),
     }
 
-    // Add old status to history
-    history.push(status);
-    Proposal::save_status_history(deps.storage, proposal, history)?;
-    // Save new status
-    Proposal::save_status(deps.storage, proposal, new_status.clone())?;
-
-    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::Update {
-        status: ResponseStatus::Success,
-    })?))
+    Ok(new_status)
 }
 
 pub fn try_receive_funding(
@@ -441,7 +551,7 @@ pub fn try_claim_funding(
         _ => Uint128::zero(),
     };
 
-    let funding = Proposal::funding(deps.storage, id, &info.sender)?;
+    let mut funding = Proposal::funding(deps.storage, id, &info.sender)?;
 
     if funding.claimed {
         return Err(Error::funding_claimed(vec![]));
@@ -462,6 +572,9 @@ pub fn try_claim_funding(
         Some(token) => token,
     };
 
+    funding.claimed = true;
+    Proposal::save_funding(deps.storage, id, &info.sender, funding)?;
+
     Ok(Response::new()
         .add_message(send_msg(
             info.sender.into(),
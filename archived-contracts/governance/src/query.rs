@@ -1,16 +1,18 @@
+use crate::handle::proposal::next_status;
 use shade_protocol::{
-    c_std::{Addr, Deps, StdResult},
+    c_std::{Addr, Deps, Env, StdResult},
     contract_interfaces::governance::{
         assembly::{Assembly, AssemblyMsg},
         contract::AllowedContract,
         profile::Profile,
-        proposal::Proposal,
+        proposal::{Proposal, Status, StatusFilter},
         stored_id::ID,
         Config,
         QueryAnswer,
+        SchemaVersion,
     },
     governance::{errors::Error, stored_id::UserID, Pagination, ResponseWithID},
-    utils::storage::plus::ItemStorage,
+    utils::{storage::plus::ItemStorage, version::VersionResponse},
 };
 use std::cmp::min;
 
@@ -20,13 +22,30 @@ pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
+pub fn version(deps: Deps) -> StdResult<VersionResponse> {
+    Ok(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        // contracts instantiated before versioning was added are v1
+        schema: SchemaVersion::may_load(deps.storage)?.map_or(1, |v| v.0),
+    })
+}
+
 pub fn total_proposals(deps: Deps) -> StdResult<QueryAnswer> {
     Ok(QueryAnswer::Total {
         total: ID::proposal(deps.storage)?.checked_add(1).unwrap(),
     })
 }
 
-pub fn proposals(deps: Deps, start: u32, end: u32) -> StdResult<QueryAnswer> {
+// Most proposals returned by a single Proposals query
+pub const MAX_PROPOSALS_QUERY: u32 = 30;
+
+pub fn proposals(
+    deps: Deps,
+    env: &Env,
+    start: u32,
+    end: u32,
+    status: Option<StatusFilter>,
+) -> StdResult<QueryAnswer> {
     let mut items = vec![];
     let total = ID::proposal(deps.storage)?;
 
@@ -34,11 +53,55 @@ pub fn proposals(deps: Deps, start: u32, end: u32) -> StdResult<QueryAnswer> {
         return Err(Error::item_not_found(vec![&start.to_string(), "Proposal"]));
     }
 
-    for i in start..=min(end, total) {
+    let last = min(end, start.saturating_add(MAX_PROPOSALS_QUERY - 1));
+
+    // Only the window is scanned, a status just narrows down what's returned from it
+    for i in start..=min(last, total) {
+        if let Some(status) = &status {
+            if !status.matches(&effective_status(deps, env, i)?) {
+                continue;
+            }
+        }
         items.push(Proposal::load(deps.storage, i)?);
     }
 
-    Ok(QueryAnswer::Proposals { props: items })
+    let mut next = None;
+    if last < total {
+        next = Some(last + 1);
+    }
+
+    Ok(QueryAnswer::Proposals { props: items, next })
+}
+
+// Periods that are over resolve to what an update would move them to, so proposals waiting on
+// one don't drop out of every filter
+fn effective_status(deps: Deps, env: &Env, proposal: u32) -> StdResult<Status> {
+    let status = Proposal::status(deps.storage, proposal)?;
+    let now = env.block.time.seconds();
+    match status {
+        Status::AssemblyVote { end, .. }
+        | Status::Funding { end, .. }
+        | Status::Voting { end, .. }
+            if end <= now =>
+        {
+            next_status(deps, env, proposal)
+        }
+        status => Ok(status),
+    }
+}
+
+pub fn proposal_votes(deps: Deps, proposal: u32) -> StdResult<QueryAnswer> {
+    if proposal > ID::proposal(deps.storage)? {
+        return Err(Error::item_not_found(vec![
+            &proposal.to_string(),
+            "Proposal",
+        ]));
+    }
+
+    Ok(QueryAnswer::ProposalVotes {
+        votes: Proposal::public_votes(deps.storage, proposal)?,
+        total_power: Proposal::voting_power(deps.storage, proposal)?,
+    })
 }
 
 pub fn total_profiles(deps: Deps) -> StdResult<QueryAnswer> {
@@ -33,6 +33,66 @@ fn add_contract() {
     assert_eq!(contracts.len(), 2);
 }
 #[test]
+fn add_contract_duplicate_name() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    for (name, address) in [("Alpha", "alpha_contract"), ("Beta", "beta_contract")] {
+        governance::ExecuteMsg::AddContract {
+            name: name.to_string(),
+            metadata: "some description".to_string(),
+            contract: Contract {
+                address: Addr::unchecked(address),
+                code_hash: "hash".to_string(),
+            },
+            assemblies: None,
+            padding: None,
+        }
+        .test_exec(
+            // Sender is self
+            &gov,
+            &mut chain,
+            gov.address.clone(),
+            &[],
+        )
+        .unwrap();
+    }
+
+    // Names are unique, including against governance itself
+    for name in ["Alpha", "Governance"] {
+        assert!(
+            governance::ExecuteMsg::AddContract {
+                name: name.to_string(),
+                metadata: "some description".to_string(),
+                contract: Contract {
+                    address: Addr::unchecked("other_contract"),
+                    code_hash: "hash".to_string(),
+                },
+                assemblies: None,
+                padding: None,
+            }
+            .test_exec(
+                // Sender is self
+                &gov,
+                &mut chain,
+                gov.address.clone(),
+                &[]
+            )
+            .is_err()
+        );
+    }
+
+    let contracts = get_contract(&mut chain, &gov, 1, 5).unwrap();
+
+    let listed: Vec<(String, Addr)> = contracts
+        .into_iter()
+        .map(|c| (c.name, c.contract.address))
+        .collect();
+    assert_eq!(listed, vec![
+        ("Alpha".to_string(), Addr::unchecked("alpha_contract")),
+        ("Beta".to_string(), Addr::unchecked("beta_contract")),
+    ]);
+}
+#[test]
 fn unauthorised_add_contract() {
     let (mut chain, gov) = admin_only_governance().unwrap();
 
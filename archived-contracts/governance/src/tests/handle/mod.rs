@@ -32,6 +32,7 @@ fn set_config_msg() {
             address: snip20.address,
             code_hash: snip20.code_hash,
         }),
+        voting_period_bounds: None,
         padding: None,
     }
     .test_exec(
@@ -60,6 +61,7 @@ fn unauthorised_set_config_msg() {
             treasury: None,
             funding_token: None,
             vote_token: None,
+            voting_period_bounds: None,
             padding: None,
         }
         .test_exec(
@@ -90,6 +92,7 @@ fn reject_disable_config_tokens() {
             address: snip20.address,
             code_hash: snip20.code_hash,
         }),
+        voting_period_bounds: None,
         padding: None,
     }
     .test_exec(
@@ -108,6 +111,7 @@ fn reject_disable_config_tokens() {
         treasury: None,
         funding_token: None,
         vote_token: None,
+        voting_period_bounds: None,
         padding: None,
     }
     .test_exec(
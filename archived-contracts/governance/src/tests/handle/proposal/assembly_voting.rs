@@ -9,6 +9,7 @@ use shade_protocol::{
             proposal::Status,
             vote::Vote,
             InstantiateMsg,
+            VotingPeriodBounds,
         },
         query_auth,
     },
@@ -99,6 +100,7 @@ pub fn init_assembly_governance_with_proposal() -> StdResult<(App, ContractInfo)
         title: "Title".to_string(),
         metadata: "Text only proposal".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
@@ -795,6 +797,7 @@ fn vote_count_percentage() {
         title: "Title".to_string(),
         metadata: "Text only proposal".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
@@ -842,3 +845,61 @@ fn vote_count_percentage() {
         _ => assert!(false),
     };
 }
+
+#[test]
+fn custom_voting_period() {
+    let (mut chain, gov) = init_assembly_governance_with_proposal().unwrap();
+
+    let proposal = |chain: &mut App, voting_period: u64| {
+        governance::ExecuteMsg::AssemblyProposal {
+            assembly: 1,
+            title: "Emergency".to_string(),
+            metadata: "Text only proposal".to_string(),
+            msgs: None,
+            voting_period: Some(voting_period),
+            padding: None,
+        }
+        .test_exec(&gov, chain, Addr::unchecked("alpha"), &[])
+    };
+
+    // Overrides are refused until bounds are configured
+    assert!(proposal(&mut chain, 1000).is_err());
+
+    governance::ExecuteMsg::SetConfig {
+        query_auth: None,
+        treasury: None,
+        funding_token: None,
+        vote_token: None,
+        voting_period_bounds: Some(VotingPeriodBounds {
+            min: 500,
+            max: 20000,
+        }),
+        padding: None,
+    }
+    .test_exec(
+        // Sender is self
+        &gov,
+        &mut chain,
+        gov.address.clone(),
+        &[],
+    )
+    .unwrap();
+
+    assert!(proposal(&mut chain, 100).is_err());
+    assert!(proposal(&mut chain, 30000).is_err());
+    proposal(&mut chain, 1000).unwrap();
+
+    let props = get_proposals(&mut chain, &gov, 0, 2).unwrap();
+
+    // The first proposal keeps the profile deadline
+    match props[0].status {
+        Status::AssemblyVote { start, end } => assert_eq!(end - start, 10000),
+        _ => assert!(false),
+    };
+
+    assert_eq!(props[1].voting_period, Some(1000));
+    match props[1].status {
+        Status::AssemblyVote { start, end } => assert_eq!(end - start, 1000),
+        _ => assert!(false),
+    };
+}
@@ -1,4 +1,9 @@
-use crate::tests::{get_proposals, handle::proposal::init_funding_token, init_chain};
+use crate::tests::{
+    get_proposals,
+    get_proposals_by_status,
+    handle::proposal::init_funding_token,
+    init_chain,
+};
 use shade_multi_test::multi::{governance::Governance, snip20::Snip20};
 use shade_protocol::{
     c_std::{to_binary, Addr, ContractInfo, StdResult, Uint128},
@@ -6,7 +11,7 @@ use shade_protocol::{
         governance,
         governance::{
             profile::{Count, FundProfile, Profile, UpdateProfile, UpdateVoteProfile},
-            proposal::Status,
+            proposal::{Status, StatusFilter},
             vote::Vote,
             InstantiateMsg,
         },
@@ -111,6 +116,7 @@ pub fn init_funding_governance_with_proposal()
         title: "Title".to_string(),
         metadata: "Text only proposal".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
@@ -191,6 +197,7 @@ fn assembly_to_funding_transition() {
         title: "Title".to_string(),
         metadata: "Text only proposal".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
@@ -293,6 +300,7 @@ fn fake_funding_token() {
             code_hash: other.code_hash,
         }),
         vote_token: None,
+        voting_period_bounds: None,
         padding: None,
     }
     .test_exec(
@@ -519,6 +527,60 @@ fn update_after_failed_funding() {
     };
 }
 #[test]
+fn update_at_deadline_underfunded() {
+    let (mut chain, gov, snip20, _auth) = init_funding_governance_with_proposal().unwrap();
+
+    // One short of the required 2000
+    snip20::ExecuteMsg::Send {
+        recipient: gov.address.clone().into(),
+        recipient_code_hash: None,
+        amount: Uint128::new(1999),
+        msg: Some(to_binary(&0).unwrap()),
+        memo: None,
+        padding: None,
+    }
+    .test_exec(
+        // Sender is self
+        &snip20,
+        &mut chain,
+        Addr::unchecked("alpha"),
+        &[],
+    )
+    .unwrap();
+
+    let end = match get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].status {
+        Status::Funding { end, .. } => end,
+        _ => panic!("Proposal is not funding"),
+    };
+
+    // Still open a second before the deadline
+    chain
+        .update_block(|block| block.time = block.time.plus_seconds(end - block.time.seconds() - 1));
+    assert!(
+        governance::ExecuteMsg::Update {
+            proposal: 0,
+            padding: None
+        }
+        .test_exec(&gov, &mut chain, Addr::unchecked("beta"), &[])
+        .is_err()
+    );
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(1));
+    governance::ExecuteMsg::Update {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("beta"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    match prop.status {
+        Status::Expired {} => assert!(true),
+        _ => assert!(false),
+    };
+}
+#[test]
 fn claim_when_not_finished() {
     let (mut chain, gov, snip20, _auth) = init_funding_governance_with_proposal().unwrap();
 
@@ -745,6 +807,7 @@ fn init_funding_governance_with_proposal_with_privacy()
         title: "Title".to_string(),
         metadata: "Text only proposal".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
@@ -779,3 +842,173 @@ fn funding_privacy() {
 
     assert!(prop.funders.is_none());
 }
+
+#[test]
+fn funding_status_filter() {
+    let (mut chain, gov, _snip20, _auth) = init_funding_governance_with_proposal().unwrap();
+
+    let matches = |chain: &mut App, status| {
+        get_proposals_by_status(chain, &gov, 0, 10, status)
+            .unwrap()
+            .len()
+    };
+
+    assert_eq!(matches(&mut chain, StatusFilter::Funding), 1);
+
+    let end = match get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].status {
+        Status::Funding { end, .. } => end,
+        _ => panic!("Proposal is not funding"),
+    };
+
+    // Past the deadline the proposal is filtered by what an update would resolve it to
+    chain.update_block(|block| block.time = block.time.plus_seconds(end - block.time.seconds()));
+    assert_eq!(matches(&mut chain, StatusFilter::Funding), 0);
+    assert_eq!(matches(&mut chain, StatusFilter::Expired), 1);
+
+    governance::ExecuteMsg::Update {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("beta"), &[])
+    .unwrap();
+    assert_eq!(matches(&mut chain, StatusFilter::Funding), 0);
+    assert_eq!(matches(&mut chain, StatusFilter::Expired), 1);
+}
+
+#[test]
+fn cancel_funding_proposal() {
+    let (mut chain, gov, snip20, _auth) = init_funding_governance_with_proposal().unwrap();
+
+    for (funder, amount) in [("alpha", 500), ("beta", 700)] {
+        snip20::ExecuteMsg::Send {
+            recipient: gov.address.clone().into(),
+            recipient_code_hash: None,
+            amount: Uint128::new(amount),
+            msg: Some(to_binary(&0).unwrap()),
+            memo: None,
+            padding: None,
+        }
+        .test_exec(&snip20, &mut chain, Addr::unchecked(funder), &[])
+        .unwrap();
+    }
+
+    // Only the proposer can cancel
+    assert!(
+        governance::ExecuteMsg::CancelProposal {
+            proposal: 0,
+            padding: None
+        }
+        .test_exec(&gov, &mut chain, Addr::unchecked("charlie"), &[])
+        .is_err()
+    );
+
+    governance::ExecuteMsg::CancelProposal {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    assert_eq!(prop.status, Status::Canceled);
+
+    // Funders were refunded in full
+    for funder in ["alpha", "beta"] {
+        let query: snip20::QueryAnswer = snip20::QueryMsg::Balance {
+            address: funder.into(),
+            key: "password".to_string(),
+        }
+        .test_query(&snip20, &chain)
+        .unwrap();
+
+        match query {
+            snip20::QueryAnswer::Balance { amount } => {
+                assert_eq!(amount, Uint128::new(10000))
+            }
+            _ => assert!(false),
+        };
+    }
+
+    assert!(
+        governance::ExecuteMsg::ClaimFunding { id: 0 }
+            .test_exec(&gov, &mut chain, Addr::unchecked("beta"), &[])
+            .is_err()
+    );
+    assert!(
+        governance::ExecuteMsg::Trigger {
+            proposal: 0,
+            padding: None
+        }
+        .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
+        .is_err()
+    );
+    assert!(
+        governance::ExecuteMsg::CancelProposal {
+            proposal: 0,
+            padding: None
+        }
+        .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
+        .is_err()
+    );
+}
+
+#[test]
+fn claim_refund_once() {
+    let (mut chain, gov, snip20, _auth) = init_funding_governance_with_proposal().unwrap();
+
+    for (funder, amount) in [("alpha", 1000), ("beta", 500)] {
+        snip20::ExecuteMsg::Send {
+            recipient: gov.address.clone().into(),
+            recipient_code_hash: None,
+            amount: Uint128::new(amount),
+            msg: Some(to_binary(&0).unwrap()),
+            memo: None,
+            padding: None,
+        }
+        .test_exec(&snip20, &mut chain, Addr::unchecked(funder), &[])
+        .unwrap();
+    }
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(10000));
+
+    governance::ExecuteMsg::Update {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("beta"), &[])
+    .unwrap();
+
+    for funder in ["alpha", "beta"] {
+        governance::ExecuteMsg::ClaimFunding { id: 0 }
+            .test_exec(&gov, &mut chain, Addr::unchecked(funder), &[])
+            .unwrap();
+
+        // Contributions can only be reclaimed once
+        assert!(
+            governance::ExecuteMsg::ClaimFunding { id: 0 }
+                .test_exec(&gov, &mut chain, Addr::unchecked(funder), &[])
+                .is_err()
+        );
+
+        let query: snip20::QueryAnswer = snip20::QueryMsg::Balance {
+            address: funder.into(),
+            key: "password".to_string(),
+        }
+        .test_query(&snip20, &chain)
+        .unwrap();
+
+        match query {
+            snip20::QueryAnswer::Balance { amount } => {
+                assert_eq!(amount, Uint128::new(10000))
+            }
+            _ => assert!(false),
+        };
+    }
+
+    // Never funded
+    assert!(
+        governance::ExecuteMsg::ClaimFunding { id: 0 }
+            .test_exec(&gov, &mut chain, Addr::unchecked("charlie"), &[])
+            .is_err()
+    );
+}
@@ -1,11 +1,14 @@
 pub mod assembly_voting;
 pub mod funding;
+pub mod quorum;
 pub mod voting;
 
 use crate::tests::{
     admin_only_governance,
     get_assemblies,
+    get_config,
     get_proposals,
+    get_proposals_by_status,
     gov_generic_proposal,
     gov_msg_proposal,
 };
@@ -14,12 +17,12 @@ use shade_protocol::{
     c_std::{to_binary, Addr, ContractInfo, StdResult},
     contract_interfaces::{
         governance,
-        governance::proposal::{ProposalMsg, Status},
+        governance::proposal::{Proposal, ProposalMsg, Status, StatusFilter},
     },
     multi_test::App,
     query_auth,
     snip20::{self, InitialBalance},
-    utils::{ExecuteCallback, InstantiateCallback, MultiTestable},
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
 };
 
 pub fn init_funding_token(
@@ -71,6 +74,7 @@ fn trigger_admin_command() {
         title: "Title".to_string(),
         metadata: "Proposal metadata".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
@@ -87,6 +91,7 @@ fn unauthorized_trigger_admin_command() {
             title: "Title".to_string(),
             metadata: "Proposal metadata".to_string(),
             msgs: None,
+            voting_period: None,
             padding: None
         }
         .test_exec(&gov, &mut chain, Addr::unchecked("random"), &[])
@@ -103,6 +108,7 @@ fn text_only_proposal() {
         title: "Title".to_string(),
         metadata: "Text only proposal".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
@@ -137,6 +143,105 @@ fn text_only_proposal() {
     assert_eq!(prop.status_history.len(), 1);
 }
 
+#[test]
+fn proposals_by_status() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    for title in ["First", "Second", "Third"] {
+        governance::ExecuteMsg::AssemblyProposal {
+            assembly: 1,
+            title: title.to_string(),
+            metadata: "Text only proposal".to_string(),
+            msgs: None,
+            voting_period: None,
+            padding: None,
+        }
+        .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+        .unwrap();
+    }
+
+    governance::ExecuteMsg::Trigger {
+        proposal: 1,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let titles = |props: Vec<Proposal>| props.into_iter().map(|p| p.title).collect::<Vec<_>>();
+
+    assert_eq!(
+        titles(get_proposals_by_status(&mut chain, &gov, 0, 10, StatusFilter::Passed).unwrap()),
+        vec!["First", "Third"]
+    );
+    assert_eq!(
+        titles(get_proposals_by_status(&mut chain, &gov, 0, 10, StatusFilter::Success).unwrap()),
+        vec!["Second"]
+    );
+
+    // Indexes are over all proposals, only the ones in range are filtered
+    assert_eq!(
+        titles(get_proposals_by_status(&mut chain, &gov, 1, 2, StatusFilter::Passed).unwrap()),
+        vec!["Third"]
+    );
+    assert!(
+        get_proposals_by_status(&mut chain, &gov, 1, 1, StatusFilter::Passed)
+            .unwrap()
+            .is_empty()
+    );
+    assert!(
+        get_proposals_by_status(&mut chain, &gov, 0, 10, StatusFilter::Voting)
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn proposals_page_cap() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    for _ in 0..32 {
+        governance::ExecuteMsg::AssemblyProposal {
+            assembly: 1,
+            title: "Title".to_string(),
+            metadata: "Text only proposal".to_string(),
+            msgs: None,
+            voting_period: None,
+            padding: None,
+        }
+        .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+        .unwrap();
+    }
+
+    let page = |chain: &App, start: u32, status: Option<StatusFilter>| {
+        match (governance::QueryMsg::Proposals {
+            start,
+            end: 100,
+            status,
+        }
+        .test_query(&gov, chain)
+        .unwrap())
+        {
+            governance::QueryAnswer::Proposals { props, next } => (props.len(), next),
+            _ => panic!("Returned wrong enum"),
+        }
+    };
+
+    assert_eq!(page(&chain, 0, None), (30, Some(30)));
+    assert_eq!(page(&chain, 30, None), (2, None));
+    assert_eq!(page(&chain, 0, Some(StatusFilter::Passed)), (30, Some(30)));
+    assert_eq!(page(&chain, 30, Some(StatusFilter::Passed)), (2, None));
+
+    assert!(
+        governance::QueryMsg::Proposals {
+            start: 32,
+            end: 100,
+            status: None,
+        }
+        .test_query::<governance::QueryAnswer>(&gov, &chain)
+        .is_err()
+    );
+}
+
 #[test]
 fn msg_proposal() {
     let (mut chain, gov) = admin_only_governance().unwrap();
@@ -182,6 +287,47 @@ fn msg_proposal() {
     assert_ne!(new_assembly.name, old_assembly.name);
 }
 
+#[test]
+fn self_config_proposal() {
+    let (mut chain, gov) = admin_only_governance().unwrap();
+
+    gov_generic_proposal(
+        &mut chain,
+        &gov,
+        "admin",
+        governance::ExecuteMsg::SetConfig {
+            query_auth: None,
+            treasury: Some(Addr::unchecked("new_treasury")),
+            funding_token: None,
+            vote_token: None,
+            voting_period_bounds: None,
+            padding: None,
+        },
+    )
+    .unwrap();
+
+    // Config can only be changed by governance itself
+    assert_eq!(
+        get_config(&mut chain, &gov).unwrap().treasury,
+        Addr::unchecked("treasury")
+    );
+
+    governance::ExecuteMsg::Trigger {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+
+    assert_eq!(prop.status, Status::Success);
+    assert_eq!(
+        get_config(&mut chain, &gov).unwrap().treasury,
+        Addr::unchecked("new_treasury")
+    );
+}
+
 #[test]
 fn multi_msg_proposal() {
     let (mut chain, gov) = admin_only_governance().unwrap();
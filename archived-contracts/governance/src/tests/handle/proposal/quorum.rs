@@ -0,0 +1,345 @@
+use crate::tests::{get_proposals, handle::proposal::init_funding_token, init_chain};
+use shade_multi_test::multi::governance::Governance;
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        governance,
+        governance::{
+            profile::{Count, FundProfile, Profile, VoteProfile},
+            proposal::Status,
+            vote::Vote,
+            InstantiateMsg,
+        },
+        snip20,
+    },
+    governance::AssemblyInit,
+    multi_test::App,
+    utils::{asset::Contract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+// Stands in for snip20_staking, only answers TotalStaked
+mod mock_staking {
+    use shade_protocol::{
+        c_std::{
+            to_binary,
+            Addr,
+            Binary,
+            ContractInfo,
+            Deps,
+            DepsMut,
+            Empty,
+            Env,
+            MessageInfo,
+            Response,
+            StdResult,
+            Uint128,
+            Uint256,
+        },
+        contract_interfaces::staking::snip20_staking,
+        multi_test::{Contract, ContractWrapper},
+        serde::{Deserialize, Serialize},
+        utils::{storage::plus::Item, ExecuteCallback, InstantiateCallback, MultiTestable},
+    };
+
+    const TOTAL: Item<Uint128> = Item::new("total");
+
+    #[derive(Serialize, Deserialize, Debug)]
+    #[serde(crate = "shade_protocol::serde")]
+    pub struct InstantiateMsg {
+        pub total: Uint128,
+    }
+
+    impl InstantiateCallback for InstantiateMsg {
+        const BLOCK_SIZE: usize = 256;
+    }
+
+    #[derive(Serialize, Deserialize, Debug)]
+    #[serde(crate = "shade_protocol::serde", rename_all = "snake_case")]
+    pub enum ExecuteMsg {
+        SetTotal { total: Uint128 },
+    }
+
+    impl ExecuteCallback for ExecuteMsg {
+        const BLOCK_SIZE: usize = 256;
+    }
+
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> StdResult<Response> {
+        TOTAL.save(deps.storage, &msg.total)?;
+        Ok(Response::new())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: ExecuteMsg,
+    ) -> StdResult<Response> {
+        match msg {
+            ExecuteMsg::SetTotal { total } => TOTAL.save(deps.storage, &total)?,
+        }
+        Ok(Response::new())
+    }
+
+    fn query(deps: Deps, _env: Env, _msg: snip20_staking::QueryMsg) -> StdResult<Binary> {
+        to_binary(&snip20_staking::QueryAnswer::TotalStaked {
+            tokens: TOTAL.load(deps.storage)?,
+            shares: Uint256::zero(),
+        })
+    }
+
+    pub struct MockStaking {
+        info: ContractInfo,
+    }
+
+    impl MultiTestable for MockStaking {
+        fn contract(&self) -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+        }
+
+        fn default() -> Self {
+            MockStaking {
+                info: ContractInfo {
+                    address: Addr::unchecked(""),
+                    code_hash: String::default(),
+                },
+            }
+        }
+    }
+}
+
+// Admin assembly proposals go to token voting, after funding if a funding token is given
+fn init_governance(
+    chain: &mut App,
+    auth: &ContractInfo,
+    staking: &ContractInfo,
+    funding: Option<&ContractInfo>,
+) -> ContractInfo {
+    InstantiateMsg {
+        treasury: Addr::unchecked("treasury"),
+        query_auth: Contract {
+            address: auth.address.clone(),
+            code_hash: auth.code_hash.clone(),
+        },
+        assemblies: Some(AssemblyInit {
+            admin_members: vec![Addr::unchecked("alpha")],
+            admin_profile: Profile {
+                name: "admin".to_string(),
+                enabled: true,
+                assembly: None,
+                funding: funding.map(|_| FundProfile {
+                    deadline: 1000,
+                    required: Uint128::new(2000),
+                    privacy: false,
+                    veto_deposit_loss: Default::default(),
+                }),
+                token: Some(VoteProfile {
+                    deadline: 10000,
+                    // 50% of total power
+                    threshold: Count::Percentage { percent: 5000 },
+                    yes_threshold: Count::Percentage { percent: 5000 },
+                    veto_threshold: Count::Percentage { percent: 5000 },
+                }),
+                cancel_deadline: 0,
+            },
+            public_profile: Profile {
+                name: "public".to_string(),
+                enabled: false,
+                assembly: None,
+                funding: None,
+                token: None,
+                cancel_deadline: 0,
+            },
+        }),
+        funding_token: funding.map(|funding| Contract {
+            address: funding.address.clone(),
+            code_hash: funding.code_hash.clone(),
+        }),
+        vote_token: Some(Contract {
+            address: staking.address.clone(),
+            code_hash: staking.code_hash.clone(),
+        }),
+        migrator: None,
+    }
+    .test_init(
+        Governance::default(),
+        chain,
+        Addr::unchecked("admin"),
+        "governance",
+        &[],
+    )
+    .unwrap()
+}
+
+/* Stake doubles after the proposal moves from funding to voting,
+ * quorum is still measured against the power at the transition
+ */
+#[test]
+fn quorum_uses_voting_snapshot() {
+    let (mut chain, auth) = init_chain();
+
+    let funding = init_funding_token(
+        &mut chain,
+        Some(vec![snip20::InitialBalance {
+            address: "alpha".into(),
+            amount: Uint128::new(10000),
+        }]),
+        Some(&auth),
+    )
+    .unwrap();
+
+    let staking = mock_staking::InstantiateMsg {
+        total: Uint128::new(20_000_000),
+    }
+    .test_init(
+        mock_staking::MockStaking::default(),
+        &mut chain,
+        Addr::unchecked("admin"),
+        "staking",
+        &[],
+    )
+    .unwrap();
+
+    let gov = init_governance(&mut chain, &auth, &staking, Some(&funding));
+
+    governance::ExecuteMsg::AssemblyProposal {
+        assembly: 1,
+        title: "Title".to_string(),
+        metadata: "Text only proposal".to_string(),
+        msgs: None,
+        voting_period: None,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: gov.address.to_string(),
+        recipient_code_hash: None,
+        amount: Uint128::new(2000),
+        msg: Some(to_binary(&0u32).unwrap()),
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&funding, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    // Funded, moves to voting and snapshots power
+    governance::ExecuteMsg::Update {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    match prop.status {
+        Status::Voting { .. } => assert!(true),
+        _ => assert!(false),
+    };
+    assert_eq!(prop.voting_power, Some(Uint128::new(20_000_000)));
+
+    // 12m clears 50% of the snapshot but not of the later total
+    governance::ExecuteMsg::ReceiveBalance {
+        sender: Addr::unchecked("alpha"),
+        msg: Some(
+            to_binary(&governance::vote::ReceiveBalanceMsg {
+                vote: Vote {
+                    yes: Uint128::new(12_000_000),
+                    no: Uint128::zero(),
+                    no_with_veto: Uint128::zero(),
+                    abstain: Uint128::zero(),
+                },
+                proposal: 0,
+            })
+            .unwrap(),
+        ),
+        balance: Uint128::new(12_000_000),
+        memo: None,
+    }
+    .test_exec(&gov, &mut chain, staking.address.clone(), &[])
+    .unwrap();
+
+    mock_staking::ExecuteMsg::SetTotal {
+        total: Uint128::new(40_000_000),
+    }
+    .test_exec(&staking, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    // Participation is reported against the snapshot as well
+    match (governance::QueryMsg::ProposalVotes { proposal: 0 })
+        .test_query(&gov, &chain)
+        .unwrap()
+    {
+        governance::QueryAnswer::ProposalVotes { votes, total_power } => {
+            assert_eq!(votes.yes, Uint128::new(12_000_000));
+            assert_eq!(total_power, Some(Uint128::new(20_000_000)));
+        }
+        _ => assert!(false),
+    };
+
+    chain.update_block(|block| block.time = block.time.plus_seconds(10001));
+
+    governance::ExecuteMsg::Update {
+        proposal: 0,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    match prop.status {
+        Status::Passed { .. } => assert!(true),
+        _ => assert!(false),
+    };
+}
+
+/* Without funding the proposal opens straight into voting,
+ * the snapshot is taken when it's created
+ */
+#[test]
+fn quorum_snapshot_on_direct_voting() {
+    let (mut chain, auth) = init_chain();
+
+    let staking = mock_staking::InstantiateMsg {
+        total: Uint128::new(20_000_000),
+    }
+    .test_init(
+        mock_staking::MockStaking::default(),
+        &mut chain,
+        Addr::unchecked("admin"),
+        "staking",
+        &[],
+    )
+    .unwrap();
+
+    let gov = init_governance(&mut chain, &auth, &staking, None);
+
+    governance::ExecuteMsg::AssemblyProposal {
+        assembly: 1,
+        title: "Title".to_string(),
+        metadata: "Text only proposal".to_string(),
+        msgs: None,
+        voting_period: None,
+        padding: None,
+    }
+    .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
+    .unwrap();
+
+    mock_staking::ExecuteMsg::SetTotal {
+        total: Uint128::new(40_000_000),
+    }
+    .test_exec(&staking, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let prop = get_proposals(&mut chain, &gov, 0, 2).unwrap()[0].clone();
+    match prop.status {
+        Status::Voting { .. } => assert!(true),
+        _ => assert!(false),
+    };
+    assert_eq!(prop.voting_power, Some(Uint128::new(20_000_000)));
+}
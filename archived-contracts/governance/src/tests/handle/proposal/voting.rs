@@ -148,6 +148,7 @@ pub fn init_voting_governance_with_proposal() -> StdResult<(App, ContractInfo, S
         title: "Title".to_string(),
         metadata: "Text only proposal".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("alpha"), &[])
@@ -121,6 +121,7 @@ fn create_proposal(chain: &mut App, gov: &ContractInfo, assembly: u16) -> AnyRes
         title: "Title".to_string(),
         metadata: "Text only proposal".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, chain, Addr::unchecked("alpha"), &[])
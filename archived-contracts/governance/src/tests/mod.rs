@@ -14,7 +14,7 @@ use shade_protocol::{
             assembly::{Assembly, AssemblyMsg},
             contract::AllowedContract,
             profile::Profile,
-            proposal::{Proposal, ProposalMsg},
+            proposal::{Proposal, ProposalMsg, StatusFilter},
             Config,
         },
     },
@@ -117,6 +117,7 @@ pub fn gov_msg_proposal(
         title: "Title".to_string(),
         metadata: "Proposal metadata".to_string(),
         msgs: Some(msgs),
+        voting_period: None,
         padding: None,
     }
     .test_exec(gov, chain, Addr::unchecked(sender), &[])
@@ -193,11 +194,35 @@ pub fn get_proposals(
     start: u32,
     end: u32,
 ) -> StdResult<Vec<Proposal>> {
-    let query: governance::QueryAnswer =
-        governance::QueryMsg::Proposals { start, end }.test_query(&gov, &chain)?;
+    let query: governance::QueryAnswer = governance::QueryMsg::Proposals {
+        start,
+        end,
+        status: None,
+    }
+    .test_query(&gov, &chain)?;
+
+    match query {
+        governance::QueryAnswer::Proposals { props, .. } => Ok(props),
+        _ => return Err(StdError::generic_err("Returned wrong enum")),
+    }
+}
+
+pub fn get_proposals_by_status(
+    chain: &mut App,
+    gov: &ContractInfo,
+    start: u32,
+    end: u32,
+    status: StatusFilter,
+) -> StdResult<Vec<Proposal>> {
+    let query: governance::QueryAnswer = governance::QueryMsg::Proposals {
+        start,
+        end,
+        status: Some(status),
+    }
+    .test_query(&gov, &chain)?;
 
     match query {
-        governance::QueryAnswer::Proposals { props } => Ok(props),
+        governance::QueryAnswer::Proposals { props, .. } => Ok(props),
         _ => return Err(StdError::generic_err("Returned wrong enum")),
     }
 }
@@ -1,12 +1,18 @@
-use crate::tests::{
-    admin_only_governance,
-    get_assemblies,
-    get_assembly_msgs,
-    get_config,
-    get_contract,
-    get_profiles,
+use crate::{
+    contract::SCHEMA_VERSION,
+    tests::{
+        admin_only_governance,
+        get_assemblies,
+        get_assembly_msgs,
+        get_config,
+        get_contract,
+        get_profiles,
+    },
+};
+use shade_protocol::{
+    contract_interfaces::governance,
+    utils::{version::VersionResponse, Query},
 };
-use shade_protocol::{contract_interfaces::governance, utils::Query};
 
 #[test]
 fn query_total_assembly_msg() {
@@ -178,3 +184,17 @@ fn query_config() {
 
     get_config(&mut chain, &gov).unwrap();
 }
+
+#[test]
+fn query_version() {
+    let (chain, gov) = admin_only_governance().unwrap();
+
+    let version: VersionResponse = governance::QueryMsg::Version {}
+        .test_query(&gov, &chain)
+        .unwrap();
+
+    assert_eq!(version, VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        schema: SCHEMA_VERSION,
+    });
+}
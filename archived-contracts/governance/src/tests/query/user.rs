@@ -74,6 +74,7 @@ fn proposals() {
         title: "Title".to_string(),
         metadata: "Text".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
@@ -105,6 +106,7 @@ fn proposals() {
         title: "Title".to_string(),
         metadata: "Text".to_string(),
         msgs: None,
+        voting_period: None,
         padding: None,
     }
     .test_exec(&gov, &mut chain, Addr::unchecked("admin"), &[])
@@ -160,6 +160,7 @@ fn test_ensemble(
             &mock_band::contract::ExecuteMsg::MockPrice {
                 symbol: "SCRT".into(),
                 price: offer_price,
+                decimals: None,
             },
             MockEnv::new("admin", band.clone()),
         )
@@ -169,6 +170,7 @@ fn test_ensemble(
             &mock_band::contract::ExecuteMsg::MockPrice {
                 symbol: "SHD".into(),
                 price: mint_price,
+                decimals: None,
             },
             MockEnv::new("admin", band.clone()),
         )
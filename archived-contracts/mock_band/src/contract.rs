@@ -3,6 +3,7 @@ use shade_protocol::c_std::{
     Api,
     Binary,
     Env,
+    Deps,
     DepsMut,
     Response,
     Querier,
@@ -12,12 +13,28 @@ use shade_protocol::c_std::{
 };
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use shade_protocol::contract_interfaces::oracles::band::{InstantiateMsg, ReferenceData};
+use shade_protocol::contract_interfaces::oracles::band::ReferenceData;
 use shade_protocol::c_std::Uint128;
 
-use shade_protocol::storage::{bucket, bucket_read, Bucket, ReadonlyBucket};
+use shade_protocol::storage::{
+    bucket,
+    bucket_read,
+    singleton,
+    singleton_read,
+    Bucket,
+    ReadonlyBucket,
+    ReadonlySingleton,
+    Singleton,
+};
 
 pub static PRICE: &[u8] = b"prices";
+pub static HISTORY: &[u8] = b"history";
+pub static HISTORY_SIZE: &[u8] = b"history_size";
+pub static ERRORING: &[u8] = b"erroring";
+pub static DECIMALS: &[u8] = b"decimals";
+
+/// Prices kept per symbol when no size is given at init
+pub const DEFAULT_HISTORY_SIZE: u32 = 10;
 
 pub fn price_r(storage: &dyn Storage) -> ReadonlyBucket<Uint128> {
     bucket_read(storage, PRICE)
@@ -27,18 +44,75 @@ pub fn price_w(storage: &mut dyn Storage) -> Bucket<Uint128> {
     bucket(storage, PRICE)
 }
 
+pub fn history_r(storage: &dyn Storage) -> ReadonlyBucket<Vec<Uint128>> {
+    bucket_read(storage, HISTORY)
+}
+
+pub fn history_w(storage: &mut dyn Storage) -> Bucket<Vec<Uint128>> {
+    bucket(storage, HISTORY)
+}
+
+pub fn history_size_r(storage: &dyn Storage) -> ReadonlySingleton<u32> {
+    singleton_read(storage, HISTORY_SIZE)
+}
+
+pub fn history_size_w(storage: &mut dyn Storage) -> Singleton<u32> {
+    singleton(storage, HISTORY_SIZE)
+}
+
+pub fn decimals_r(storage: &dyn Storage) -> ReadonlyBucket<u8> {
+    bucket_read(storage, DECIMALS)
+}
+
+pub fn decimals_w(storage: &mut dyn Storage) -> Bucket<u8> {
+    bucket(storage, DECIMALS)
+}
+
+pub fn erroring_r(storage: &dyn Storage) -> ReadonlyBucket<bool> {
+    bucket_read(storage, ERRORING)
+}
+
+pub fn erroring_w(storage: &mut dyn Storage) -> Bucket<bool> {
+    bucket(storage, ERRORING)
+}
+
+/// Fails the query for symbols marked as erroring
+fn check_erroring(storage: &dyn Storage, symbol: &str) -> StdResult<()> {
+    if erroring_r(storage).may_load(symbol.as_bytes())?.unwrap_or(false) {
+        return Err(StdError::generic_err("oracle error"));
+    }
+    Ok(())
+}
+
+// Extends band::InstantiateMsg, an empty init message is still accepted
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct InstantiateMsg {
+    /// Number of past prices kept per symbol
+    pub history_size: Option<u32>,
+}
+
 pub fn init(
-    _deps: DepsMut,
+    deps: DepsMut,
     _env: Env,
-    _msg: InstantiateMsg,
+    msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    history_size_w(deps.storage)
+        .save(&msg.history_size.unwrap_or(DEFAULT_HISTORY_SIZE))?;
     Ok(Response::default())
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    MockPrice { symbol: String, price: Uint128 },
+    MockPrice {
+        symbol: String,
+        price: Uint128,
+        /// Decimals the price is scaled to, only reported by GetReferenceDataWithDecimals
+        decimals: Option<u8>,
+    },
+    /// Makes reference data queries for the symbol fail, to simulate an oracle outage
+    SetErroring { symbol: String, erroring: bool },
 }
 
 pub fn handle(
@@ -47,8 +121,34 @@ pub fn handle(
     msg: ExecuteMsg,
 ) -> StdResult<Response> {
     return match msg {
-        ExecuteMsg::MockPrice { symbol, price } => {
+        ExecuteMsg::MockPrice {
+            symbol,
+            price,
+            decimals,
+        } => {
             price_w(deps.storage).save(symbol.as_bytes(), &price)?;
+            match decimals {
+                Some(decimals) => decimals_w(deps.storage).save(symbol.as_bytes(), &decimals)?,
+                None => decimals_w(deps.storage).remove(symbol.as_bytes()),
+            }
+
+            // Oldest prices fall off the front once the buffer is full
+            let size = history_size_r(deps.storage)
+                .may_load()?
+                .unwrap_or(DEFAULT_HISTORY_SIZE) as usize;
+            let mut history = history_r(deps.storage)
+                .may_load(symbol.as_bytes())?
+                .unwrap_or_default();
+            history.push(price);
+            if history.len() > size {
+                history.drain(..history.len() - size);
+            }
+            history_w(deps.storage).save(symbol.as_bytes(), &history)?;
+
+            Ok(Response::default())
+        }
+        ExecuteMsg::SetErroring { symbol, erroring } => {
+            erroring_w(deps.storage).save(symbol.as_bytes(), &erroring)?;
             Ok(Response::default())
         }
     };
@@ -65,7 +165,31 @@ pub enum QueryMsg {
         base_symbols: Vec<String>,
         quote_symbols: Vec<String>,
     },
+    /// Same as GetReferenceDataBulk, but missing feeds come back as None
+    /// instead of failing the whole query
+    GetReferenceDataBulkPartial {
+        base_symbols: Vec<String>,
+        quote_symbols: Vec<String>,
+    },
+    /// Same as GetReferenceData, along with the decimals set by MockPrice
+    GetReferenceDataWithDecimals {
+        base_symbol: String,
+        quote_symbol: String,
+    },
+    /// Past prices of a symbol, oldest first
+    GetPriceHistory {
+        symbol: String,
+    },
 }
+/// ReferenceData extended with the decimals the rate is scaled to
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ReferenceDataWithDecimals {
+    pub rate: Uint128,
+    pub decimals: Option<u8>,
+    pub last_updated_base: u64,
+    pub last_updated_quote: u64,
+}
+
 pub fn query(
     deps: Deps,
     msg: QueryMsg,
@@ -75,6 +199,7 @@ pub fn query(
             base_symbol,
             quote_symbol: _,
         } => {
+            check_erroring(deps.storage, &base_symbol)?;
             if let Some(price) = price_r(deps.storage).may_load(base_symbol.as_bytes())? {
                 return to_binary(&ReferenceData {
                     rate: price,
@@ -91,6 +216,7 @@ pub fn query(
             let mut results = Vec::new();
 
             for sym in base_symbols {
+                check_erroring(deps.storage, &sym)?;
                 if let Some(price) = price_r(deps.storage).may_load(sym.as_bytes())? {
                     results.push(ReferenceData {
                         rate: price,
@@ -106,5 +232,42 @@ pub fn query(
             }
             to_binary(&results)
         }
+        QueryMsg::GetReferenceDataBulkPartial {
+            base_symbols,
+            quote_symbols: _,
+        } => {
+            let mut results = Vec::new();
+
+            for sym in base_symbols {
+                check_erroring(deps.storage, &sym)?;
+                let price = price_r(deps.storage).may_load(sym.as_bytes())?;
+                results.push(price.map(|rate| ReferenceData {
+                    rate,
+                    last_updated_base: 0,
+                    last_updated_quote: 0,
+                }));
+            }
+            to_binary(&results)
+        }
+        QueryMsg::GetReferenceDataWithDecimals {
+            base_symbol,
+            quote_symbol: _,
+        } => {
+            check_erroring(deps.storage, &base_symbol)?;
+            if let Some(price) = price_r(deps.storage).may_load(base_symbol.as_bytes())? {
+                return to_binary(&ReferenceDataWithDecimals {
+                    rate: price,
+                    decimals: decimals_r(deps.storage).may_load(base_symbol.as_bytes())?,
+                    last_updated_base: 0,
+                    last_updated_quote: 0,
+                });
+            }
+            Err(StdError::generic_err("Missing Price Feed"))
+        }
+        QueryMsg::GetPriceHistory { symbol } => to_binary(
+            &history_r(deps.storage)
+                .may_load(symbol.as_bytes())?
+                .unwrap_or_default(),
+        ),
     }
 }
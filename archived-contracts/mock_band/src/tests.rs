@@ -0,0 +1,230 @@
+use shade_protocol::{
+    c_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        Uint128,
+    },
+    contract_interfaces::oracles::band::ReferenceData,
+};
+
+use crate::contract::{
+    handle,
+    init,
+    query,
+    ExecuteMsg,
+    InstantiateMsg,
+    QueryMsg,
+    ReferenceDataWithDecimals,
+};
+
+#[test]
+fn price_history() {
+    let mut deps = mock_dependencies();
+    init(deps.as_mut(), mock_env(), InstantiateMsg {
+        history_size: Some(2),
+    })
+    .unwrap();
+
+    for price in [100u128, 110, 120] {
+        handle(deps.as_mut(), mock_env(), ExecuteMsg::MockPrice {
+            symbol: "SCRT".to_string(),
+            price: Uint128::new(price),
+            decimals: None,
+        })
+        .unwrap();
+    }
+
+    // Only the last two prices fit in the buffer
+    let history: Vec<Uint128> = from_binary(
+        &query(deps.as_ref(), QueryMsg::GetPriceHistory {
+            symbol: "SCRT".to_string(),
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history, vec![Uint128::new(110), Uint128::new(120)]);
+
+    let history: Vec<Uint128> = from_binary(
+        &query(deps.as_ref(), QueryMsg::GetPriceHistory {
+            symbol: "ETH".to_string(),
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    assert!(history.is_empty());
+}
+
+#[test]
+fn price_history_in_order() {
+    let mut deps = mock_dependencies();
+    init(deps.as_mut(), mock_env(), InstantiateMsg {
+        history_size: None,
+    })
+    .unwrap();
+
+    for price in [120u128, 110, 100] {
+        handle(deps.as_mut(), mock_env(), ExecuteMsg::MockPrice {
+            symbol: "SCRT".to_string(),
+            price: Uint128::new(price),
+            decimals: None,
+        })
+        .unwrap();
+    }
+
+    let history: Vec<Uint128> = from_binary(
+        &query(deps.as_ref(), QueryMsg::GetPriceHistory {
+            symbol: "SCRT".to_string(),
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(history, vec![
+        Uint128::new(120),
+        Uint128::new(110),
+        Uint128::new(100)
+    ]);
+}
+
+#[test]
+fn erroring_symbol() {
+    let mut deps = mock_dependencies();
+    init(deps.as_mut(), mock_env(), InstantiateMsg {
+        history_size: None,
+    })
+    .unwrap();
+
+    for symbol in ["SCRT", "ETH"] {
+        handle(deps.as_mut(), mock_env(), ExecuteMsg::MockPrice {
+            symbol: symbol.to_string(),
+            price: Uint128::new(100),
+            decimals: None,
+        })
+        .unwrap();
+    }
+    handle(deps.as_mut(), mock_env(), ExecuteMsg::SetErroring {
+        symbol: "ETH".to_string(),
+        erroring: true,
+    })
+    .unwrap();
+
+    let err = query(deps.as_ref(), QueryMsg::GetReferenceData {
+        base_symbol: "ETH".to_string(),
+        quote_symbol: "USD".to_string(),
+    })
+    .unwrap_err();
+    assert!(err.to_string().contains("oracle error"));
+
+    let err = query(deps.as_ref(), QueryMsg::GetReferenceDataBulk {
+        base_symbols: vec!["SCRT".to_string(), "ETH".to_string()],
+        quote_symbols: vec!["USD".to_string(), "USD".to_string()],
+    })
+    .unwrap_err();
+    assert!(err.to_string().contains("oracle error"));
+
+    // Clearing the flag restores the price
+    handle(deps.as_mut(), mock_env(), ExecuteMsg::SetErroring {
+        symbol: "ETH".to_string(),
+        erroring: false,
+    })
+    .unwrap();
+    let data: ReferenceData = from_binary(
+        &query(deps.as_ref(), QueryMsg::GetReferenceData {
+            base_symbol: "ETH".to_string(),
+            quote_symbol: "USD".to_string(),
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(data.rate, Uint128::new(100));
+}
+
+#[test]
+fn bulk_partial() {
+    let mut deps = mock_dependencies();
+    init(deps.as_mut(), mock_env(), InstantiateMsg {
+        history_size: None,
+    })
+    .unwrap();
+
+    for (symbol, price) in [("SCRT", 100u128), ("ETH", 200)] {
+        handle(deps.as_mut(), mock_env(), ExecuteMsg::MockPrice {
+            symbol: symbol.to_string(),
+            price: Uint128::new(price),
+            decimals: None,
+        })
+        .unwrap();
+    }
+
+    let base_symbols = vec!["SCRT".to_string(), "BTC".to_string(), "ETH".to_string()];
+    let quote_symbols = vec!["USD".to_string(); 3];
+
+    // The all or nothing query still fails on the missing feed
+    assert!(
+        query(deps.as_ref(), QueryMsg::GetReferenceDataBulk {
+            base_symbols: base_symbols.clone(),
+            quote_symbols: quote_symbols.clone(),
+        })
+        .is_err()
+    );
+
+    let data: Vec<Option<ReferenceData>> = from_binary(
+        &query(deps.as_ref(), QueryMsg::GetReferenceDataBulkPartial {
+            base_symbols,
+            quote_symbols,
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    let rates: Vec<Option<Uint128>> = data.into_iter().map(|d| d.map(|d| d.rate)).collect();
+    assert_eq!(rates, vec![
+        Some(Uint128::new(100)),
+        None,
+        Some(Uint128::new(200))
+    ]);
+}
+
+#[test]
+fn price_decimals() {
+    let mut deps = mock_dependencies();
+    init(deps.as_mut(), mock_env(), InstantiateMsg {
+        history_size: None,
+    })
+    .unwrap();
+
+    handle(deps.as_mut(), mock_env(), ExecuteMsg::MockPrice {
+        symbol: "SCRT".to_string(),
+        price: Uint128::new(150_000_000),
+        decimals: Some(8),
+    })
+    .unwrap();
+
+    let data: ReferenceDataWithDecimals = from_binary(
+        &query(deps.as_ref(), QueryMsg::GetReferenceDataWithDecimals {
+            base_symbol: "SCRT".to_string(),
+            quote_symbol: "USD".to_string(),
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(data.rate, Uint128::new(150_000_000));
+    assert_eq!(data.decimals, Some(8));
+
+    // Omitting decimals goes back to a bare price
+    handle(deps.as_mut(), mock_env(), ExecuteMsg::MockPrice {
+        symbol: "SCRT".to_string(),
+        price: Uint128::new(2),
+        decimals: None,
+    })
+    .unwrap();
+
+    let data: ReferenceDataWithDecimals = from_binary(
+        &query(deps.as_ref(), QueryMsg::GetReferenceDataWithDecimals {
+            base_symbol: "SCRT".to_string(),
+            quote_symbol: "USD".to_string(),
+        })
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(data.rate, Uint128::new(2));
+    assert_eq!(data.decimals, None);
+}
@@ -1,4 +1,8 @@
-use crate::{execute, query};
+use crate::{
+    execute,
+    migration::{self, SCHEMA_VERSION},
+    query,
+};
 use shade_protocol::{
     c_std::{
         shd_entry_point,
@@ -16,7 +20,18 @@ use shade_protocol::{
     },
     contract_interfaces::{
         dao::adapter,
-        sky::{Config, Cycles, ExecuteMsg, InstantiateMsg, QueryMsg, SelfAddr, ViewingKeys},
+        sky::{
+            BalanceAuth,
+            Config,
+            Cycles,
+            ExecuteMsg,
+            InstantiateMsg,
+            MigrateMsg,
+            QueryMsg,
+            SchemaVersion,
+            SelfAddr,
+            ViewingKeys,
+        },
     },
     snip20::helpers::set_viewing_key_msg,
     utils::storage::plus::ItemStorage,
@@ -36,6 +51,10 @@ pub fn instantiate(
         sscrt_token: msg.sscrt_token.clone(),
         treasury: msg.treasury,
         payback_rate: msg.payback_rate,
+        min_amount: msg.min_amount,
+        max_hops: msg.max_hops,
+        balance_auth: msg.balance_auth.unwrap_or(BalanceAuth::ViewingKey),
+        execution_cooldown: msg.execution_cooldown,
     };
 
     if msg.payback_rate == Decimal::zero() {
@@ -45,6 +64,7 @@ pub fn instantiate(
     state.save(deps.storage)?;
     SelfAddr(env.contract.address).save(deps.storage)?;
     Cycles(vec![]).save(deps.storage)?;
+    SchemaVersion(SCHEMA_VERSION).save(deps.storage)?;
 
     deps.api
         .debug(&format!("Contract was initialized by {}", info.sender));
@@ -82,6 +102,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             sscrt_token,
             treasury,
             payback_rate,
+            min_amount,
+            max_hops,
+            balance_auth,
+            execution_cooldown,
             ..
         } => execute::try_update_config(
             deps,
@@ -93,6 +117,10 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             sscrt_token,
             treasury,
             payback_rate,
+            min_amount,
+            max_hops,
+            balance_auth,
+            execution_cooldown,
         ),
         ExecuteMsg::SetCycles { cycles, .. } => execute::try_set_cycles(deps, env, info, cycles),
         ExecuteMsg::AppendCycles { cycle, .. } => execute::try_append_cycle(deps, env, info, cycle),
@@ -100,12 +128,16 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             execute::try_update_cycle(deps, env, info, cycle, index)
         }
         ExecuteMsg::RemoveCycle { index, .. } => execute::try_remove_cycle(deps, env, info, index),
+        ExecuteMsg::SetCycleEnabled { index, enabled, .. } => {
+            execute::try_set_cycle_enabled(deps, env, info, index, enabled)
+        }
         ExecuteMsg::ArbCycle { amount, index, .. } => {
             execute::try_arb_cycle(deps, env, info, amount, index)
         }
         ExecuteMsg::ArbAllCycles { amount, .. } => {
             execute::try_arb_all_cycles(deps, env, info, amount)
         }
+        ExecuteMsg::Receive { .. } => execute::try_receive(deps, info),
         ExecuteMsg::Adapter(adapter) => match adapter {
             adapter::SubExecuteMsg::Unbond { asset, amount } => {
                 let asset = deps.api.addr_validate(&asset)?;
@@ -123,6 +155,13 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
     }
 }
 
+#[shd_entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    migration::migrate_storage(deps.storage, msg.default_fee)?;
+
+    Ok(Response::new())
+}
+
 #[shd_entry_point]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
@@ -135,6 +174,15 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::IsAnyCycleProfitable { amount } => {
             to_binary(&query::any_cycles_profitable(deps, amount)?)
         }
+        QueryMsg::SimulateCycle {
+            pair_addrs,
+            start_addr,
+            amount,
+        } => to_binary(&query::simulate_cycle(
+            deps, pair_addrs, start_addr, amount,
+        )?),
+        QueryMsg::TotalProfit {} => to_binary(&query::total_profit(deps)?),
+        QueryMsg::Version {} => to_binary(&query::version(deps)?),
         QueryMsg::Adapter(adapter) => match adapter {
             adapter::SubQueryMsg::Balance { asset } => to_binary(&query::adapter_balance(
                 deps,
@@ -11,6 +11,7 @@ use shade_protocol::{
         Response,
         StdError,
         StdResult,
+        Storage,
         SubMsg,
         Uint128,
     },
@@ -19,9 +20,13 @@ use shade_protocol::{
         sky::{
             self,
             cycles::{Cycle, Offer},
+            BalanceAuth,
             Config,
             Cycles,
             ExecuteAnswer,
+            LastExecution,
+            TokenProfit,
+            TotalProfit,
             ViewingKeys,
         },
     },
@@ -29,7 +34,7 @@ use shade_protocol::{
     utils::{
         asset::Contract,
         generic_response::ResponseStatus,
-        storage::plus::ItemStorage,
+        storage::plus::{ItemStorage, MapStorage},
         ExecuteCallback,
     },
 };
@@ -44,6 +49,10 @@ pub fn try_update_config(
     sscrt_token: Option<Contract>,
     treasury: Option<Contract>,
     payback_rate: Option<Decimal>,
+    min_amount: Option<Uint128>,
+    max_hops: Option<u32>,
+    balance_auth: Option<BalanceAuth>,
+    execution_cooldown: Option<u64>,
 ) -> StdResult<Response> {
     //Admin-only
     let mut config = Config::load(deps.storage)?;
@@ -92,6 +101,18 @@ pub fn try_update_config(
         }
         config.payback_rate = payback_rate;
     }
+    if let Some(min_amount) = min_amount {
+        config.min_amount = min_amount;
+    }
+    if let Some(max_hops) = max_hops {
+        config.max_hops = max_hops;
+    }
+    if let Some(balance_auth) = balance_auth {
+        config.balance_auth = balance_auth;
+    }
+    if let Some(execution_cooldown) = execution_cooldown {
+        config.execution_cooldown = execution_cooldown;
+    }
     config.save(deps.storage)?;
     Ok(Response::new()
         .set_data(to_binary(&ExecuteAnswer::UpdateConfig { status: true })?)
@@ -105,12 +126,12 @@ pub fn try_set_cycles(
     cycles_to_set: Vec<Cycle>,
 ) -> StdResult<Response> {
     //Admin-only
-    let shade_admin = Config::load(deps.storage)?.shade_admin;
+    let config = Config::load(deps.storage)?;
     validate_admin(
         &deps.querier,
         AdminPermissions::SkyAdmin,
         info.sender.to_string(),
-        &shade_admin,
+        &config.shade_admin,
     )?;
 
     if cycles_to_set.clone().len() > 40 {
@@ -119,9 +140,17 @@ pub fn try_set_cycles(
 
     // validate cycles
     for cycle in cycles_to_set.clone() {
+        validate_hops(&cycle, config.max_hops)?;
         cycle.validate_cycle()?;
     }
 
+    // cooldowns are keyed by index and belong to the cycles being replaced
+    if let Some(old_cycles) = Cycles::may_load(deps.storage)? {
+        for j in 0..old_cycles.0.len() {
+            LastExecution::remove(deps.storage, j as u64);
+        }
+    }
+
     let new_cycles = Cycles(cycles_to_set);
     new_cycles.save(deps.storage)?;
 
@@ -135,15 +164,16 @@ pub fn try_append_cycle(
     cycles_to_add: Vec<Cycle>,
 ) -> StdResult<Response> {
     //Admin-only
-    let shade_admin = Config::load(deps.storage)?.shade_admin;
+    let config = Config::load(deps.storage)?;
     validate_admin(
         &deps.querier,
         AdminPermissions::SkyAdmin,
         info.sender.to_string(),
-        &shade_admin,
+        &config.shade_admin,
     )?;
 
     for cycle in cycles_to_add.clone() {
+        validate_hops(&cycle, config.max_hops)?;
         cycle.validate_cycle()?;
     }
 
@@ -160,6 +190,18 @@ pub fn try_append_cycle(
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::AppendCycles { status: true })?))
 }
 
+// Long cycles are expensive to simulate and rarely profitable after fees
+fn validate_hops(cycle: &Cycle, max_hops: u32) -> StdResult<()> {
+    if cycle.pair_addrs.len() > max_hops as usize {
+        return Err(StdError::generic_err(format!(
+            "Cycle has {} hops, the maximum is {}",
+            cycle.pair_addrs.len(),
+            max_hops
+        )));
+    }
+    Ok(())
+}
+
 pub fn try_update_cycle(
     deps: DepsMut,
     _env: Env,
@@ -169,14 +211,15 @@ pub fn try_update_cycle(
 ) -> StdResult<Response> {
     let i = index.u128() as usize;
     //Admin-only
-    let shade_admin = Config::load(deps.storage)?.shade_admin;
+    let config = Config::load(deps.storage)?;
     validate_admin(
         &deps.querier,
         AdminPermissions::SkyAdmin,
         info.sender.to_string(),
-        &shade_admin,
+        &config.shade_admin,
     )?;
 
+    validate_hops(&cycle, config.max_hops)?;
     cycle.validate_cycle()?;
     let mut cycles = Cycles::load(deps.storage)?;
     if i > cycles.0.clone().len() - 1 {
@@ -211,22 +254,59 @@ pub fn try_remove_cycle(
         return Err(StdError::generic_err("index out of bounds"));
     }
 
+    // shift the cooldowns of the later cycles down with them
+    for j in i..cycles.len() - 1 {
+        match LastExecution::may_load(deps.storage, j as u64 + 1)? {
+            Some(last) => last.save(deps.storage, j as u64)?,
+            None => LastExecution::remove(deps.storage, j as u64),
+        }
+    }
+    LastExecution::remove(deps.storage, cycles.len() as u64 - 1);
+
     cycles.remove(i);
     Cycles(cycles).save(deps.storage)?;
 
     Ok(Response::new().set_data(to_binary(&ExecuteAnswer::RemoveCycle { status: true })?))
 }
 
-pub fn try_arb_cycle(
+pub fn try_set_cycle_enabled(
     deps: DepsMut,
     _env: Env,
     info: MessageInfo,
+    index: Uint128,
+    enabled: bool,
+) -> StdResult<Response> {
+    let i = index.u128() as usize;
+    //Admin-only
+    let shade_admin = Config::load(deps.storage)?.shade_admin;
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::SkyAdmin,
+        info.sender.to_string(),
+        &shade_admin,
+    )?;
+
+    let mut cycles = Cycles::load(deps.storage)?;
+    if i >= cycles.0.len() {
+        return Err(StdError::generic_err("index out of bounds"));
+    }
+    cycles.0[i].enabled = enabled;
+    cycles.save(deps.storage)?;
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::SetCycleEnabled { status: true })?))
+}
+
+pub fn try_arb_cycle(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
     amount: Uint128,
     index: Uint128,
 ) -> StdResult<Response> {
     let mut messages = vec![];
     let mut return_swap_amounts = vec![];
     let mut payback_amount = Uint128::zero();
+    let mut cycle_profit = Uint128::zero();
     let i = index.u128() as usize;
     // cur_asset will keep track of the asset that we currently "have"
     let mut cur_asset = Contract {
@@ -234,6 +314,13 @@ pub fn try_arb_cycle(
         code_hash: "".to_string(),
     };
 
+    if let Some(ready_at) = cooling_down(deps.storage, i as u64, env.block.height)? {
+        return Err(StdError::generic_err(format!(
+            "Cycle is cooling down until block {}",
+            ready_at
+        )));
+    }
+
     // don't need to check for an index out of bounds since that check will happen in
     // cycle_profitability
     let res = cycle_profitability(deps.as_ref(), amount, index)?; // get profitability data from query
@@ -243,7 +330,11 @@ pub fn try_arb_cycle(
             direction,
             swap_amounts,
             profit,
+            enabled,
         } => {
+            if !enabled {
+                return Err(StdError::generic_err("Cycle is disabled"));
+            }
             return_swap_amounts = swap_amounts.clone();
             if direction.pair_addrs[0] // test to see which of the token attributes are the proposed starting addr
                 .token0
@@ -285,6 +376,7 @@ pub fn try_arb_cycle(
                     cur_asset = arb_pair.token0.clone();
                 }
             }
+            cycle_profit = profit;
             // calculate payback amount
             payback_amount = profit * Config::load(deps.storage)?.payback_rate;
 
@@ -308,13 +400,40 @@ pub fn try_arb_cycle(
         ));
     }
 
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::ExecuteArbCycle {
+    add_profit(deps.storage, &cur_asset.address, cycle_profit)?;
+    LastExecution(env.block.height).save(deps.storage, i as u64)?;
+
+    Ok(Response::new()
+        .add_submessages(messages)
+        .set_data(to_binary(&ExecuteAnswer::ExecuteArbCycle {
             status: true,
             swap_amounts: return_swap_amounts,
             payback_amount,
-        })?),
-    )
+        })?))
+}
+
+// Block the cycle can run again at, if it hasn't been reached yet. Rerunning a cycle right away
+// mostly chases an opportunity the last run already took
+fn cooling_down(storage: &dyn Storage, index: u64, height: u64) -> StdResult<Option<u64>> {
+    if let Some(last) = LastExecution::may_load(storage, index)? {
+        let ready_at = last.0 + Config::load(storage)?.execution_cooldown;
+        if height < ready_at {
+            return Ok(Some(ready_at));
+        }
+    }
+    Ok(None)
+}
+
+fn add_profit(storage: &mut dyn Storage, token: &Addr, amount: Uint128) -> StdResult<()> {
+    let mut totals = TotalProfit::load_or_default(storage)?;
+    match totals.0.iter_mut().find(|p| p.token == *token) {
+        Some(total) => total.amount = total.amount.checked_add(amount)?,
+        None => totals.0.push(TokenProfit {
+            token: token.clone(),
+            amount,
+        }),
+    }
+    totals.save(storage)
 }
 
 pub fn try_arb_all_cycles(
@@ -328,19 +447,24 @@ pub fn try_arb_all_cycles(
     let res = any_cycles_profitable(deps.as_ref(), amount)?; // get profitability data from query
     match res {
         sky::QueryAnswer::IsAnyCycleProfitable {
+            index,
             is_profitable,
             profit,
             ..
         } => {
-            // loop through the data returned for each cycle
+            // loop through the data returned for each cycle, disabled cycles are already left out
             for (i, profit_bool) in is_profitable.iter().enumerate() {
+                // a cycle still cooling down would fail its ArbCycle and the whole batch with it
+                if cooling_down(deps.storage, index[i].u128() as u64, env.block.height)?.is_some() {
+                    continue;
+                }
                 // if a cycle is profitable call the try_arb_cycle fn and keep track of the
                 // total_profit
                 if profit_bool.clone() {
                     messages.push(SubMsg::new(
                         sky::ExecuteMsg::ArbCycle {
                             amount,
-                            index: Uint128::from(i as u128),
+                            index: index[i],
                             padding: None,
                         }
                         .to_cosmos_msg(
@@ -359,12 +483,25 @@ pub fn try_arb_all_cycles(
     }
     // calculate payback_amount
     let payback_amount = total_profit * Config::load(deps.storage)?.payback_rate;
-    Ok(
-        Response::new().set_data(to_binary(&ExecuteAnswer::ArbAllCycles {
+    Ok(Response::new()
+        .add_submessages(messages)
+        .set_data(to_binary(&ExecuteAnswer::ArbAllCycles {
             status: true,
             payback_amount,
-        })?),
-    )
+        })?))
+}
+
+// Funds sent in by the manager are simply held, only the tokens sky trades are accepted
+pub fn try_receive(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
+    let config = Config::load(deps.storage)?;
+    if !(config.shd_token.address == info.sender
+        || config.silk_token.address == info.sender
+        || config.sscrt_token.address == info.sender)
+    {
+        return Err(StdError::generic_err("Unrecognized asset"));
+    }
+
+    Ok(Response::new().set_data(to_binary(&ExecuteAnswer::Receive { status: true })?))
 }
 
 pub fn try_adapter_unbond(
@@ -1,3 +1,4 @@
 pub mod contract;
 pub mod execute;
+pub mod migration;
 pub mod query;
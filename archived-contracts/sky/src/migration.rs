@@ -0,0 +1,115 @@
+use shade_protocol::{
+    c_std::{Decimal, StdError, StdResult, Storage, Uint128},
+    contract_interfaces::sky::{BalanceAuth, Config, Cycles, SchemaVersion},
+    cosmwasm_schema::cw_serde,
+    secret_storage_plus::Item,
+    utils::{asset::Contract, storage::plus::ItemStorage},
+};
+
+/// Storage layout written by this code
+pub const SCHEMA_VERSION: u64 = 3;
+
+/// Hop limit given to configs stored before max_hops, raised to fit any registered cycle
+pub const MIGRATED_MAX_HOPS: u32 = 3;
+
+/// Config before min_amount, max_hops & balance_auth
+#[cw_serde]
+pub struct ConfigV1 {
+    pub shade_admin: Contract,
+    pub shd_token: Contract,
+    pub silk_token: Contract,
+    pub sscrt_token: Contract,
+    pub treasury: Contract,
+    pub payback_rate: Decimal,
+}
+
+/// Config before execution_cooldown
+#[cw_serde]
+pub struct ConfigV2 {
+    pub shade_admin: Contract,
+    pub shd_token: Contract,
+    pub silk_token: Contract,
+    pub sscrt_token: Contract,
+    pub treasury: Contract,
+    pub payback_rate: Decimal,
+    pub min_amount: Uint128,
+    pub max_hops: u32,
+    pub balance_auth: BalanceAuth,
+}
+
+pub const CONFIG_V1: Item<ConfigV1> = Item::new("item_config");
+pub const CONFIG_V2: Item<ConfigV2> = Item::new("item_config");
+
+/// Rewrites storage up to SCHEMA_VERSION, contracts already on it are left untouched
+pub fn migrate_storage(storage: &mut dyn Storage, default_fee: Option<Uint128>) -> StdResult<()> {
+    // Contracts instantiated before the version was tracked are on the first layout
+    let version = SchemaVersion::may_load(storage)?.map_or(1, |v| v.0);
+
+    if version < 2 {
+        v1_to_v2(storage, default_fee)?;
+    }
+    if version < 3 {
+        v2_to_v3(storage)?;
+    }
+
+    SchemaVersion(SCHEMA_VERSION).save(storage)
+}
+
+fn v1_to_v2(storage: &mut dyn Storage, default_fee: Option<Uint128>) -> StdResult<()> {
+    if default_fee.unwrap_or_default() > Uint128::new(10_000) {
+        return Err(StdError::generic_err(
+            "Pair fee can't exceed 10000 basis points",
+        ));
+    }
+
+    // Cycles stored before pair fees existed load with no fee on every pair
+    let mut cycles = Cycles::load(storage)?;
+    for cycle in cycles.0.iter_mut() {
+        for pair in cycle.pair_addrs.iter_mut() {
+            if pair.fee.is_none() {
+                pair.fee = default_fee;
+            }
+        }
+    }
+    cycles.save(storage)?;
+
+    // Keep every registered cycle valid under the new hop limit
+    let longest = cycles
+        .0
+        .iter()
+        .map(|c| c.pair_addrs.len() as u32)
+        .max()
+        .unwrap_or_default();
+
+    // The new fields default to the behaviour sky had before they existed
+    let config = CONFIG_V1.load(storage)?;
+    CONFIG_V2.save(storage, &ConfigV2 {
+        shade_admin: config.shade_admin,
+        shd_token: config.shd_token,
+        silk_token: config.silk_token,
+        sscrt_token: config.sscrt_token,
+        treasury: config.treasury,
+        payback_rate: config.payback_rate,
+        min_amount: Uint128::zero(),
+        max_hops: longest.max(MIGRATED_MAX_HOPS),
+        balance_auth: BalanceAuth::ViewingKey,
+    })
+}
+
+fn v2_to_v3(storage: &mut dyn Storage) -> StdResult<()> {
+    // No cooldown, cycles could be executed back to back before it existed
+    let config = CONFIG_V2.load(storage)?;
+    Config {
+        shade_admin: config.shade_admin,
+        shd_token: config.shd_token,
+        silk_token: config.silk_token,
+        sscrt_token: config.sscrt_token,
+        treasury: config.treasury,
+        payback_rate: config.payback_rate,
+        min_amount: config.min_amount,
+        max_hops: config.max_hops,
+        balance_auth: config.balance_auth,
+        execution_cooldown: 0,
+    }
+    .save(storage)
+}
@@ -3,16 +3,19 @@ use shade_protocol::{
     contract_interfaces::{
         dao::adapter,
         sky::{
-            cycles::{Offer},
+            cycles::{ArbPair, Cycle, Offer},
+            BalanceAuth,
             Config,
             Cycles,
             QueryAnswer,
+            SchemaVersion,
             SelfAddr,
+            TotalProfit,
             ViewingKeys,
         },
         snip20,
     },
-    utils::{storage::plus::ItemStorage, Query},
+    utils::{asset::Contract, storage::plus::ItemStorage, version::VersionResponse, Query},
 };
 
 pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
@@ -21,46 +24,34 @@ pub fn config(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
-pub fn get_balances(deps: Deps) -> StdResult<QueryAnswer> {
-    let viewing_key = ViewingKeys::load(deps.storage)?.0;
-    let self_addr = SelfAddr::load(deps.storage)?.0;
-    let config = Config::load(deps.storage)?;
-
-    // Query shd balance
-    let mut res = snip20::QueryMsg::Balance {
-        address: self_addr.clone().to_string(),
-        key: viewing_key.clone(),
-    }
-    .query(&deps.querier, &config.shd_token.clone())?;
-
-    let shd_bal = match res {
-        snip20::QueryAnswer::Balance { amount } => amount,
-        _ => Uint128::zero(),
+// Queries sky's balance of a token with whichever auth the config selects
+fn balance_of(deps: Deps, config: &Config, contract: &Contract) -> StdResult<Uint128> {
+    let res = match &config.balance_auth {
+        BalanceAuth::ViewingKey => snip20::QueryMsg::Balance {
+            address: SelfAddr::load(deps.storage)?.0.to_string(),
+            key: ViewingKeys::load(deps.storage)?.0,
+        }
+        .query(&deps.querier, contract)?,
+        BalanceAuth::Permit { permit } => snip20::QueryMsg::WithPermit {
+            permit: Some(permit.clone()),
+            auth_permit: None,
+            query: snip20::QueryWithPermit::Balance {},
+        }
+        .query(&deps.querier, contract)?,
     };
 
-    // Query silk balance
-    res = snip20::QueryMsg::Balance {
-        address: self_addr.clone().to_string(),
-        key: viewing_key.clone(),
-    }
-    .query(&deps.querier, &config.silk_token.clone())?;
-
-    let silk_bal = match res {
+    Ok(match res {
         snip20::QueryAnswer::Balance { amount } => amount,
         _ => Uint128::zero(),
-    };
+    })
+}
 
-    // Query sscrt balance
-    res = snip20::QueryMsg::Balance {
-        address: self_addr.clone().to_string(),
-        key: viewing_key.clone(),
-    }
-    .query(&deps.querier, &config.sscrt_token.clone())?;
+pub fn get_balances(deps: Deps) -> StdResult<QueryAnswer> {
+    let config = Config::load(deps.storage)?;
 
-    let sscrt_bal = match res {
-        snip20::QueryAnswer::Balance { amount } => amount,
-        _ => Uint128::zero(),
-    };
+    let shd_bal = balance_of(deps, &config, &config.shd_token)?;
+    let silk_bal = balance_of(deps, &config, &config.silk_token)?;
+    let sscrt_bal = balance_of(deps, &config, &config.sscrt_token)?;
 
     Ok(QueryAnswer::Balance {
         shd_bal,
@@ -69,6 +60,20 @@ pub fn get_balances(deps: Deps) -> StdResult<QueryAnswer> {
     })
 }
 
+pub fn version(deps: Deps) -> StdResult<VersionResponse> {
+    Ok(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        // contracts instantiated before versioning was added are v1
+        schema: SchemaVersion::may_load(deps.storage)?.map_or(1, |v| v.0),
+    })
+}
+
+pub fn total_profit(deps: Deps) -> StdResult<QueryAnswer> {
+    Ok(QueryAnswer::TotalProfit {
+        profits: TotalProfit::load_or_default(deps.storage)?.0,
+    })
+}
+
 pub fn get_cycles(deps: Deps) -> StdResult<QueryAnswer> {
     //Need to make private eventually
     Ok(QueryAnswer::GetCycles {
@@ -77,22 +82,65 @@ pub fn get_cycles(deps: Deps) -> StdResult<QueryAnswer> {
 }
 
 pub fn cycle_profitability(deps: Deps, amount: Uint128, index: Uint128) -> StdResult<QueryAnswer> {
-    let mut cycles = Cycles::load(deps.storage)?.0;
-    let mut swap_amounts = vec![amount];
+    check_min_amount(deps, amount)?;
+
+    let cycles = Cycles::load(deps.storage)?.0;
     let i = index.u128() as usize;
 
     if (i) >= cycles.len() {
         return Err(StdError::generic_err("Index passed is out of bounds"));
     }
 
+    profitability(deps, cycles[i].clone(), amount)
+}
+
+pub fn simulate_cycle(
+    deps: Deps,
+    pair_addrs: Vec<ArbPair>,
+    start_addr: Contract,
+    amount: Uint128,
+) -> StdResult<QueryAnswer> {
+    check_min_amount(deps, amount)?;
+
+    if pair_addrs.is_empty() {
+        return Err(StdError::generic_err(
+            "Cycle must include at least one pair",
+        ));
+    }
+    // the cycle isn't stored, but it has to be one that could be
+    let cycle = Cycle {
+        pair_addrs,
+        start_addr,
+        enabled: true,
+    };
+    cycle.validate_cycle()?;
+
+    profitability(deps, cycle, amount)
+}
+
+fn check_min_amount(deps: Deps, amount: Uint128) -> StdResult<()> {
+    // rounding and fees on dust amounts can report profit that won't survive execution
+    let min_amount = Config::load(deps.storage)?.min_amount;
+    if amount < min_amount {
+        return Err(StdError::generic_err(format!(
+            "Amount {} is below the minimum of {}",
+            amount, min_amount
+        )));
+    }
+    Ok(())
+}
+
+fn profitability(deps: Deps, mut cycle: Cycle, amount: Uint128) -> StdResult<QueryAnswer> {
+    let mut swap_amounts = vec![amount];
+
     // set up inital offer
     let mut current_offer = Offer {
-        asset: cycles[i].start_addr.clone(),
+        asset: cycle.start_addr.clone(),
         amount,
     };
 
     //loop through the pairs in the cycle
-    for arb_pair in cycles[i].pair_addrs.clone() {
+    for arb_pair in cycle.pair_addrs.clone() {
         // simulate swap will run a query with respect to which dex or minting that the pair says
         // it is
         let estimated_return = arb_pair
@@ -114,7 +162,8 @@ pub fn cycle_profitability(deps: Deps, amount: Uint128, index: Uint128) -> StdRe
         }
     }
 
-    if swap_amounts.len() > cycles[i].pair_addrs.clone().len() {
+    // the initial amount is the first entry, followed by one estimate per pair
+    if swap_amounts.len() > cycle.pair_addrs.clone().len() + 1 {
         return Err(StdError::generic_err("More swap amounts than arb pairs"));
     }
 
@@ -122,21 +171,22 @@ pub fn cycle_profitability(deps: Deps, amount: Uint128, index: Uint128) -> StdRe
     if current_offer.amount.u128() > amount.u128() {
         return Ok(QueryAnswer::IsCycleProfitable {
             is_profitable: true,
-            direction: cycles[i].clone(),
+            direction: cycle.clone(),
             swap_amounts,
             profit: current_offer.amount.checked_sub(amount)?,
+            enabled: cycle.enabled,
         });
     }
 
     // reset these variables in order to check the other way
     swap_amounts = vec![amount];
     current_offer = Offer {
-        asset: cycles[i].start_addr.clone(),
+        asset: cycle.start_addr.clone(),
         amount,
     };
 
     // this is a fancy way of iterating through a vec in reverse
-    for arb_pair in cycles[i].pair_addrs.clone().iter().rev() {
+    for arb_pair in cycle.pair_addrs.clone().iter().rev() {
         // get the estimated return from the simulate swap function
         let estimated_return = arb_pair
             .clone()
@@ -159,26 +209,29 @@ pub fn cycle_profitability(deps: Deps, amount: Uint128, index: Uint128) -> StdRe
     // check to see if this direction was profitable
     if current_offer.amount > amount {
         // do an inplace reversal of the pair_addrs so that we know which way the opportunity goes
-        cycles[i].pair_addrs.reverse();
+        cycle.pair_addrs.reverse();
         return Ok(QueryAnswer::IsCycleProfitable {
             is_profitable: true,
-            direction: cycles[i].clone(),
+            direction: cycle.clone(),
             swap_amounts,
             profit: current_offer.amount.checked_sub(amount)?,
+            enabled: cycle.enabled,
         });
     }
 
     // If both possible directions are unprofitable, return false
     Ok(QueryAnswer::IsCycleProfitable {
         is_profitable: false,
-        direction: cycles[0].clone(),
+        direction: cycle.clone(),
         swap_amounts: vec![],
         profit: Uint128::zero(),
+        enabled: cycle.enabled,
     })
 }
 
 pub fn any_cycles_profitable(deps: Deps, amount: Uint128) -> StdResult<QueryAnswer> {
     let cycles = Cycles::load(deps.storage)?.0;
+    let mut return_index = vec![];
     let mut return_is_profitable = vec![];
     let mut return_directions = vec![];
     let mut return_swap_amounts = vec![];
@@ -186,17 +239,23 @@ pub fn any_cycles_profitable(deps: Deps, amount: Uint128) -> StdResult<QueryAnsw
 
     // loop through the cycles with an index
     for index in 0..cycles.len() {
+        // disabled cycles are only checked when queried directly
+        if !cycles[index].enabled {
+            continue;
+        }
         // for each cycle, check its profitability
-        let res = cycle_profitability(deps, amount, Uint128::from(index as u128)).unwrap();
+        let res = cycle_profitability(deps, amount, Uint128::from(index as u128))?;
         match res {
             QueryAnswer::IsCycleProfitable {
                 is_profitable,
                 direction,
                 swap_amounts,
                 profit,
+                ..
             } => {
                 if is_profitable {
                     // push the results to a vec
+                    return_index.push(Uint128::from(index as u128));
                     return_is_profitable.push(is_profitable);
                     return_directions.push(direction);
                     return_swap_amounts.push(swap_amounts);
@@ -210,6 +269,7 @@ pub fn any_cycles_profitable(deps: Deps, amount: Uint128) -> StdResult<QueryAnsw
     }
 
     Ok(QueryAnswer::IsAnyCycleProfitable {
+        index: return_index,
         is_profitable: return_is_profitable,
         direction: return_directions,
         swap_amounts: return_swap_amounts,
@@ -217,10 +277,9 @@ pub fn any_cycles_profitable(deps: Deps, amount: Uint128) -> StdResult<QueryAnsw
     })
 }
 
-pub fn adapter_balance(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
+// Everything sky holds is liquid, unrecognized assets read as zero
+fn liquid_balance(deps: Deps, asset: Addr) -> StdResult<Uint128> {
     let config = Config::load(deps.storage)?;
-    let viewing_key = ViewingKeys::load(deps.storage)?.0;
-    let self_addr = SelfAddr::load(deps.storage)?.0;
 
     let contract;
     if config.shd_token.address == asset {
@@ -230,65 +289,29 @@ pub fn adapter_balance(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswe
     } else if config.sscrt_token.address == asset {
         contract = config.sscrt_token.clone();
     } else {
-        return Ok(adapter::QueryAnswer::Unbondable {
-            amount: Uint128::zero(),
-        });
-    }
-
-    let res = snip20::QueryMsg::Balance {
-        address: self_addr.clone().to_string(),
-        key: viewing_key.clone(),
+        return Ok(Uint128::zero());
     }
-    .query(&deps.querier, &contract.clone())?;
 
-    let amount = match res {
-        snip20::QueryAnswer::Balance { amount } => amount,
-        _ => Uint128::zero(),
-    };
+    balance_of(deps, &config, &contract)
+}
 
-    Ok(adapter::QueryAnswer::Unbondable {
-        amount: Uint128::new(amount.u128()),
+pub fn adapter_balance(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
+    Ok(adapter::QueryAnswer::Balance {
+        amount: liquid_balance(deps, asset)?,
     })
 }
 
+// Unbonds are paid out immediately, so nothing is ever left to claim. The manager counts
+// claimable funds as unbonding, reporting the liquid balance here would have it top sky up on
+// every update
 pub fn adapter_claimable(_deps: Deps, _asset: Addr) -> StdResult<adapter::QueryAnswer> {
     Ok(adapter::QueryAnswer::Claimable {
         amount: Uint128::zero(),
-    })
 }
 
-// Same as adapter_balance
 pub fn adapter_unbondable(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
-    let config = Config::load(deps.storage)?;
-    let viewing_key = ViewingKeys::load(deps.storage)?.0;
-    let self_addr = SelfAddr::load(deps.storage)?.0;
-
-    let contract;
-    if config.shd_token.address == asset {
-        contract = config.shd_token.clone();
-    } else if config.silk_token.address == asset {
-        contract = config.silk_token.clone();
-    } else if config.sscrt_token.address == asset {
-        contract = config.sscrt_token.clone();
-    } else {
-        return Ok(adapter::QueryAnswer::Unbondable {
-            amount: Uint128::zero(),
-        });
-    }
-
-    let res = snip20::QueryMsg::Balance {
-        address: self_addr.clone().to_string(),
-        key: viewing_key.clone(),
-    }
-    .query(&deps.querier, &contract.clone())?;
-
-    let amount = match res {
-        snip20::QueryAnswer::Balance { amount } => amount,
-        _ => Uint128::zero(),
-    };
-
     Ok(adapter::QueryAnswer::Unbondable {
-        amount: Uint128::new(amount.u128()),
+        amount: liquid_balance(deps, asset)?,
     })
 }
 
@@ -298,37 +321,8 @@ pub fn adapter_unbonding(_deps: Deps, _asset: Addr) -> StdResult<adapter::QueryA
     })
 }
 
-// Same as adapter_balance
 pub fn adapter_reserves(deps: Deps, asset: Addr) -> StdResult<adapter::QueryAnswer> {
-    let config = Config::load(deps.storage)?;
-    let viewing_key = ViewingKeys::load(deps.storage)?.0;
-    let self_addr = SelfAddr::load(deps.storage)?.0;
-
-    let contract;
-    if config.shd_token.address == asset {
-        contract = config.shd_token.clone();
-    } else if config.silk_token.address == asset {
-        contract = config.silk_token.clone();
-    } else if config.sscrt_token.address == asset {
-        contract = config.sscrt_token.clone();
-    } else {
-        return Ok(adapter::QueryAnswer::Unbondable {
-            amount: Uint128::zero(),
-        });
-    }
-
-    let res = snip20::QueryMsg::Balance {
-        address: self_addr.clone().to_string(),
-        key: viewing_key.clone(),
-    }
-    .query(&deps.querier, &contract.clone())?;
-
-    let amount = match res {
-        snip20::QueryAnswer::Balance { amount } => amount,
-        _ => Uint128::zero(),
-    };
-
-    Ok(adapter::QueryAnswer::Unbondable {
-        amount: Uint128::new(amount.u128()),
+    Ok(adapter::QueryAnswer::Reserves {
+        amount: liquid_balance(deps, asset)?,
     })
 }
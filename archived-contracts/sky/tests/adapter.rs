@@ -0,0 +1,100 @@
+use shade_protocol::{
+    c_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env},
+        to_binary,
+        Addr,
+        ContractResult,
+        SystemError,
+        SystemResult,
+        Uint128,
+        WasmQuery,
+    },
+    contract_interfaces::{
+        dao::adapter,
+        sky::{QueryMsg, SelfAddr, ViewingKeys},
+        snip20,
+    },
+    utils::storage::plus::ItemStorage,
+};
+use sky::contract::query;
+
+mod common;
+use common::config;
+
+#[test]
+fn adapter_queries_report_liquid_balance() {
+    let mut deps = mock_dependencies();
+    // Every token reports the same snip20 balance
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { .. } => SystemResult::Ok(ContractResult::Ok(
+            to_binary(&snip20::QueryAnswer::Balance {
+                amount: Uint128::new(500),
+            })
+            .unwrap(),
+        )),
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+
+    config().save(deps.as_mut().storage).unwrap();
+    SelfAddr(Addr::unchecked("sky"))
+        .save(deps.as_mut().storage)
+        .unwrap();
+    ViewingKeys("key".to_string())
+        .save(deps.as_mut().storage)
+        .unwrap();
+
+    let adapter_query = |msg: adapter::SubQueryMsg| -> adapter::QueryAnswer {
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Adapter(msg)).unwrap()).unwrap()
+    };
+
+    let asset = "shd".to_string();
+    assert_eq!(
+        adapter_query(adapter::SubQueryMsg::Balance {
+            asset: asset.clone()
+        }),
+        adapter::QueryAnswer::Balance {
+            amount: Uint128::new(500)
+        }
+    );
+    assert_eq!(
+        adapter_query(adapter::SubQueryMsg::Reserves {
+            asset: asset.clone()
+        }),
+        adapter::QueryAnswer::Reserves {
+            amount: Uint128::new(500)
+        }
+    );
+    assert_eq!(
+        adapter_query(adapter::SubQueryMsg::Unbondable {
+            asset: asset.clone()
+        }),
+        adapter::QueryAnswer::Unbondable {
+            amount: Uint128::new(500)
+        }
+    );
+    assert_eq!(
+        adapter_query(adapter::SubQueryMsg::Unbonding {
+            asset: asset.clone()
+        }),
+        adapter::QueryAnswer::Unbonding {
+            amount: Uint128::zero()
+        }
+    );
+    assert_eq!(
+        adapter_query(adapter::SubQueryMsg::Claimable { asset }),
+        adapter::QueryAnswer::Claimable {
+            amount: Uint128::zero()
+        }
+    );
+
+    // Assets sky doesn't hold read as empty
+    assert_eq!(
+        adapter_query(adapter::SubQueryMsg::Balance {
+            asset: "other".to_string()
+        }),
+        adapter::QueryAnswer::Balance {
+            amount: Uint128::zero()
+        }
+    );
+}
@@ -0,0 +1,74 @@
+use shade_protocol::{
+    c_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env, mock_info},
+        CosmosMsg,
+        Response,
+        Uint128,
+        WasmMsg,
+    },
+    contract_interfaces::sky::{Config, Cycles, ExecuteMsg, LastExecution},
+    utils::storage::plus::{ItemStorage, MapStorage},
+};
+use sky::execute::try_arb_all_cycles;
+
+mod common;
+use common::{config, cycle, mock_pairs};
+
+fn dispatched_indexes(res: &Response) -> Vec<Uint128> {
+    res.messages
+        .iter()
+        .map(|sub| match &sub.msg {
+            CosmosMsg::Wasm(WasmMsg::Execute { msg, .. }) => match from_binary(msg).unwrap() {
+                ExecuteMsg::ArbCycle { index, .. } => index,
+                _ => panic!("Unexpected message"),
+            },
+            _ => panic!("Unexpected message"),
+        })
+        .collect()
+}
+
+#[test]
+fn arb_all_cycles_uses_stored_index() {
+    let mut deps = mock_dependencies();
+    // pair_a and pair_b lose on the way around, pair_c and pair_d win
+    mock_pairs(&mut deps, &[
+        ("pair_a", 900_000),
+        ("pair_b", 900_000),
+        ("pair_c", 1_200_000),
+        ("pair_d", 1_100_000),
+    ]);
+
+    Config {
+        execution_cooldown: 5,
+        ..config()
+    }
+    .save(deps.as_mut().storage)
+    .unwrap();
+    Cycles(vec![
+        cycle("pair_a", "pair_b"),
+        cycle("pair_a", "pair_b"),
+        cycle("pair_c", "pair_d"),
+    ])
+    .save(deps.as_mut().storage)
+    .unwrap();
+
+    let amount = Uint128::new(1_000_000);
+    let mut env = mock_env();
+    let res =
+        try_arb_all_cycles(deps.as_mut(), env.clone(), mock_info("arber", &[]), amount).unwrap();
+    assert_eq!(dispatched_indexes(&res), vec![Uint128::new(2)]);
+
+    // A cycle cooling down is left out instead of failing the whole batch
+    LastExecution(env.block.height)
+        .save(deps.as_mut().storage, 2)
+        .unwrap();
+    env.block.height += 4;
+    let res =
+        try_arb_all_cycles(deps.as_mut(), env.clone(), mock_info("arber", &[]), amount).unwrap();
+    assert!(dispatched_indexes(&res).is_empty());
+
+    env.block.height += 1;
+    let res = try_arb_all_cycles(deps.as_mut(), env, mock_info("arber", &[]), amount).unwrap();
+    assert_eq!(dispatched_indexes(&res), vec![Uint128::new(2)]);
+}
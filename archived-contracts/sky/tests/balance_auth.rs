@@ -0,0 +1,95 @@
+use shade_protocol::{
+    c_std::{
+        from_binary,
+        testing::mock_dependencies,
+        to_binary,
+        Addr,
+        Binary,
+        ContractResult,
+        SystemError,
+        SystemResult,
+        Uint128,
+        WasmQuery,
+    },
+    contract_interfaces::{
+        sky::{BalanceAuth, QueryAnswer, SelfAddr, ViewingKeys},
+        snip20::{self, Permission, PermitParams, QueryPermit},
+    },
+    query_authentication::transaction::{PermitSignature, PubKey},
+    utils::storage::plus::ItemStorage,
+};
+use sky::query::get_balances;
+
+mod common;
+use common::config;
+
+fn permit() -> QueryPermit {
+    QueryPermit {
+        params: PermitParams {
+            allowed_tokens: vec![
+                Addr::unchecked("shd"),
+                Addr::unchecked("silk"),
+                Addr::unchecked("sscrt"),
+            ],
+            permit_name: "sky".to_string(),
+            permissions: vec![Permission::Balance],
+        },
+        signature: PermitSignature {
+            pub_key: PubKey::new(Binary::from(vec![2; 33])),
+            signature: Binary::from(vec![0; 64]),
+        },
+        account_number: None,
+        chain_id: Some("chain".to_string()),
+        sequence: None,
+        memo: None,
+    }
+}
+
+#[test]
+fn balances_with_permit() {
+    let mut deps = mock_dependencies();
+    // Mock snip20 that only answers permit balance queries
+    deps.querier.update_wasm(|query| match query {
+        WasmQuery::Smart { msg, .. } => match from_binary(msg) {
+            Ok(snip20::QueryMsg::WithPermit {
+                permit: Some(_),
+                query: snip20::QueryWithPermit::Balance {},
+                ..
+            }) => SystemResult::Ok(ContractResult::Ok(
+                to_binary(&snip20::QueryAnswer::Balance {
+                    amount: Uint128::new(500),
+                })
+                .unwrap(),
+            )),
+            _ => SystemResult::Ok(ContractResult::Err("expected a permit".to_string())),
+        },
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+
+    let mut config = config();
+    config.save(deps.as_mut().storage).unwrap();
+    ViewingKeys("key".to_string())
+        .save(deps.as_mut().storage)
+        .unwrap();
+    SelfAddr(Addr::unchecked("sky"))
+        .save(deps.as_mut().storage)
+        .unwrap();
+
+    assert!(get_balances(deps.as_ref()).is_err());
+
+    config.balance_auth = BalanceAuth::Permit { permit: permit() };
+    config.save(deps.as_mut().storage).unwrap();
+
+    match get_balances(deps.as_ref()).unwrap() {
+        QueryAnswer::Balance {
+            shd_bal,
+            silk_bal,
+            sscrt_bal,
+        } => {
+            assert_eq!(shd_bal, Uint128::new(500));
+            assert_eq!(silk_bal, Uint128::new(500));
+            assert_eq!(sscrt_bal, Uint128::new(500));
+        }
+        _ => panic!("Unexpected query answer"),
+    }
+}
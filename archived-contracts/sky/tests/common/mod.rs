@@ -0,0 +1,107 @@
+#![allow(dead_code)]
+
+use shade_protocol::{
+    admin::ValidateAdminPermissionResponse,
+    c_std::{
+        testing::{MockApi, MockQuerier, MockStorage},
+        to_binary,
+        Addr,
+        ContractResult,
+        Decimal,
+        OwnedDeps,
+        SystemError,
+        SystemResult,
+        Uint128,
+        WasmQuery,
+    },
+    contract_interfaces::{
+        dex::{dex::Dex, shadeswap},
+        sky::{
+            cycles::{ArbPair, Cycle},
+            BalanceAuth,
+            Config,
+        },
+    },
+    utils::asset::Contract,
+};
+
+pub fn contract(address: &str) -> Contract {
+    Contract {
+        address: Addr::unchecked(address),
+        code_hash: "hash".to_string(),
+    }
+}
+
+pub fn pair(name: &str, token0: &str, token1: &str) -> ArbPair {
+    ArbPair {
+        pair_contract: Some(Contract {
+            address: Addr::unchecked(name),
+            code_hash: name.to_string(),
+        }),
+        mint_info: None,
+        token0: contract(token0),
+        token0_decimals: Uint128::new(6),
+        token0_amount: None,
+        token1: contract(token1),
+        token1_decimals: Uint128::new(6),
+        token1_amount: None,
+        dex: Dex::ShadeSwap,
+        fee: None,
+    }
+}
+
+// shd -> silk -> shd through the two pairs
+pub fn cycle(first: &str, second: &str) -> Cycle {
+    Cycle {
+        pair_addrs: vec![pair(first, "shd", "silk"), pair(second, "silk", "shd")],
+        start_addr: contract("shd"),
+        enabled: true,
+    }
+}
+
+pub fn config() -> Config {
+    Config {
+        shade_admin: contract("admin"),
+        shd_token: contract("shd"),
+        silk_token: contract("silk"),
+        sscrt_token: contract("sscrt"),
+        treasury: contract("treasury"),
+        payback_rate: Decimal::percent(50),
+        min_amount: Uint128::zero(),
+        max_hops: 3,
+        balance_auth: BalanceAuth::ViewingKey,
+        execution_cooldown: 0,
+    }
+}
+
+// Each mock pair quotes a fixed return regardless of the offer, the admin allows everything
+pub fn mock_pairs(
+    deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>,
+    quotes: &'static [(&'static str, u128)],
+) {
+    deps.querier.update_wasm(move |query| match query {
+        WasmQuery::Smart { contract_addr, .. } if contract_addr == "admin" => {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&ValidateAdminPermissionResponse {
+                    has_permission: true,
+                })
+                .unwrap(),
+            ))
+        }
+        WasmQuery::Smart { contract_addr, .. } => {
+            let quote = quotes
+                .iter()
+                .find(|(pair, _)| *pair == contract_addr.as_str());
+            match quote {
+                Some((_, quote)) => SystemResult::Ok(ContractResult::Ok(
+                    to_binary(&shadeswap::QueryMsgResponse::EstimatedPrice {
+                        estimated_price: Uint128::new(*quote),
+                    })
+                    .unwrap(),
+                )),
+                None => SystemResult::Err(SystemError::Unknown {}),
+            }
+        }
+        _ => SystemResult::Err(SystemError::Unknown {}),
+    });
+}
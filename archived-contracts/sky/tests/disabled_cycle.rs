@@ -0,0 +1,64 @@
+use shade_protocol::{
+    c_std::{testing::mock_dependencies, Uint128},
+    contract_interfaces::sky::{cycles::Cycle, Cycles, QueryAnswer},
+    utils::storage::plus::ItemStorage,
+};
+use sky::query::{any_cycles_profitable, cycle_profitability};
+
+mod common;
+use common::{config, contract, pair};
+
+#[test]
+fn disabled_cycle_skipped_by_scan() {
+    let mut deps = mock_dependencies();
+
+    config().save(deps.as_mut().storage).unwrap();
+
+    // The pair contract doesn't exist, so simulating this cycle fails
+    let mut cycle = Cycle {
+        pair_addrs: vec![pair("pair", "shd", "silk")],
+        start_addr: contract("shd"),
+        enabled: true,
+    };
+    Cycles(vec![cycle.clone()])
+        .save(deps.as_mut().storage)
+        .unwrap();
+    assert!(any_cycles_profitable(deps.as_ref(), Uint128::new(100)).is_err());
+
+    cycle.enabled = false;
+    Cycles(vec![cycle]).save(deps.as_mut().storage).unwrap();
+    match any_cycles_profitable(deps.as_ref(), Uint128::new(100)).unwrap() {
+        QueryAnswer::IsAnyCycleProfitable { is_profitable, .. } => {
+            assert!(is_profitable.is_empty())
+        }
+        _ => panic!("Unexpected query answer"),
+    }
+}
+
+#[test]
+fn disabled_cycle_directly_queryable() {
+    let mut deps = mock_dependencies();
+
+    config().save(deps.as_mut().storage).unwrap();
+
+    // An empty path can be simulated without any pair contracts
+    Cycles(vec![Cycle {
+        pair_addrs: vec![],
+        start_addr: contract("shd"),
+        enabled: false,
+    }])
+    .save(deps.as_mut().storage)
+    .unwrap();
+
+    match cycle_profitability(deps.as_ref(), Uint128::new(100), Uint128::zero()).unwrap() {
+        QueryAnswer::IsCycleProfitable {
+            is_profitable,
+            enabled,
+            ..
+        } => {
+            assert!(!is_profitable);
+            assert!(!enabled);
+        }
+        _ => panic!("Unexpected query answer"),
+    }
+}
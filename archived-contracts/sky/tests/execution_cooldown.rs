@@ -0,0 +1,136 @@
+use shade_protocol::{
+    c_std::{
+        testing::{mock_dependencies, mock_env, mock_info},
+        Uint128,
+    },
+    contract_interfaces::sky::{Config, Cycles},
+    utils::storage::plus::ItemStorage,
+};
+use sky::{
+    execute::{try_arb_cycle, try_remove_cycle, try_set_cycles},
+    query::cycle_profitability,
+};
+
+mod common;
+use common::{config, cycle, mock_pairs};
+
+#[test]
+fn cycle_cooldown() {
+    let mut deps = mock_dependencies();
+    mock_pairs(&mut deps, &[("pair_a", 1_200_000), ("pair_b", 1_100_000)]);
+
+    Config {
+        execution_cooldown: 5,
+        ..config()
+    }
+    .save(deps.as_mut().storage)
+    .unwrap();
+    Cycles(vec![cycle("pair_a", "pair_b")])
+        .save(deps.as_mut().storage)
+        .unwrap();
+
+    let amount = Uint128::new(1_000_000);
+    let mut env = mock_env();
+    try_arb_cycle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("arber", &[]),
+        amount,
+        Uint128::zero(),
+    )
+    .unwrap();
+
+    env.block.height += 4;
+    assert!(
+        try_arb_cycle(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("arber", &[]),
+            amount,
+            Uint128::zero(),
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("cooling down")
+    );
+
+    // The cycle can still be checked while it cools down
+    assert!(cycle_profitability(deps.as_ref(), amount, Uint128::zero()).is_ok());
+
+    env.block.height += 1;
+    try_arb_cycle(
+        deps.as_mut(),
+        env,
+        mock_info("arber", &[]),
+        amount,
+        Uint128::zero(),
+    )
+    .unwrap();
+}
+
+#[test]
+fn cooldown_follows_cycle() {
+    let mut deps = mock_dependencies();
+    mock_pairs(&mut deps, &[
+        ("pair_a", 1_200_000),
+        ("pair_b", 1_100_000),
+        ("pair_c", 1_200_000),
+        ("pair_d", 1_100_000),
+    ]);
+
+    Config {
+        execution_cooldown: 5,
+        ..config()
+    }
+    .save(deps.as_mut().storage)
+    .unwrap();
+    Cycles(vec![cycle("pair_a", "pair_b"), cycle("pair_c", "pair_d")])
+        .save(deps.as_mut().storage)
+        .unwrap();
+
+    let amount = Uint128::new(1_000_000);
+    let env = mock_env();
+    try_arb_cycle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("arber", &[]),
+        amount,
+        Uint128::new(1),
+    )
+    .unwrap();
+
+    // The executed cycle moves to index 0 and keeps its cooldown
+    try_remove_cycle(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("admin", &[]),
+        Uint128::zero(),
+    )
+    .unwrap();
+    assert!(
+        try_arb_cycle(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("arber", &[]),
+            amount,
+            Uint128::zero(),
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("cooling down")
+    );
+
+    // Replaced cycles don't inherit the cooldown
+    try_set_cycles(deps.as_mut(), env.clone(), mock_info("admin", &[]), vec![
+        cycle("pair_a", "pair_b"),
+    ])
+    .unwrap();
+    try_arb_cycle(
+        deps.as_mut(),
+        env,
+        mock_info("arber", &[]),
+        amount,
+        Uint128::zero(),
+    )
+    .unwrap();
+}
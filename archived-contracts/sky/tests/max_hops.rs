@@ -0,0 +1,53 @@
+use shade_protocol::{
+    c_std::testing::{mock_dependencies, mock_env, mock_info},
+    contract_interfaces::sky::{cycles::Cycle, Config, Cycles},
+    utils::storage::plus::ItemStorage,
+};
+use sky::execute::try_append_cycle;
+
+mod common;
+use common::{config, contract, mock_pairs, pair};
+
+#[test]
+fn register_cycle_max_hops() {
+    let mut deps = mock_dependencies();
+    mock_pairs(&mut deps, &[]);
+
+    Config {
+        max_hops: 2,
+        ..config()
+    }
+    .save(deps.as_mut().storage)
+    .unwrap();
+    Cycles(vec![]).save(deps.as_mut().storage).unwrap();
+
+    let at_limit = Cycle {
+        pair_addrs: vec![pair("pair_a", "shd", "silk"), pair("pair_b", "silk", "shd")],
+        start_addr: contract("shd"),
+        enabled: true,
+    };
+    try_append_cycle(deps.as_mut(), mock_env(), mock_info("admin", &[]), vec![
+        at_limit,
+    ])
+    .unwrap();
+
+    let over_limit = Cycle {
+        pair_addrs: vec![
+            pair("pair_a", "shd", "silk"),
+            pair("pair_b", "silk", "sscrt"),
+            pair("pair_c", "sscrt", "shd"),
+        ],
+        start_addr: contract("shd"),
+        enabled: true,
+    };
+    assert!(
+        try_append_cycle(deps.as_mut(), mock_env(), mock_info("admin", &[]), vec![
+            over_limit,
+        ])
+        .unwrap_err()
+        .to_string()
+        .contains("the maximum is 2")
+    );
+
+    assert_eq!(Cycles::load(deps.as_ref().storage).unwrap().0.len(), 1);
+}
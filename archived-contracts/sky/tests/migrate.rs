@@ -0,0 +1,127 @@
+use shade_protocol::{
+    c_std::{
+        testing::{mock_dependencies, mock_env},
+        Decimal,
+        Storage,
+        Uint128,
+    },
+    contract_interfaces::sky::{BalanceAuth, Config, Cycles, MigrateMsg, SchemaVersion},
+    utils::storage::plus::ItemStorage,
+};
+use sky::{
+    contract::migrate,
+    migration::{MIGRATED_MAX_HOPS, SCHEMA_VERSION},
+};
+
+// Config as it was stored before min_amount, max_hops and balance_auth
+const OLD_CONFIG: &str = r#"{
+    "shade_admin": {"address": "admin", "code_hash": "hash"},
+    "shd_token": {"address": "shd", "code_hash": "hash"},
+    "silk_token": {"address": "silk", "code_hash": "hash"},
+    "sscrt_token": {"address": "sscrt", "code_hash": "hash"},
+    "treasury": {"address": "treasury", "code_hash": "hash"},
+    "payback_rate": "0.5"
+}"#;
+
+// A cycle as it was stored before pairs carried a fee
+const OLD_CYCLES: &str = r#"[{
+    "pair_addrs": [{
+        "pair_contract": {"address": "pair", "code_hash": "hash"},
+        "mint_info": null,
+        "token0": {"address": "shd", "code_hash": "hash"},
+        "token0_decimals": "8",
+        "token0_amount": null,
+        "token1": {"address": "silk", "code_hash": "hash"},
+        "token1_decimals": "6",
+        "token1_amount": null,
+        "dex": "shade_swap"
+    }],
+    "start_addr": {"address": "shd", "code_hash": "hash"}
+}]"#;
+
+#[test]
+fn migrate_fills_pair_fee() {
+    let mut deps = mock_dependencies();
+    deps.storage.set(b"item_config", OLD_CONFIG.as_bytes());
+    deps.storage.set(b"item_cycles", OLD_CYCLES.as_bytes());
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {
+        default_fee: Some(Uint128::new(30)),
+    })
+    .unwrap();
+
+    let cycles = Cycles::load(deps.as_ref().storage).unwrap();
+    assert_eq!(cycles.0[0].pair_addrs[0].fee, Some(Uint128::new(30)));
+    assert!(cycles.0[0].enabled);
+    assert_eq!(
+        SchemaVersion::load(deps.as_ref().storage).unwrap().0,
+        SCHEMA_VERSION
+    );
+}
+
+#[test]
+fn migrate_rejects_large_fee() {
+    let mut deps = mock_dependencies();
+    deps.storage.set(b"item_config", OLD_CONFIG.as_bytes());
+    deps.storage.set(b"item_cycles", OLD_CYCLES.as_bytes());
+
+    assert!(
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {
+            default_fee: Some(Uint128::new(10_001)),
+        })
+        .is_err()
+    );
+}
+
+#[test]
+fn migrate_fills_config() {
+    let mut deps = mock_dependencies();
+    deps.storage.set(b"item_config", OLD_CONFIG.as_bytes());
+    deps.storage.set(b"item_cycles", OLD_CYCLES.as_bytes());
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg { default_fee: None }).unwrap();
+
+    let config = Config::load(deps.as_ref().storage).unwrap();
+    assert_eq!(config.shd_token.address.as_str(), "shd");
+    assert_eq!(config.payback_rate, Decimal::percent(50));
+    assert_eq!(config.min_amount, Uint128::zero());
+    assert_eq!(config.max_hops, MIGRATED_MAX_HOPS);
+    assert_eq!(config.balance_auth, BalanceAuth::ViewingKey);
+    assert_eq!(config.execution_cooldown, 0);
+
+    // Running it again on the migrated layout changes nothing
+    migrate(deps.as_mut(), mock_env(), MigrateMsg { default_fee: None }).unwrap();
+    assert_eq!(Config::load(deps.as_ref().storage).unwrap(), config);
+}
+
+#[test]
+fn migrate_adds_execution_cooldown() {
+    let mut deps = mock_dependencies();
+    // A v2 config, carrying everything but the cooldown
+    deps.storage.set(
+        b"item_config",
+        br#"{
+            "shade_admin": {"address": "admin", "code_hash": "hash"},
+            "shd_token": {"address": "shd", "code_hash": "hash"},
+            "silk_token": {"address": "silk", "code_hash": "hash"},
+            "sscrt_token": {"address": "sscrt", "code_hash": "hash"},
+            "treasury": {"address": "treasury", "code_hash": "hash"},
+            "payback_rate": "0.5",
+            "min_amount": "100",
+            "max_hops": 5,
+            "balance_auth": "viewing_key"
+        }"#,
+    );
+    SchemaVersion(2).save(deps.as_mut().storage).unwrap();
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg { default_fee: None }).unwrap();
+
+    let config = Config::load(deps.as_ref().storage).unwrap();
+    assert_eq!(config.min_amount, Uint128::new(100));
+    assert_eq!(config.max_hops, 5);
+    assert_eq!(config.execution_cooldown, 0);
+    assert_eq!(
+        SchemaVersion::load(deps.as_ref().storage).unwrap().0,
+        SCHEMA_VERSION
+    );
+}
@@ -0,0 +1,37 @@
+use shade_protocol::{
+    c_std::{testing::mock_dependencies, Uint128},
+    contract_interfaces::sky::{Config, Cycles},
+    utils::storage::plus::ItemStorage,
+};
+use sky::query::cycle_profitability;
+
+mod common;
+use common::config;
+
+#[test]
+fn cycle_profitability_below_min_amount() {
+    let mut deps = mock_dependencies();
+
+    Config {
+        min_amount: Uint128::new(100),
+        ..config()
+    }
+    .save(deps.as_mut().storage)
+    .unwrap();
+    Cycles(vec![]).save(deps.as_mut().storage).unwrap();
+
+    assert!(
+        cycle_profitability(deps.as_ref(), Uint128::new(99), Uint128::zero())
+            .unwrap_err()
+            .to_string()
+            .contains("below the minimum")
+    );
+
+    // At the minimum the amount is accepted, failing only on the empty cycle list
+    assert!(
+        cycle_profitability(deps.as_ref(), Uint128::new(100), Uint128::zero())
+            .unwrap_err()
+            .to_string()
+            .contains("out of bounds")
+    );
+}
@@ -0,0 +1,67 @@
+use shade_protocol::{
+    c_std::{testing::mock_dependencies, Uint128},
+    contract_interfaces::sky::{cycles::ArbPair, QueryAnswer},
+    utils::storage::plus::ItemStorage,
+};
+use sky::query::simulate_cycle;
+
+mod common;
+use common::{config, contract, mock_pairs, pair};
+
+fn fee_pair(name: &str, token0: &str, token1: &str, fee: Option<u128>) -> ArbPair {
+    ArbPair {
+        fee: fee.map(Uint128::new),
+        ..pair(name, token0, token1)
+    }
+}
+
+fn profit(answer: QueryAnswer) -> Uint128 {
+    match answer {
+        QueryAnswer::IsCycleProfitable { profit, .. } => profit,
+        _ => panic!("Unexpected query answer"),
+    }
+}
+
+#[test]
+fn mixed_fee_cycle() {
+    let mut deps = mock_dependencies();
+    mock_pairs(&mut deps, &[("pair_a", 1_200_000), ("pair_b", 1_100_000)]);
+
+    config().save(deps.as_mut().storage).unwrap();
+
+    let amount = Uint128::new(1_000_000);
+
+    // Unset fees leave the quotes untouched
+    assert_eq!(
+        profit(
+            simulate_cycle(
+                deps.as_ref(),
+                vec![
+                    fee_pair("pair_a", "shd", "silk", None),
+                    fee_pair("pair_b", "silk", "shd", None),
+                ],
+                contract("shd"),
+                amount,
+            )
+            .unwrap()
+        ),
+        Uint128::new(100_000)
+    );
+
+    // 0.3% of 1_200_000 is 3_600, 1% of 1_100_000 is 11_000
+    assert_eq!(
+        profit(
+            simulate_cycle(
+                deps.as_ref(),
+                vec![
+                    fee_pair("pair_a", "shd", "silk", Some(30)),
+                    fee_pair("pair_b", "silk", "shd", Some(100)),
+                ],
+                contract("shd"),
+                amount,
+            )
+            .unwrap()
+        ),
+        Uint128::new(89_000)
+    );
+}
@@ -0,0 +1,56 @@
+use shade_protocol::{
+    c_std::{testing::mock_dependencies, Uint128},
+    contract_interfaces::sky::{Cycles, QueryAnswer},
+    utils::storage::plus::ItemStorage,
+};
+use sky::query::simulate_cycle;
+
+mod common;
+use common::{config, contract, mock_pairs, pair};
+
+#[test]
+fn simulate_unregistered_cycle() {
+    let mut deps = mock_dependencies();
+    mock_pairs(&mut deps, &[("pair_a", 120), ("pair_b", 110)]);
+
+    config().save(deps.as_mut().storage).unwrap();
+    // Nothing is registered
+    Cycles(vec![]).save(deps.as_mut().storage).unwrap();
+
+    match simulate_cycle(
+        deps.as_ref(),
+        vec![pair("pair_a", "shd", "silk"), pair("pair_b", "silk", "shd")],
+        contract("shd"),
+        Uint128::new(100),
+    )
+    .unwrap()
+    {
+        QueryAnswer::IsCycleProfitable {
+            is_profitable,
+            swap_amounts,
+            profit,
+            ..
+        } => {
+            assert!(is_profitable);
+            assert_eq!(swap_amounts, vec![
+                Uint128::new(100),
+                Uint128::new(120),
+                Uint128::new(110)
+            ]);
+            assert_eq!(profit, Uint128::new(10));
+        }
+        _ => panic!("Unexpected query answer"),
+    }
+
+    // Cycles that couldn't be registered can't be simulated either
+    assert!(
+        simulate_cycle(
+            deps.as_ref(),
+            vec![pair("pair_a", "shd", "silk")],
+            contract("sscrt"),
+            Uint128::new(100),
+        )
+        .is_err()
+    );
+    assert!(simulate_cycle(deps.as_ref(), vec![], contract("shd"), Uint128::new(100)).is_err());
+}
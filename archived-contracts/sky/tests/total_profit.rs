@@ -0,0 +1,60 @@
+use shade_protocol::{
+    c_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Addr,
+        Deps,
+        Uint128,
+    },
+    contract_interfaces::sky::{Cycles, QueryAnswer, QueryMsg, TokenProfit},
+    utils::storage::plus::ItemStorage,
+};
+use sky::{contract::query, execute::try_arb_cycle};
+
+mod common;
+use common::{config, cycle, mock_pairs};
+
+fn total_profit(deps: Deps) -> Vec<TokenProfit> {
+    match from_binary(&query(deps, mock_env(), QueryMsg::TotalProfit {}).unwrap()).unwrap() {
+        QueryAnswer::TotalProfit { profits } => profits,
+        _ => panic!("Unexpected query answer"),
+    }
+}
+
+#[test]
+fn total_profit_sums_cycles() {
+    let mut deps = mock_dependencies();
+    mock_pairs(&mut deps, &[
+        ("pair_a", 1_200_000),
+        ("pair_b", 1_100_000),
+        ("pair_c", 1_050_000),
+    ]);
+
+    config().save(deps.as_mut().storage).unwrap();
+    Cycles(vec![cycle("pair_a", "pair_b"), cycle("pair_a", "pair_c")])
+        .save(deps.as_mut().storage)
+        .unwrap();
+
+    // Nothing is booked before a cycle runs
+    assert!(total_profit(deps.as_ref()).is_empty());
+
+    let amount = Uint128::new(1_000_000);
+    for index in [0u128, 1] {
+        let res = try_arb_cycle(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("arber", &[]),
+            amount,
+            Uint128::new(index),
+        )
+        .unwrap();
+        // A swap per pair and the payback, profit is only booked alongside them
+        assert_eq!(res.messages.len(), 3);
+    }
+
+    // 100_000 from the first cycle and 50_000 from the second
+    assert_eq!(total_profit(deps.as_ref()), vec![TokenProfit {
+        token: Addr::unchecked("shd"),
+        amount: Uint128::new(150_000),
+    }]);
+}
@@ -0,0 +1,136 @@
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    sky::Sky,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Decimal, Uint128},
+    contract_interfaces::{
+        dao::{
+            adapter,
+            manager,
+            treasury_manager::{self, AllocationType, RawAllocation},
+        },
+        sky,
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn token(app: &mut App, admin: &Addr, symbol: &str, balance: Uint128) -> ContractInfo {
+    snip20::InstantiateMsg {
+        name: symbol.to_lowercase(),
+        admin: Some(admin.to_string()),
+        symbol: symbol.into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string(),
+            amount: balance,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), app, admin.clone(), symbol, &[])
+    .unwrap()
+}
+
+fn adapter_balance(app: &App, adapter: &ContractInfo, token: &ContractInfo) -> Uint128 {
+    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Balance {
+        asset: token.address.to_string(),
+    })
+    .test_query(adapter, app)
+    .unwrap()
+    {
+        adapter::QueryAnswer::Balance { amount } => amount,
+        _ => panic!("query failed"),
+    }
+}
+
+/* Sky's funds stay liquid, so once its allocation is met a second update has nothing to send
+ */
+#[test]
+fn update_funds_sky_once() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let deposit = Uint128::new(1000);
+    let allocation = Uint128::new(500);
+
+    let shd = token(&mut app, &admin, "SHD", deposit);
+    let silk = token(&mut app, &admin, "SILK", Uint128::zero());
+    let sscrt = token(&mut app, &admin, "SSCRT", Uint128::zero());
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.clone().into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let sky = sky::InstantiateMsg {
+        shade_admin: admin_auth.into(),
+        shd_token: shd.clone().into(),
+        silk_token: silk.into(),
+        sscrt_token: sscrt.into(),
+        treasury: manager.clone().into(),
+        viewing_key: "viewing_key".to_string(),
+        payback_rate: Decimal::percent(50),
+        min_amount: Uint128::zero(),
+        max_hops: 3,
+        balance_auth: None,
+        execution_cooldown: 0,
+    }
+    .test_init(Sky::default(), &mut app, admin.clone(), "sky", &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: shd.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: shd.address.to_string(),
+        allocation: RawAllocation {
+            nick: Some("sky".to_string()),
+            contract: RawContract::from(sky.clone()),
+            alloc_type: AllocationType::Amount,
+            amount: allocation,
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&shd, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    for _ in 0..2 {
+        manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+            asset: shd.address.to_string(),
+        })
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .unwrap();
+
+        assert_eq!(adapter_balance(&app, &sky, &shd), allocation);
+    }
+}
@@ -0,0 +1,49 @@
+use shade_protocol::{
+    c_std::{
+        from_binary,
+        testing::{mock_dependencies, mock_env, mock_info},
+        Decimal,
+        Uint128,
+    },
+    contract_interfaces::sky::{InstantiateMsg, QueryMsg},
+    utils::version::VersionResponse,
+};
+use sky::{
+    contract::{instantiate, query},
+    migration::SCHEMA_VERSION,
+};
+
+mod common;
+use common::contract;
+
+#[test]
+fn version_after_instantiate() {
+    let mut deps = mock_dependencies();
+
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("admin", &[]),
+        InstantiateMsg {
+            shade_admin: contract("admin"),
+            shd_token: contract("shd"),
+            silk_token: contract("silk"),
+            sscrt_token: contract("sscrt"),
+            treasury: contract("treasury"),
+            viewing_key: "key".to_string(),
+            payback_rate: Decimal::percent(50),
+            min_amount: Uint128::zero(),
+            max_hops: 3,
+            balance_auth: None,
+            execution_cooldown: 0,
+        },
+    )
+    .unwrap();
+
+    let version: VersionResponse =
+        from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Version {}).unwrap()).unwrap();
+    assert_eq!(version, VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        schema: SCHEMA_VERSION,
+    });
+}
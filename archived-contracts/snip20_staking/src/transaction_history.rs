@@ -20,6 +20,7 @@ use crate::state::Config;
 
 const PREFIX_TXS: &[u8] = b"transactions";
 const PREFIX_TRANSFERS: &[u8] = b"transfers";
+const PREFIX_TRANSFER_COUNT: &[u8] = b"transfer_count";
 
 // Note that id is a globally incrementing counter.
 // Since it's 64 bits long, even at 50 tx/s it would take
@@ -94,6 +95,23 @@ pub struct RichTx {
     pub block_height: u64,
 }
 
+/// A single entry of an address's combined tx and transfer history
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum HistoryEntry {
+    Tx(RichTx),
+    Transfer(Tx),
+}
+
+impl HistoryEntry {
+    pub fn id(&self) -> u64 {
+        match self {
+            HistoryEntry::Tx(tx) => tx.id,
+            HistoryEntry::Transfer(transfer) => transfer.id,
+        }
+    }
+}
+
 // Stored types:
 
 /// This type is the stored version of the legacy transfers
@@ -389,15 +407,27 @@ impl StoredRichTx {
         })
     }
 
-    fn from_stored_legacy_transfer(transfer: StoredLegacyTransfer) -> Self {
-        let action = StoredTxAction::transfer(transfer.from, transfer.sender, transfer.receiver);
-        Self {
-            id: transfer.id,
-            action,
-            coins: transfer.coins,
-            memo: transfer.memo,
-            block_time: transfer.block_time,
-            block_height: transfer.block_height,
+    /// Rebuilds the legacy transfer view of a stored transfer, None for other tx types
+    fn into_legacy_transfer(self, api: &dyn Api) -> StdResult<Option<Tx>> {
+        if self.action.tx_type != TxCode::Transfer.to_u8() {
+            return Ok(None);
+        }
+        match self.action.into_humanized(api)? {
+            TxAction::Transfer {
+                from,
+                sender,
+                recipient,
+            } => Ok(Some(Tx {
+                id: self.id,
+                from,
+                sender,
+                receiver: recipient,
+                coins: self.coins,
+                memo: self.memo,
+                block_time: Some(self.block_time),
+                block_height: Some(self.block_height),
+            })),
+            _ => Ok(None),
         }
     }
 }
@@ -427,34 +457,26 @@ pub fn store_transfer(
         denom,
         amount: amount.into(),
     };
-    let transfer = StoredLegacyTransfer {
-        id,
-        from: owner.clone(),
-        sender: sender.clone(),
-        receiver: receiver.clone(),
-        coins,
-        memo,
-        block_time: block.time,
-        block_height: block.height,
-    };
-    let tx = StoredRichTx::from_stored_legacy_transfer(transfer.clone());
+    // Transfers are only stored as txs, get_transfers rebuilds the legacy view from them
+    let action = StoredTxAction::transfer(owner.clone(), sender.clone(), receiver.clone());
+    let tx = StoredRichTx::new(id, action, coins, memo, block);
 
     // Write to the owners history if it's different from the other two addresses
     if owner != sender && owner != receiver {
         // shade_protocol::c_std::debug_print("saving transaction history for owner");
         append_tx(store, &tx, owner)?;
-        append_transfer(store, &transfer, owner)?;
+        increment_transfer_count(store, owner);
     }
     // Write to the sender's history if it's different from the receiver
     if sender != receiver {
         // shade_protocol::c_std::debug_print("saving transaction history for sender");
         append_tx(store, &tx, sender)?;
-        append_transfer(store, &transfer, sender)?;
+        increment_transfer_count(store, sender);
     }
     // Always write to the recipient's history
     // shade_protocol::c_std::debug_print("saving transaction history for receiver");
     append_tx(store, &tx, receiver)?;
-    append_transfer(store, &transfer, receiver)?;
+    increment_transfer_count(store, receiver);
 
     Ok(())
 }
@@ -635,6 +657,22 @@ pub fn store_claim_reward(
     Ok(())
 }
 
+/// Number of transfers stored only as txs, the older ones are counted by the legacy store
+fn transfer_count(storage: &dyn Storage, for_address: &CanonicalAddr) -> u64 {
+    let store = ReadonlyPrefixedStorage::new(PREFIX_TRANSFER_COUNT, storage);
+    store
+        .get(for_address.as_slice())
+        .and_then(|count| count.try_into().ok())
+        .map(u64::from_be_bytes)
+        .unwrap_or_default()
+}
+
+fn increment_transfer_count(store: &mut dyn Storage, for_address: &CanonicalAddr) {
+    let count = transfer_count(store, for_address) + 1;
+    let mut store = PrefixedStorage::new(PREFIX_TRANSFER_COUNT, store);
+    store.set(for_address.as_slice(), &count.to_be_bytes());
+}
+
 fn append_tx(
     store: &mut dyn Storage,
     tx: &StoredRichTx,
@@ -645,16 +683,6 @@ fn append_tx(
     store.push(tx)
 }
 
-fn append_transfer(
-    store: &mut dyn Storage,
-    tx: &StoredLegacyTransfer,
-    for_address: &CanonicalAddr,
-) -> StdResult<()> {
-    let mut store = PrefixedStorage::multilevel(&[PREFIX_TRANSFERS, for_address.as_slice()], store);
-    let mut store = AppendStoreMut::attach_or_create(&mut store)?;
-    store.push(tx)
-}
-
 pub fn get_txs<A: Api, S: ReadonlyStorage>(
     api: &dyn Api,
     storage: &dyn Storage,
@@ -688,36 +716,333 @@ pub fn get_txs<A: Api, S: ReadonlyStorage>(
     txs.map(|txs| (txs, store.len() as u64))
 }
 
-pub fn get_transfers<A: Api, S: ReadonlyStorage>(
+/// Transfers used to be written to their own store next to the txs, doubling the writes of
+/// every transfer. New transfers are only kept as txs, so the history is the legacy records
+/// merged with the transfer txs that have no legacy copy, latest first.
+/// Both stores are read from the end, so only the entries up to the requested page are loaded.
+pub fn get_transfers(
     api: &dyn Api,
     storage: &dyn Storage,
     for_address: &CanonicalAddr,
     page: u32,
     page_size: u32,
 ) -> StdResult<(Vec<Tx>, u64)> {
-    let store =
+    let transfer_store =
         ReadonlyPrefixedStorage::multilevel(&[PREFIX_TRANSFERS, for_address.as_slice()], storage);
+    let legacy = match AppendStore::<StoredLegacyTransfer, _, _>::attach(&transfer_store) {
+        Some(store) => Some(store?),
+        None => None,
+    };
+    let tx_store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_TXS, for_address.as_slice()], storage);
+    let txs = match AppendStore::<StoredRichTx, _, _>::attach(&tx_store) {
+        Some(store) => Some(store?),
+        None => None,
+    };
 
-    // Try to access the storage of transfers for the account.
-    // If it doesn't exist yet, return an empty list of transfers.
-    let store = AppendStore::<StoredLegacyTransfer, _, _>::attach(&store);
-    let store = if let Some(result) = store {
-        result?
-    } else {
-        return Ok((vec![], 0));
+    // Every legacy record has a tx copy, the transfers after them are counted as they're stored
+    let total = legacy.as_ref().map_or(0, |store| store.len() as u64)
+        + transfer_count(storage, for_address);
+
+    let legacy_iter = legacy.iter().flat_map(|store| {
+        store
+            .iter()
+            .rev()
+            .map(|transfer| transfer.and_then(|transfer| transfer.into_humanized(api)))
+    });
+    let tx_iter = txs.iter().flat_map(|store| {
+        store
+            .iter()
+            .rev()
+            .filter_map(|tx| tx.and_then(|tx| tx.into_legacy_transfer(api)).transpose())
+    });
+
+    let transfers = merge_page(
+        legacy_iter,
+        tx_iter,
+        |transfer| transfer.id,
+        (page * page_size) as _,
+        page_size as _,
+    )?;
+
+    Ok((transfers, total))
+}
+
+/// Merges an address's txs and legacy transfers into one stream, latest first.
+/// Transfers are mirrored into txs under the same id, so only transfers without a matching tx
+/// are returned as `HistoryEntry::Transfer`.
+/// Both stores are read from the end, so only the entries up to the requested page are loaded.
+pub fn get_unified_history(
+    api: &dyn Api,
+    storage: &dyn Storage,
+    for_address: &CanonicalAddr,
+    page: u32,
+    page_size: u32,
+) -> StdResult<(Vec<HistoryEntry>, u64)> {
+    let tx_store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_TXS, for_address.as_slice()], storage);
+    let txs = match AppendStore::<StoredRichTx, _, _>::attach(&tx_store) {
+        Some(store) => Some(store?),
+        None => None,
+    };
+    let transfer_store =
+        ReadonlyPrefixedStorage::multilevel(&[PREFIX_TRANSFERS, for_address.as_slice()], storage);
+    let legacy = match AppendStore::<StoredLegacyTransfer, _, _>::attach(&transfer_store) {
+        Some(store) => Some(store?),
+        None => None,
     };
 
-    // Take `page_size` txs starting from the latest tx, potentially skipping `page * page_size`
-    // txs from the start.
-    let transfer_iter = store
-        .iter()
-        .rev()
-        .skip((page * page_size) as _)
-        .take(page_size as _);
+    // Legacy records were always written together with their tx copy
+    let total = txs.as_ref().map_or(0, |store| store.len() as u64);
+
+    let tx_iter = txs.iter().flat_map(|store| {
+        store
+            .iter()
+            .rev()
+            .map(|tx| tx.and_then(|tx| Ok(HistoryEntry::Tx(tx.into_humanized(api)?))))
+    });
+    let legacy_iter = legacy.iter().flat_map(|store| {
+        store.iter().rev().map(|transfer| {
+            transfer.and_then(|transfer| Ok(HistoryEntry::Transfer(transfer.into_humanized(api)?)))
+        })
+    });
 
-    // The `and_then` here flattens the `StdResult<StdResult<RichTx>>` to an `StdResult<RichTx>`
-    let transfers: StdResult<Vec<Tx>> = transfer_iter
-        .map(|tx| tx.map(|tx| tx.into_humanized(api)).and_then(|x| x))
-        .collect();
-    transfers.map(|txs| (txs, store.len() as u64))
+    let entries = merge_page(
+        tx_iter,
+        legacy_iter,
+        |entry| entry.id(),
+        (page * page_size) as _,
+        page_size as _,
+    )?;
+
+    Ok((entries, total))
+}
+
+/* Pages through two streams that are each ordered latest first as if they were one.
+ * ids are globally incrementing, so an id in both streams reaches the front of each at the same
+ * time and is only returned once, from `preferred`.
+ * Only the entries up to the end of the page are read.
+ */
+fn merge_page<T>(
+    mut preferred: impl Iterator<Item = StdResult<T>>,
+    mut other: impl Iterator<Item = StdResult<T>>,
+    id: impl Fn(&T) -> u64,
+    skip: usize,
+    take: usize,
+) -> StdResult<Vec<T>> {
+    let mut page = vec![];
+    let mut next_preferred = preferred.next().transpose()?;
+    let mut next_other = other.next().transpose()?;
+    let mut index = 0;
+
+    while page.len() < take {
+        let entry = match (next_preferred.take(), next_other.take()) {
+            (None, None) => break,
+            (Some(a), None) => {
+                next_preferred = preferred.next().transpose()?;
+                a
+            }
+            (None, Some(b)) => {
+                next_other = other.next().transpose()?;
+                b
+            }
+            (Some(a), Some(b)) => match id(&a).cmp(&id(&b)) {
+                std::cmp::Ordering::Greater => {
+                    next_preferred = preferred.next().transpose()?;
+                    next_other = Some(b);
+                    a
+                }
+                std::cmp::Ordering::Less => {
+                    next_preferred = Some(a);
+                    next_other = other.next().transpose()?;
+                    b
+                }
+                std::cmp::Ordering::Equal => {
+                    next_preferred = preferred.next().transpose()?;
+                    next_other = other.next().transpose()?;
+                    a
+                }
+            },
+        };
+
+        if index >= skip {
+            page.push(entry);
+        }
+        index += 1;
+    }
+
+    Ok(page)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shade_protocol::c_std::{testing::*, BlockInfo};
+
+    #[test]
+    fn unified_history_dedupes_transfers() {
+        let mut deps = mock_dependencies(20, &[]);
+        let alice = deps
+            .api
+            .canonical_address(&Addr::unchecked("alice".to_string()))
+            .unwrap();
+        let bob = deps
+            .api
+            .canonical_address(&Addr::unchecked("bob".to_string()))
+            .unwrap();
+        let block = BlockInfo {
+            height: 1,
+            time: 1,
+            chain_id: "chain_id".to_string(),
+        };
+
+        store_transfer(
+            &mut deps.storage,
+            &alice,
+            &alice,
+            &bob,
+            Uint128::new(100),
+            "SECSEC".to_string(),
+            None,
+            &block,
+        )
+        .unwrap();
+        store_claim_reward(
+            &mut deps.storage,
+            &bob,
+            Uint128::new(10),
+            "SECSEC".to_string(),
+            None,
+            &block,
+        )
+        .unwrap();
+
+        let (entries, total) = get_unified_history(&deps.api, &deps.storage, &bob, 0, 10).unwrap();
+
+        // The transfer is in both streams but only returned once
+        assert_eq!(total, 2);
+        assert_eq!(entries.iter().map(|e| e.id()).collect::<Vec<u64>>(), vec![
+            2, 1
+        ]);
+        assert!(entries.iter().all(|e| matches!(e, HistoryEntry::Tx(_))));
+    }
+
+    #[test]
+    fn transfers_read_back_from_txs() {
+        let mut deps = mock_dependencies(20, &[]);
+        let alice = deps
+            .api
+            .canonical_address(&Addr::unchecked("alice".to_string()))
+            .unwrap();
+        let bob = deps
+            .api
+            .canonical_address(&Addr::unchecked("bob".to_string()))
+            .unwrap();
+        let block = BlockInfo {
+            height: 1,
+            time: 1,
+            chain_id: "chain_id".to_string(),
+        };
+
+        // A transfer recorded before transfers stopped being stored twice
+        let legacy = StoredLegacyTransfer {
+            id: increment_tx_count(&mut deps.storage).unwrap(),
+            from: alice.clone(),
+            sender: alice.clone(),
+            receiver: bob.clone(),
+            coins: Coin {
+                denom: "SECSEC".to_string(),
+                amount: Uint128::new(50).into(),
+            },
+            memo: None,
+            block_time: 1,
+            block_height: 1,
+        };
+        {
+            let mut store =
+                PrefixedStorage::multilevel(&[PREFIX_TRANSFERS, bob.as_slice()], &mut deps.storage);
+            let mut store = AppendStoreMut::attach_or_create(&mut store).unwrap();
+            store.push(&legacy).unwrap();
+        }
+
+        store_transfer(
+            &mut deps.storage,
+            &alice,
+            &alice,
+            &bob,
+            Uint128::new(100),
+            "SECSEC".to_string(),
+            None,
+            &block,
+        )
+        .unwrap();
+        store_claim_reward(
+            &mut deps.storage,
+            &bob,
+            Uint128::new(10),
+            "SECSEC".to_string(),
+            None,
+            &block,
+        )
+        .unwrap();
+
+        // The new transfer only went into the tx store
+        let transfer_store =
+            ReadonlyPrefixedStorage::multilevel(&[PREFIX_TRANSFERS, bob.as_slice()], &deps.storage);
+        let store = AppendStore::<StoredLegacyTransfer, _, _>::attach(&transfer_store)
+            .unwrap()
+            .unwrap();
+        assert_eq!(store.len(), 1);
+
+        let (transfers, total) = get_transfers(&deps.api, &deps.storage, &bob, 0, 10).unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(transfers.iter().map(|t| t.id).collect::<Vec<u64>>(), vec![
+            2, 1
+        ]);
+        assert_eq!(transfers[0].coins.amount, Uint128::new(100));
+        assert_eq!(transfers[0].receiver, Addr::unchecked("bob"));
+        assert_eq!(transfers[1].coins.amount, Uint128::new(50));
+
+        let (transfers, total) = get_transfers(&deps.api, &deps.storage, &bob, 1, 1).unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(transfers[0].id, 1);
+    }
+
+    #[test]
+    fn unified_history_pages() {
+        let mut deps = mock_dependencies(20, &[]);
+        let bob = deps
+            .api
+            .canonical_address(&Addr::unchecked("bob".to_string()))
+            .unwrap();
+        let block = BlockInfo {
+            height: 1,
+            time: 1,
+            chain_id: "chain_id".to_string(),
+        };
+
+        for _ in 0..3 {
+            store_claim_reward(
+                &mut deps.storage,
+                &bob,
+                Uint128::new(10),
+                "SECSEC".to_string(),
+                None,
+                &block,
+            )
+            .unwrap();
+        }
+
+        let (entries, total) = get_unified_history(&deps.api, &deps.storage, &bob, 0, 2).unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(entries.iter().map(|e| e.id()).collect::<Vec<u64>>(), vec![
+            3, 2
+        ]);
+
+        let (entries, total) = get_unified_history(&deps.api, &deps.storage, &bob, 1, 2).unwrap();
+        assert_eq!(total, 3);
+        assert_eq!(entries.iter().map(|e| e.id()).collect::<Vec<u64>>(), vec![
+            1
+        ]);
+    }
 }
@@ -1,25 +1,28 @@
 use shade_protocol::{
     admin::{
         errors::unauthorized_super, AdminAuthStatus, AdminsResponse, ConfigResponse, ExecuteMsg,
-        InstantiateMsg, PermissionsResponse, QueryMsg,
+        InstantiateMsg, PendingSuperResponse, QueryMsg,
     },
     c_std::{
         shd_entry_point, to_binary, Addr, Deps, DepsMut, Env, MessageInfo, QueryResponse, Response,
         StdResult, Storage,
     },
-    utils::pad_handle_result,
+    utils::{pad_handle_result, version::VersionResponse},
 };
 
 use crate::{
     execute::{
-        try_self_destruct, try_toggle_status, try_transfer_super, try_update_registry,
-        try_update_registry_bulk,
+        try_accept_super, try_cancel_super_transfer, try_propose_super, try_revoke_permission,
+        try_self_destruct, try_set_min_permission_len, try_toggle_status, try_transfer_super,
+        try_update_registry, try_update_registry_bulk,
     },
-    query::query_validate_permission,
-    shared::{ADMINS, PERMISSIONS, STATUS, SUPER},
+    query::{query_permission_log, query_user_permissions, query_validate_permission},
+    shared::{min_permission_len, ADMINS, PENDING_SUPER, SCHEMA, STATUS, SUPER},
 };
 
 pub const RESPONSE_BLOCK_SIZE: usize = 256;
+/// Storage layout written by this code.
+pub const SCHEMA_VERSION: u64 = 1;
 
 #[cfg_attr(not(feature = "library"), shd_entry_point)]
 pub fn instantiate(
@@ -34,6 +37,7 @@ pub fn instantiate(
 
     ADMINS.save(deps.storage, &Vec::new())?;
     STATUS.save(deps.storage, &AdminAuthStatus::Active)?;
+    SCHEMA.save(deps.storage, &SCHEMA_VERSION)?;
 
     let res = Response::new()
         .add_attribute("action", "initialized")
@@ -44,22 +48,49 @@ pub fn instantiate(
 #[cfg_attr(not(feature = "library"), shd_entry_point)]
 pub fn execute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> StdResult<Response> {
-    // Only the super user can execute anything on this contract.
-    is_super(deps.storage, &info.sender)?;
+    // Only the super user can execute anything on this contract, except for the pending super
+    // accepting their transfer.
+    if msg != (ExecuteMsg::AcceptSuper {}) {
+        is_super(deps.storage, &info.sender)?;
+    }
     // Super user is assumed to have been verified by this point.
     pad_handle_result(
         match msg {
-            ExecuteMsg::UpdateRegistry { action } => {
-                try_update_registry(deps.storage, deps.api, action)
+            ExecuteMsg::UpdateRegistry { action } => try_update_registry(
+                deps.storage,
+                deps.api,
+                &info.sender,
+                env.block.height,
+                action,
+            ),
+            ExecuteMsg::UpdateRegistryBulk { actions } => {
+                try_update_registry_bulk(deps, &info.sender, env.block.height, actions)
             }
-            ExecuteMsg::UpdateRegistryBulk { actions } => try_update_registry_bulk(deps, actions),
+            ExecuteMsg::RevokePermission {
+                user,
+                permission,
+                remove_if_empty,
+            } => try_revoke_permission(
+                deps,
+                &info.sender,
+                env.block.height,
+                user,
+                permission,
+                remove_if_empty,
+            ),
             ExecuteMsg::TransferSuper { new_super } => try_transfer_super(deps, new_super),
+            ExecuteMsg::ProposeSuper { new_super } => try_propose_super(deps, new_super),
+            ExecuteMsg::AcceptSuper {} => try_accept_super(deps, info.sender),
+            ExecuteMsg::CancelSuperTransfer {} => try_cancel_super_transfer(deps),
             ExecuteMsg::SelfDestruct {} => try_self_destruct(deps),
             ExecuteMsg::ToggleStatus { new_status } => try_toggle_status(deps, new_status),
+            ExecuteMsg::SetMinPermissionLen { min_permission_len } => {
+                try_set_min_permission_len(deps, min_permission_len)
+            }
         },
         RESPONSE_BLOCK_SIZE,
     )
@@ -80,6 +111,7 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
         QueryMsg::GetConfig {} => to_binary(&ConfigResponse {
             super_admin: SUPER.load(deps.storage)?,
             status: STATUS.load(deps.storage)?,
+            min_permission_len: min_permission_len(deps.storage)?,
         }),
         QueryMsg::ValidateAdminPermission { permission, user } => {
             to_binary(&query_validate_permission(deps, permission, user)?)
@@ -93,15 +125,17 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<QueryResponse> {
                 admins: ADMINS.load(deps.storage)?,
             })
         }
-        QueryMsg::GetPermissions { user } => {
-            STATUS
-                .load(deps.storage)?
-                .not_shutdown()?
-                .not_under_maintenance()?;
-            let validated_user = deps.api.addr_validate(user.as_str())?;
-            to_binary(&PermissionsResponse {
-                permissions: PERMISSIONS.load(deps.storage, &validated_user)?,
-            })
+        QueryMsg::GetPermissions { user } => to_binary(&query_user_permissions(deps, user)?),
+        QueryMsg::GetPermissionLog { page, page_size } => {
+            to_binary(&query_permission_log(deps, page, page_size)?)
         }
+        QueryMsg::GetPendingSuper {} => to_binary(&PendingSuperResponse {
+            pending_super: PENDING_SUPER.may_load(deps.storage)?,
+        }),
+        QueryMsg::Version {} => to_binary(&VersionResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            // Contracts instantiated before versioning was added are v1.
+            schema: SCHEMA.may_load(deps.storage)?.unwrap_or(1),
+        }),
     }?)
 }
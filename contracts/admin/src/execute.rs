@@ -1,17 +1,24 @@
-use crate::shared::{validate_permissions, ADMINS, PERMISSIONS, STATUS, SUPER};
-use shade_protocol::admin::errors::{no_permission, unregistered_admin};
-use shade_protocol::admin::{AdminAuthStatus, RegistryAction};
+use crate::shared::{
+    log_permission_change, validate_permissions, ADMINS, MIN_PERMISSION_LEN, PENDING_SUPER,
+    PERMISSIONS, STATUS, SUPER,
+};
+use shade_protocol::admin::errors::{
+    invalid_min_permission_len, no_permission, unauthorized_pending_super, unregistered_admin,
+};
+use shade_protocol::admin::{AdminAuthStatus, PermissionAction, PermissionChange, RegistryAction};
 use shade_protocol::c_std::{Addr, Api, DepsMut, Response, StdResult, Storage};
 
 /// Performs one registry update. Cannot be run during a shutdown.
 pub fn try_update_registry(
     store: &mut dyn Storage,
     api: &dyn Api,
+    actor: &Addr,
+    block_height: u64,
     action: RegistryAction,
 ) -> StdResult<Response> {
     STATUS.load(store)?.not_shutdown()?;
     let mut admins = ADMINS.load(store)?;
-    resolve_registry_action(store, &mut admins, api, action)?;
+    resolve_registry_action(store, &mut admins, api, actor, block_height, action)?;
     ADMINS.save(store, &admins)?;
     Ok(Response::default())
 }
@@ -19,17 +26,61 @@ pub fn try_update_registry(
 /// Performs bulk registry updates. Cannot be run during a shutdown.
 pub fn try_update_registry_bulk(
     deps: DepsMut,
+    actor: &Addr,
+    block_height: u64,
     actions: Vec<RegistryAction>,
 ) -> StdResult<Response> {
     STATUS.load(deps.storage)?.not_shutdown()?;
     let mut admins = ADMINS.load(deps.storage)?;
     for action in actions {
-        resolve_registry_action(deps.storage, &mut admins, deps.api, action)?;
+        resolve_registry_action(
+            deps.storage,
+            &mut admins,
+            deps.api,
+            actor,
+            block_height,
+            action,
+        )?;
     }
     ADMINS.save(deps.storage, &admins)?;
     Ok(Response::default())
 }
 
+/// Removes a single permission from a user, leaving the rest of their permissions untouched.
+/// Cannot be run during a shutdown.
+pub fn try_revoke_permission(
+    deps: DepsMut,
+    actor: &Addr,
+    block_height: u64,
+    user: String,
+    permission: String,
+    remove_if_empty: bool,
+) -> StdResult<Response> {
+    STATUS.load(deps.storage)?.not_shutdown()?;
+    let user = deps.api.addr_validate(user.as_str())?;
+    let mut admins = ADMINS.load(deps.storage)?;
+    verify_registered(&admins, &user)?;
+    let mut permissions = PERMISSIONS.load(deps.storage, &user)?;
+    if !permissions.contains(&permission) {
+        return Ok(Response::default());
+    }
+    permissions.retain(|perm| perm.ne(&permission));
+    if permissions.is_empty() && remove_if_empty {
+        delete_admin(deps.storage, &mut admins, deps.api, user.to_string())?;
+        ADMINS.save(deps.storage, &admins)?;
+    } else {
+        PERMISSIONS.save(deps.storage, &user, &permissions)?;
+    }
+    log_permission_change(deps.storage, &PermissionChange {
+        actor: actor.clone(),
+        target: user,
+        permission,
+        action: PermissionAction::Revoke,
+        block_height,
+    })?;
+    Ok(Response::default())
+}
+
 pub fn try_transfer_super(deps: DepsMut, new_super: String) -> StdResult<Response> {
     let valid_super = deps.api.addr_validate(new_super.as_str())?;
     // If you're trying to transfer the super permissions to someone who hasn't been registered as an admin,
@@ -40,12 +91,44 @@ pub fn try_transfer_super(deps: DepsMut, new_super: String) -> StdResult<Respons
     } else {
         // Update the super and remove them from the admin list.
         SUPER.save(deps.storage, &valid_super)?;
+        // A proposal made before the direct transfer can't be accepted afterwards.
+        PENDING_SUPER.remove(deps.storage);
         delete_admin(deps.storage, &mut admins, deps.api, new_super)?;
         ADMINS.save(deps.storage, &admins)?;
     }
     Ok(Response::default())
 }
 
+/// First step of a super transfer, the proposed address has to accept it before it takes effect.
+pub fn try_propose_super(deps: DepsMut, new_super: String) -> StdResult<Response> {
+    let valid_super = deps.api.addr_validate(new_super.as_str())?;
+    PENDING_SUPER.save(deps.storage, &valid_super)?;
+    Ok(Response::default())
+}
+
+/// Second step of a super transfer, called by the proposed address.
+pub fn try_accept_super(deps: DepsMut, sender: Addr) -> StdResult<Response> {
+    STATUS.load(deps.storage)?.not_shutdown()?;
+    match PENDING_SUPER.may_load(deps.storage)? {
+        Some(pending) if pending == sender => {
+            SUPER.save(deps.storage, &pending)?;
+            PENDING_SUPER.remove(deps.storage);
+            // The super has every permission so they don't need to stay registered as an admin.
+            let mut admins = ADMINS.load(deps.storage)?;
+            delete_admin(deps.storage, &mut admins, deps.api, pending.to_string())?;
+            ADMINS.save(deps.storage, &admins)?;
+            Ok(Response::default())
+        }
+        _ => Err(unauthorized_pending_super(sender.as_str())),
+    }
+}
+
+/// Drops the pending super transfer, if any.
+pub fn try_cancel_super_transfer(deps: DepsMut) -> StdResult<Response> {
+    PENDING_SUPER.remove(deps.storage);
+    Ok(Response::default())
+}
+
 pub fn try_self_destruct(deps: DepsMut) -> StdResult<Response> {
     // Clear permissions
     let admins = ADMINS.load(deps.storage)?;
@@ -54,11 +137,22 @@ pub fn try_self_destruct(deps: DepsMut) -> StdResult<Response> {
         .for_each(|admin| PERMISSIONS.remove(deps.storage, admin));
     // Clear admins
     ADMINS.save(deps.storage, &vec![])?;
+    // Drop any pending super transfer
+    PENDING_SUPER.remove(deps.storage);
     // Disable contract
     STATUS.save(deps.storage, &AdminAuthStatus::Shutdown)?;
     Ok(Response::default())
 }
 
+pub fn try_set_min_permission_len(deps: DepsMut, min_permission_len: u32) -> StdResult<Response> {
+    // An empty permission name would otherwise become valid.
+    if min_permission_len == 0 {
+        return Err(invalid_min_permission_len());
+    }
+    MIN_PERMISSION_LEN.save(deps.storage, &min_permission_len)?;
+    Ok(Response::default())
+}
+
 pub fn try_toggle_status(deps: DepsMut, new_status: AdminAuthStatus) -> StdResult<Response> {
     STATUS.update(deps.storage, |_| -> StdResult<_> { Ok(new_status) })?;
     Ok(Response::default())
@@ -68,15 +162,33 @@ fn resolve_registry_action(
     store: &mut dyn Storage,
     admins: &mut Vec<Addr>,
     api: &dyn Api,
+    actor: &Addr,
+    block_height: u64,
     action: RegistryAction,
 ) -> StdResult<()> {
     match action {
         RegistryAction::RegisterAdmin { user } => register_admin(store, admins, api, user),
         RegistryAction::GrantAccess { permissions, user } => {
-            grant_access(store, api, admins, permissions, user)
+            let user = grant_access(store, api, admins, permissions.clone(), user)?;
+            log_permission_changes(
+                store,
+                actor,
+                &user,
+                permissions,
+                PermissionAction::Grant,
+                block_height,
+            )
         }
         RegistryAction::RevokeAccess { permissions, user } => {
-            revoke_access(store, api, admins, permissions, user)
+            let user = revoke_access(store, api, admins, permissions.clone(), user)?;
+            log_permission_changes(
+                store,
+                actor,
+                &user,
+                permissions,
+                PermissionAction::Revoke,
+                block_height,
+            )
         }
         RegistryAction::DeleteAdmin { user } => delete_admin(store, admins, api, user),
     }?;
@@ -120,9 +232,9 @@ fn grant_access(
     admins: &[Addr],
     mut permissions: Vec<String>,
     user: String,
-) -> StdResult<()> {
+) -> StdResult<Addr> {
     let user = api.addr_validate(user.as_str())?;
-    validate_permissions(permissions.as_slice())?;
+    validate_permissions(store, permissions.as_slice())?;
     verify_registered(admins, &user)?;
     PERMISSIONS.update(store, &user, |old_perms| -> StdResult<_> {
         match old_perms {
@@ -134,7 +246,7 @@ fn grant_access(
             None => Err(no_permission(user.as_str())),
         }
     })?;
-    Ok(())
+    Ok(user)
 }
 
 fn revoke_access(
@@ -143,9 +255,9 @@ fn revoke_access(
     admins: &[Addr],
     permissions: Vec<String>,
     user: String,
-) -> StdResult<()> {
+) -> StdResult<Addr> {
     let user = api.addr_validate(user.as_str())?;
-    validate_permissions(permissions.as_slice())?;
+    validate_permissions(store, permissions.as_slice())?;
     verify_registered(admins, &user)?;
     PERMISSIONS.update(store, &user, |old_perms| -> StdResult<_> {
         match old_perms {
@@ -156,6 +268,27 @@ fn revoke_access(
             None => Err(no_permission(user.as_str())),
         }
     })?;
+    Ok(user)
+}
+
+/// Records one audit log entry per permission, in the order they were requested.
+fn log_permission_changes(
+    store: &mut dyn Storage,
+    actor: &Addr,
+    target: &Addr,
+    permissions: Vec<String>,
+    action: PermissionAction,
+    block_height: u64,
+) -> StdResult<()> {
+    for permission in permissions {
+        log_permission_change(store, &PermissionChange {
+            actor: actor.clone(),
+            target: target.clone(),
+            permission,
+            action: action.clone(),
+            block_height,
+        })?;
+    }
     Ok(())
 }
 
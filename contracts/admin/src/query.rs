@@ -1,6 +1,11 @@
-use crate::shared::{is_valid_permission, PERMISSIONS, STATUS, SUPER};
+use crate::shared::{
+    is_valid_permission, PERMISSIONS, PERMISSION_LOG, PERMISSION_LOG_COUNT, STATUS, SUPER,
+};
 use shade_protocol::{
-    admin::{errors::unregistered_admin, ValidateAdminPermissionResponse},
+    admin::{
+        errors::unregistered_admin, PermissionLogResponse, PermissionsResponse,
+        ValidateAdminPermissionResponse,
+    },
     c_std::{Deps, StdResult},
 };
 
@@ -14,7 +19,7 @@ pub fn query_validate_permission(
         .load(deps.storage)?
         .not_shutdown()?
         .not_under_maintenance()?;
-    is_valid_permission(permission.as_str())?;
+    is_valid_permission(deps.storage, permission.as_str())?;
     let valid_user = deps.api.addr_validate(user.as_str())?;
     let super_admin = SUPER.load(deps.storage)?;
 
@@ -38,3 +43,42 @@ pub fn query_validate_permission(
     }
     Ok(ValidateAdminPermissionResponse { has_permission })
 }
+
+/// Lists every permission the user was granted. The super admin doesn't need to be registered.
+pub fn query_user_permissions(deps: Deps, user: String) -> StdResult<PermissionsResponse> {
+    STATUS
+        .load(deps.storage)?
+        .not_shutdown()?
+        .not_under_maintenance()?;
+    let valid_user = deps.api.addr_validate(user.as_str())?;
+    let is_super = valid_user == SUPER.load(deps.storage)?;
+
+    let permissions = match PERMISSIONS.may_load(deps.storage, &valid_user)? {
+        Some(permissions) => permissions,
+        None if is_super => vec![],
+        None => return Err(unregistered_admin(valid_user.as_str())),
+    };
+    Ok(PermissionsResponse {
+        permissions,
+        is_super,
+    })
+}
+
+/// Pages through the permission audit log, oldest changes first.
+pub fn query_permission_log(
+    deps: Deps,
+    page: u32,
+    page_size: u32,
+) -> StdResult<PermissionLogResponse> {
+    let total = PERMISSION_LOG_COUNT
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let start = page as u64 * page_size as u64;
+    let end = total.min(start + page_size as u64);
+
+    let mut changes = vec![];
+    for id in start..end {
+        changes.push(PERMISSION_LOG.load(deps.storage, id)?);
+    }
+    Ok(PermissionLogResponse { changes, total })
+}
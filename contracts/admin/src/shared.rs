@@ -1,8 +1,8 @@
 use shade_protocol::c_std::Addr;
 use shade_protocol::utils::storage::plus::{Item, Map};
 use shade_protocol::{
-    admin::{errors::invalid_permission_format, AdminAuthStatus},
-    c_std::StdResult,
+    admin::{errors::invalid_permission_format, AdminAuthStatus, PermissionChange},
+    c_std::{StdResult, Storage},
 };
 
 /// Maps user to permissions for which they have user.
@@ -11,25 +11,54 @@ pub const PERMISSIONS: Map<&Addr, Vec<String>> = Map::new("permissions");
 pub const ADMINS: Item<Vec<Addr>> = Item::new("admins");
 /// Super user.
 pub const SUPER: Item<Addr> = Item::new("super");
+/// Proposed super user, waiting to accept the transfer.
+pub const PENDING_SUPER: Item<Addr> = Item::new("pending_super");
 /// Whether or not this contract can be consumed.
 pub const STATUS: Item<AdminAuthStatus> = Item::new("is_active");
+/// Storage layout version, bumped by migrations.
+pub const SCHEMA: Item<u64> = Item::new("schema_version");
+/// Append-only log of permission changes, keyed by insertion order.
+pub const PERMISSION_LOG: Map<u64, PermissionChange> = Map::new("permission_log");
+/// Number of entries in the permission log.
+pub const PERMISSION_LOG_COUNT: Item<u64> = Item::new("permission_log_count");
+/// Shortest permission name accepted, falls back to DEFAULT_MIN_PERMISSION_LEN when unset.
+pub const MIN_PERMISSION_LEN: Item<u32> = Item::new("min_permission_len");
 
-pub fn validate_permissions(permissions: &[String]) -> StdResult<()> {
+pub const DEFAULT_MIN_PERMISSION_LEN: u32 = 11;
+
+pub fn log_permission_change(store: &mut dyn Storage, change: &PermissionChange) -> StdResult<()> {
+    let id = PERMISSION_LOG_COUNT.may_load(store)?.unwrap_or_default();
+    PERMISSION_LOG.save(store, id, change)?;
+    PERMISSION_LOG_COUNT.save(store, &(id + 1))
+}
+
+pub fn min_permission_len(store: &dyn Storage) -> StdResult<u32> {
+    Ok(MIN_PERMISSION_LEN
+        .may_load(store)?
+        .unwrap_or(DEFAULT_MIN_PERMISSION_LEN))
+}
+
+pub fn validate_permissions(store: &dyn Storage, permissions: &[String]) -> StdResult<()> {
+    let min_len = min_permission_len(store)?;
     for permission in permissions {
-        is_valid_permission(permission.as_str())?;
+        check_permission(permission.as_str(), min_len)?;
     }
     Ok(())
 }
 
-pub fn is_valid_permission(permission: &str) -> StdResult<()> {
-    if permission.len() <= 10 {
-        return Err(invalid_permission_format(permission));
+pub fn is_valid_permission(store: &dyn Storage, permission: &str) -> StdResult<()> {
+    check_permission(permission, min_permission_len(store)?)
+}
+
+fn check_permission(permission: &str, min_len: u32) -> StdResult<()> {
+    if permission.len() < min_len as usize {
+        return Err(invalid_permission_format(permission, min_len));
     }
     let valid_chars = permission.bytes().all(|byte| {
         (b'A'..=b'Z').contains(&byte) || (b'0'..=b'9').contains(&byte) || b'_'.eq(&byte)
     });
     if !valid_chars {
-        return Err(invalid_permission_format(permission));
+        return Err(invalid_permission_format(permission, min_len));
     }
     Ok(())
 }
@@ -1,14 +1,15 @@
-use crate::shared::is_valid_permission;
+use crate::{contract::SCHEMA_VERSION, shared::is_valid_permission};
 use rstest::*;
 use shade_multi_test::multi::admin::Admin;
 use shade_protocol::{
     admin::{
         AdminAuthStatus, AdminsResponse, ConfigResponse, ExecuteMsg, InstantiateMsg,
+        PendingSuperResponse, PermissionAction, PermissionChange, PermissionLogResponse,
         PermissionsResponse, QueryMsg, RegistryAction, ValidateAdminPermissionResponse,
     },
-    c_std::Addr,
+    c_std::{testing::mock_dependencies, Addr},
     multi_test::App,
-    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+    utils::{version::VersionResponse, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
 };
 
 #[rstest]
@@ -21,7 +22,8 @@ use shade_protocol::{
 #[case("*@#$*!*#!#!#****", false)]
 #[case("VAULT_TARGET_addr", false)]
 fn test_is_valid_permission(#[case] permission: String, #[case] is_valid: bool) {
-    let resp = is_valid_permission(permission.as_str());
+    let deps = mock_dependencies();
+    let resp = is_valid_permission(deps.as_ref().storage, permission.as_str());
     if is_valid {
         assert!(resp.is_ok());
     } else {
@@ -29,6 +31,73 @@ fn test_is_valid_permission(#[case] permission: String, #[case] is_valid: bool)
     }
 }
 
+#[rstest]
+#[case("ABC", false)]
+#[case("ABCD", true)]
+#[case("ABCDE", true)]
+#[case("ABCd", false)]
+fn test_custom_min_permission_len(#[case] permission: String, #[case] is_valid: bool) {
+    let mut chain: App = App::default();
+    let contract = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    //only the super can change the minimum and it can't be zero
+    assert!(ExecuteMsg::SetMinPermissionLen {
+        min_permission_len: 4,
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("user"), &[])
+    .is_err());
+    assert!(ExecuteMsg::SetMinPermissionLen {
+        min_permission_len: 0,
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .is_err());
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(config.min_permission_len, 11);
+
+    ExecuteMsg::SetMinPermissionLen {
+        min_permission_len: 4,
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(config.min_permission_len, 4);
+
+    let result = ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec![permission.clone()],
+                user: "user".to_string(),
+            },
+        ],
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[]);
+    assert_eq!(result.is_ok(), is_valid);
+
+    let result = QueryMsg::ValidateAdminPermission {
+        permission,
+        user: "admin".to_string(),
+    }
+    .test_query::<ValidateAdminPermissionResponse>(&contract, &chain);
+    assert_eq!(result.is_ok(), is_valid);
+}
+
 #[rstest]
 #[case(AdminAuthStatus::Active, vec![true, true, true, false, true, true, true])]
 #[case(AdminAuthStatus::Maintenance, vec![true, true, true, false, true, true, true])]
@@ -349,3 +418,565 @@ fn test_permissions(
         }
     }
 }
+
+#[test]
+fn test_revoke_permission() {
+    let mut chain = App::default();
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec![
+                    "PLACE_SAN_JUAN".to_string(),
+                    "PLACE_NEW_YORK".to_string(),
+                    "PLACE_CAPRI_ISLAND".to_string(),
+                ],
+                user: "user".to_string(),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    //only the super can revoke
+    assert!(ExecuteMsg::RevokePermission {
+        user: "user".to_string(),
+        permission: "PLACE_NEW_YORK".to_string(),
+        remove_if_empty: false,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("user"), &[])
+    .is_err());
+
+    ExecuteMsg::RevokePermission {
+        user: "user".to_string(),
+        permission: "PLACE_NEW_YORK".to_string(),
+        remove_if_empty: false,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let res: PermissionsResponse = QueryMsg::GetPermissions {
+        user: "user".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(res.permissions, vec![
+        "PLACE_SAN_JUAN".to_string(),
+        "PLACE_CAPRI_ISLAND".to_string(),
+    ]);
+
+    //revoking a permission the user doesn't have changes nothing
+    ExecuteMsg::RevokePermission {
+        user: "user".to_string(),
+        permission: "PLACE_NEW_YORK".to_string(),
+        remove_if_empty: true,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let res: PermissionsResponse = QueryMsg::GetPermissions {
+        user: "user".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(res.permissions, vec![
+        "PLACE_SAN_JUAN".to_string(),
+        "PLACE_CAPRI_ISLAND".to_string(),
+    ]);
+}
+
+#[rstest]
+#[case(false)]
+#[case(true)]
+fn test_revoke_last_permission(#[case] remove_if_empty: bool) {
+    let mut chain = App::default();
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistryBulk {
+        actions: vec![
+            RegistryAction::RegisterAdmin {
+                user: "user".to_string(),
+            },
+            RegistryAction::GrantAccess {
+                permissions: vec!["PLACE_SAN_JUAN".to_string()],
+                user: "user".to_string(),
+            },
+        ],
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    ExecuteMsg::RevokePermission {
+        user: "user".to_string(),
+        permission: "PLACE_SAN_JUAN".to_string(),
+        remove_if_empty,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let res: AdminsResponse = QueryMsg::GetAdmins {}.test_query(&admin, &chain).unwrap();
+    assert_eq!(
+        res.admins.contains(&Addr::unchecked("user")),
+        !remove_if_empty
+    );
+
+    let res = QueryMsg::GetPermissions {
+        user: "user".to_string(),
+    }
+    .test_query::<PermissionsResponse>(&admin, &chain);
+    if remove_if_empty {
+        assert!(res.is_err());
+    } else {
+        assert!(res.unwrap().permissions.is_empty());
+    }
+}
+
+#[test]
+fn test_permission_log() {
+    let mut chain = App::default();
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::RegisterAdmin {
+            user: "user".to_string(),
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::GrantAccess {
+            permissions: vec!["PLACE_SAN_JUAN".to_string()],
+            user: "user".to_string(),
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    let grant_height = chain.block_info().height;
+
+    chain.update_block(|block| block.height += 1);
+
+    ExecuteMsg::RevokePermission {
+        user: "user".to_string(),
+        permission: "PLACE_SAN_JUAN".to_string(),
+        remove_if_empty: false,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let res: PermissionLogResponse = QueryMsg::GetPermissionLog {
+        page: 0,
+        page_size: 10,
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(res.total, 2);
+    assert_eq!(res.changes, vec![
+        PermissionChange {
+            actor: Addr::unchecked("admin"),
+            target: Addr::unchecked("user"),
+            permission: "PLACE_SAN_JUAN".to_string(),
+            action: PermissionAction::Grant,
+            block_height: grant_height,
+        },
+        PermissionChange {
+            actor: Addr::unchecked("admin"),
+            target: Addr::unchecked("user"),
+            permission: "PLACE_SAN_JUAN".to_string(),
+            action: PermissionAction::Revoke,
+            block_height: grant_height + 1,
+        },
+    ]);
+
+    //pages past the first only hold what's left
+    let res: PermissionLogResponse = QueryMsg::GetPermissionLog {
+        page: 1,
+        page_size: 1,
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(res.changes.len(), 1);
+    assert_eq!(res.changes[0].action, PermissionAction::Revoke);
+}
+
+#[test]
+fn test_user_permissions() {
+    let mut chain = App::default();
+    let admin = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::RegisterAdmin {
+            user: "user".to_string(),
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    //registered but nothing granted yet
+    let res: PermissionsResponse = QueryMsg::GetPermissions {
+        user: "user".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(res, PermissionsResponse {
+        permissions: vec![],
+        is_super: false,
+    });
+
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::GrantAccess {
+            permissions: vec!["PLACE_SAN_JUAN".to_string(), "PLACE_NEW_YORK".to_string()],
+            user: "user".to_string(),
+        },
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let res: PermissionsResponse = QueryMsg::GetPermissions {
+        user: "user".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert_eq!(res.permissions, vec![
+        "PLACE_SAN_JUAN".to_string(),
+        "PLACE_NEW_YORK".to_string(),
+    ]);
+    assert!(!res.is_super);
+
+    //the super doesn't need to be registered
+    let res: PermissionsResponse = QueryMsg::GetPermissions {
+        user: "admin".to_string(),
+    }
+    .test_query(&admin, &chain)
+    .unwrap();
+    assert!(res.permissions.is_empty());
+    assert!(res.is_super);
+
+    assert!(QueryMsg::GetPermissions {
+        user: "stranger".to_string(),
+    }
+    .test_query::<PermissionsResponse>(&admin, &chain)
+    .is_err());
+
+    ExecuteMsg::ToggleStatus {
+        new_status: AdminAuthStatus::Maintenance,
+    }
+    .test_exec(&admin, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+    assert!(QueryMsg::GetPermissions {
+        user: "user".to_string(),
+    }
+    .test_query::<PermissionsResponse>(&admin, &chain)
+    .is_err());
+}
+
+#[test]
+fn test_propose_accept_super() {
+    let mut chain: App = App::default();
+    let contract = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    //only the super can propose
+    assert!(ExecuteMsg::ProposeSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+    .is_err());
+
+    ExecuteMsg::ProposeSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    //proposing doesn't transfer anything yet
+    let config: ConfigResponse = QueryMsg::GetConfig {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(config.super_admin.as_str(), "admin");
+
+    ExecuteMsg::AcceptSuper {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .unwrap();
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(config.super_admin.as_str(), "new_super");
+
+    //the old super lost their access and the proposal can't be accepted twice
+    assert!(ExecuteMsg::ToggleStatus {
+        new_status: AdminAuthStatus::Maintenance,
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .is_err());
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+}
+
+#[test]
+fn test_unauthorized_accept_super() {
+    let mut chain: App = App::default();
+    let contract = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    //nothing has been proposed
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+
+    ExecuteMsg::ProposeSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    //neither a random user nor the current super can accept
+    for user in ["user", "admin"] {
+        assert!(ExecuteMsg::AcceptSuper {}
+            .test_exec(&contract, &mut chain, Addr::unchecked(user), &[])
+            .is_err());
+    }
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(config.super_admin.as_str(), "admin");
+}
+
+#[test]
+fn test_cancel_super_transfer() {
+    let mut chain: App = App::default();
+    let contract = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    let pending: PendingSuperResponse = QueryMsg::GetPendingSuper {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(pending.pending_super, None);
+
+    ExecuteMsg::ProposeSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let pending: PendingSuperResponse = QueryMsg::GetPendingSuper {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(pending.pending_super, Some(Addr::unchecked("new_super")));
+
+    //only the super can cancel, not even the candidate
+    assert!(ExecuteMsg::CancelSuperTransfer {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+
+    ExecuteMsg::CancelSuperTransfer {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+        .unwrap();
+
+    let pending: PendingSuperResponse = QueryMsg::GetPendingSuper {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(pending.pending_super, None);
+
+    //the canceled candidate can't accept anymore
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(config.super_admin.as_str(), "admin");
+}
+
+#[test]
+fn test_pending_super_cleared() {
+    let mut chain: App = App::default();
+    let contract = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::UpdateRegistry {
+        action: RegistryAction::RegisterAdmin {
+            user: "other_super".to_string(),
+        },
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    ExecuteMsg::ProposeSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    //a direct transfer drops the proposal
+    ExecuteMsg::TransferSuper {
+        new_super: "other_super".to_string(),
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    let pending: PendingSuperResponse = QueryMsg::GetPendingSuper {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(pending.pending_super, None);
+
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+
+    ExecuteMsg::ProposeSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("other_super"), &[])
+    .unwrap();
+
+    //so does a self destruct
+    ExecuteMsg::SelfDestruct {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("other_super"), &[])
+        .unwrap();
+
+    let pending: PendingSuperResponse = QueryMsg::GetPendingSuper {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(pending.pending_super, None);
+
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(config.super_admin.as_str(), "other_super");
+}
+
+#[test]
+fn test_accept_super_shutdown() {
+    let mut chain: App = App::default();
+    let contract = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    ExecuteMsg::ProposeSuper {
+        new_super: "new_super".to_string(),
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    ExecuteMsg::ToggleStatus {
+        new_status: AdminAuthStatus::Shutdown,
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    assert!(ExecuteMsg::AcceptSuper {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .is_err());
+
+    ExecuteMsg::ToggleStatus {
+        new_status: AdminAuthStatus::Active,
+    }
+    .test_exec(&contract, &mut chain, Addr::unchecked("admin"), &[])
+    .unwrap();
+
+    ExecuteMsg::AcceptSuper {}
+        .test_exec(&contract, &mut chain, Addr::unchecked("new_super"), &[])
+        .unwrap();
+
+    let config: ConfigResponse = QueryMsg::GetConfig {}
+        .test_query(&contract, &chain)
+        .unwrap();
+    assert_eq!(config.super_admin.as_str(), "new_super");
+}
+
+#[test]
+fn test_version() {
+    let mut chain: App = App::default();
+    let contract = InstantiateMsg { super_admin: None }
+        .test_init(
+            Admin::default(),
+            &mut chain,
+            Addr::unchecked("admin"),
+            "admin_contract",
+            &[],
+        )
+        .unwrap();
+
+    let res: VersionResponse = QueryMsg::Version {}.test_query(&contract, &chain).unwrap();
+    assert_eq!(res.version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(res.schema, SCHEMA_VERSION);
+}
@@ -1,8 +1,9 @@
-use crate::{execute, query, storage::*};
+use crate::{execute, migration, query, storage::*};
 use shade_protocol::{
     c_std::{
         shd_entry_point,
         to_binary,
+        Addr,
         Binary,
         Deps,
         DepsMut,
@@ -10,10 +11,19 @@ use shade_protocol::{
         MessageInfo,
         Response,
         StdResult,
+        Uint128,
     },
     dao::{
         manager,
-        treasury_manager::{Config, ExecuteMsg, Holding, InstantiateMsg, QueryMsg, Status},
+        treasury_manager::{
+            Config,
+            ExecuteMsg,
+            Holding,
+            InstantiateMsg,
+            MigrateMsg,
+            QueryMsg,
+            Status,
+        },
     },
 };
 
@@ -29,7 +39,12 @@ pub fn instantiate(
     CONFIG.save(deps.storage, &Config {
         admin_auth: msg.admin_auth.into_valid(deps.api)?,
         treasury: treasury.clone(),
+        profit_recipient: None,
+        dust_threshold: Uint128::zero(),
+        max_messages: None,
+        query_auth: None,
     })?;
+    SCHEMA.save(deps.storage, &migration::SCHEMA_VERSION)?;
 
     VIEWING_KEY.save(deps.storage, &msg.viewing_key)?;
     ASSET_LIST.save(deps.storage, &Vec::new())?;
@@ -60,16 +75,46 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
         ExecuteMsg::UpdateConfig {
             admin_auth,
             treasury,
-        } => execute::update_config(deps, env, info, admin_auth, treasury),
+            profit_recipient,
+            dust_threshold,
+            max_messages,
+            query_auth,
+        } => execute::update_config(
+            deps,
+            env,
+            info,
+            admin_auth,
+            treasury,
+            profit_recipient,
+            dust_threshold,
+            max_messages,
+            query_auth,
+        ),
         ExecuteMsg::RegisterAsset { contract } => {
             let contract = contract.into_valid(deps.api)?;
             execute::register_asset(deps, &env, info, &contract)
         }
+        ExecuteMsg::RefreshAsset { asset } => {
+            let asset = asset.into_valid(deps.api)?;
+            execute::refresh_asset(deps, &env, info, &asset)
+        }
+        ExecuteMsg::CancelUnbond { asset, amount } => {
+            let asset = query::asset_key(deps.as_ref(), &asset)?;
+            execute::cancel_unbond(deps, &env, info, asset, amount)
+        }
+        ExecuteMsg::RegisterNativeAsset { denom } => {
+            execute::register_native_asset(deps, &env, info, denom)
+        }
+        ExecuteMsg::DepositNative {} => execute::deposit_native(deps, &env, info),
         ExecuteMsg::Allocate { asset, allocation } => {
-            let asset = deps.api.addr_validate(&asset)?;
+            let asset = query::asset_key(deps.as_ref(), &asset)?;
             let allocation = allocation.valid(deps.api)?;
             execute::allocate(deps, &env, info, asset, allocation)
         }
+        ExecuteMsg::UpdateTreasury { treasury } => {
+            let treasury = deps.api.addr_validate(&treasury)?;
+            execute::update_treasury(deps, &env, info, treasury)
+        }
         ExecuteMsg::AddHolder { holder } => {
             let holder = deps.api.addr_validate(&holder)?;
             execute::add_holder(deps, &env, info, holder)
@@ -78,83 +123,154 @@ pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> S
             let holder = deps.api.addr_validate(&holder)?;
             execute::remove_holder(deps, &env, info, holder)
         }
+        ExecuteMsg::Update { asset, force } => {
+            let asset = query::asset_key(deps.as_ref(), &asset)?;
+            execute::update(deps, &env, info, asset, force.unwrap_or(false))
+        }
         ExecuteMsg::Manager(a) => match a {
             manager::SubExecuteMsg::Unbond { asset, amount } => {
-                let asset = deps.api.addr_validate(&asset)?;
+                let asset = query::asset_key(deps.as_ref(), &asset)?;
                 execute::unbond(deps, &env, info, asset, amount)
             }
             manager::SubExecuteMsg::Claim { asset } => {
-                let asset = deps.api.addr_validate(&asset)?;
+                let asset = query::asset_key(deps.as_ref(), &asset)?;
                 execute::claim(deps, &env, info, asset)
             }
             manager::SubExecuteMsg::Update { asset } => {
-                let asset = deps.api.addr_validate(&asset)?;
-                execute::update(deps, &env, info, asset)
+                let asset = query::asset_key(deps.as_ref(), &asset)?;
+                execute::update(deps, &env, info, asset, false)
             }
         },
     }
 }
 
+#[shd_entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    migration::migrate_storage(deps.storage)?;
+    Ok(Response::new())
+}
+
 #[shd_entry_point]
 pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query::config(deps)?),
         QueryMsg::Assets {} => to_binary(&query::assets(deps)?),
-        QueryMsg::Allocations { asset } => {
+        QueryMsg::NativeAssets {} => to_binary(&query::native_assets(deps)?),
+        QueryMsg::AssetContract { asset } => {
             let asset = deps.api.addr_validate(&asset)?;
+            to_binary(&query::asset_contract(deps, asset)?)
+        }
+        QueryMsg::Allocations { asset } => {
+            let asset = query::asset_key(deps, &asset)?;
             to_binary(&query::allocations(deps, asset)?)
         }
         QueryMsg::PendingAllowance { asset } => {
-            let asset = deps.api.addr_validate(&asset)?;
+            let asset = query::asset_key(deps, &asset)?;
             to_binary(&query::pending_allowance(deps, env, asset)?)
         }
-        QueryMsg::Holders {} => to_binary(&query::holders(deps)?),
-        QueryMsg::Holding { holder } => {
+        QueryMsg::LastAllowanceUsed { asset } => {
+            let asset = query::asset_key(deps, &asset)?;
+            to_binary(&query::last_allowance_used(deps, asset)?)
+        }
+        QueryMsg::ExcessLosses { asset } => {
+            let asset = query::asset_key(deps, &asset)?;
+            to_binary(&query::excess_losses(deps, asset)?)
+        }
+        QueryMsg::Holders { auth } => {
+            let user = query::authenticate(deps, auth)?;
+            to_binary(&query::holders(deps, user)?)
+        }
+        QueryMsg::Holding { holder, auth } => {
             let holder = deps.api.addr_validate(&holder)?;
-            to_binary(&query::holding(deps, holder)?)
+            let user = query::authenticate(deps, auth)?;
+            to_binary(&query::holding(deps, holder, user)?)
+        }
+        QueryMsg::HoldingWithPermit { permit } => {
+            to_binary(&query::holding_with_permit(deps, permit)?)
+        }
+        QueryMsg::AssetHolders { asset, auth } => {
+            let asset = query::asset_key(deps, &asset)?;
+            let user = query::authenticate(deps, auth)?;
+            to_binary(&query::asset_holders(deps, asset, user)?)
+        }
+        QueryMsg::TotalUnbonding { asset } => {
+            let asset = query::asset_key(deps, &asset)?;
+            to_binary(&query::total_unbonding(deps, asset)?)
+        }
+        QueryMsg::IdleAllocations { asset, since } => {
+            let asset = query::asset_key(deps, &asset)?;
+            to_binary(&query::idle_allocations(deps, asset, since)?)
+        }
+        QueryMsg::FundingOrder { asset } => {
+            let asset = query::asset_key(deps, &asset)?;
+            to_binary(&query::funding_order(deps, env, asset)?)
+        }
+        QueryMsg::AdapterApr { asset } => {
+            let asset = query::asset_key(deps, &asset)?;
+            to_binary(&query::adapter_apr(deps, asset)?)
         }
         QueryMsg::Metrics {
             date,
             epoch,
             period,
         } => to_binary(&query::metrics(deps, env, date, epoch, period)?),
+        QueryMsg::Version {} => to_binary(&query::version(deps)?),
 
-        QueryMsg::Manager(a) => match a {
-            manager::SubQueryMsg::Balance { asset, holder } => {
-                let asset = deps.api.addr_validate(&asset)?;
-                let holder = deps.api.addr_validate(&holder)?;
-                to_binary(&query::balance(deps, asset, holder)?)
-            }
-            manager::SubQueryMsg::BatchBalance { assets, holder } => {
-                let mut val_assets = vec![];
+        QueryMsg::Manager(q) => manager_query(deps, env, q, None),
+        QueryMsg::ManagerWithAuth { query, auth } => {
+            let user = query::authenticate(deps, auth)?;
+            manager_query(deps, env, query, Some(user))
+        }
+    }
+}
 
-                for a in assets {
-                    val_assets.push(deps.api.addr_validate(&a)?);
-                }
-                let holder = deps.api.addr_validate(&holder)?;
+fn manager_query(
+    deps: Deps,
+    env: Env,
+    msg: manager::SubQueryMsg,
+    user: Option<Addr>,
+) -> StdResult<Binary> {
+    match msg {
+        manager::SubQueryMsg::Balance { asset, holder } => {
+            let asset = query::asset_key(deps, &asset)?;
+            let holder = deps.api.addr_validate(&holder)?;
+            query::authorize_holder(deps, &holder, user)?;
+            to_binary(&query::balance(deps, asset, holder)?)
+        }
+        manager::SubQueryMsg::BatchBalance { assets, holder } => {
+            let mut val_assets = vec![];
 
-                to_binary(&query::batch_balance(deps, val_assets, holder)?)
-            }
-            manager::SubQueryMsg::Unbonding { asset, holder } => {
-                let asset = deps.api.addr_validate(&asset)?;
-                let holder = deps.api.addr_validate(&holder)?;
-                to_binary(&query::unbonding(deps, asset, holder)?)
-            }
-            manager::SubQueryMsg::Unbondable { asset, holder } => {
-                let asset = deps.api.addr_validate(&asset)?;
-                let holder = deps.api.addr_validate(&holder)?;
-                to_binary(&query::unbondable(deps, env, asset, holder)?)
-            }
-            manager::SubQueryMsg::Claimable { asset, holder } => {
-                let asset = deps.api.addr_validate(&asset)?;
-                let holder = deps.api.addr_validate(&holder)?;
-                to_binary(&query::claimable(deps, env, asset, holder)?)
-            }
-            manager::SubQueryMsg::Reserves { asset, holder } => {
-                let asset = deps.api.addr_validate(&asset)?;
-                let holder = deps.api.addr_validate(&holder)?;
-                to_binary(&query::reserves(deps, env, asset, holder)?)
+            for a in assets {
+                val_assets.push(query::asset_key(deps, &a)?);
             }
-        },
+            let holder = deps.api.addr_validate(&holder)?;
+            query::authorize_holder(deps, &holder, user)?;
+
+            to_binary(&query::batch_balance(deps, val_assets, holder)?)
+        }
+        manager::SubQueryMsg::Unbonding { asset, holder } => {
+            let asset = query::asset_key(deps, &asset)?;
+            let holder = deps.api.addr_validate(&holder)?;
+            query::authorize_holder(deps, &holder, user)?;
+            to_binary(&query::unbonding(deps, asset, holder)?)
+        }
+        manager::SubQueryMsg::Unbondable { asset, holder } => {
+            let asset = query::asset_key(deps, &asset)?;
+            let holder = deps.api.addr_validate(&holder)?;
+            query::authorize_holder(deps, &holder, user)?;
+            to_binary(&query::unbondable(deps, env, asset, holder)?)
+        }
+        manager::SubQueryMsg::Claimable { asset, holder } => {
+            let asset = query::asset_key(deps, &asset)?;
+            let holder = deps.api.addr_validate(&holder)?;
+            query::authorize_holder(deps, &holder, user)?;
+            to_binary(&query::claimable(deps, env, asset, holder)?)
+        }
+        // reserves are the manager's own balance, nothing holder specific
+        manager::SubQueryMsg::Reserves { asset, holder } => {
+            let asset = query::load_asset(deps, &asset)?;
+            let holder = deps.api.addr_validate(&holder)?;
+            to_binary(&query::reserves(deps, env, asset, holder)?)
+        }
     }
 }
@@ -1,33 +1,41 @@
-use crate::storage::*;
+use crate::{query, storage::*};
 use itertools::{Either, Itertools};
 use shade_protocol::{
     admin::helpers::{validate_admin, AdminPermissions},
     c_std::{
         to_binary,
         Addr,
+        BankMsg,
         Binary,
+        Coin,
+        CosmosMsg,
         DepsMut,
         Env,
         MessageInfo,
         Response,
-        StdError,
         StdResult,
+        Storage,
         Uint128,
     },
     dao::{
         adapter,
         treasury_manager::{
             Action,
+            AdapterSamples,
             Allocation,
             AllocationMeta,
             AllocationTempData,
             AllocationType,
+            Asset,
             Balance,
+            BalanceSample,
+            Config,
             Context,
             ExecuteAnswer,
             Holding,
             Metric,
             Status,
+            TreasuryManagerError,
         },
     },
     snip20,
@@ -35,7 +43,6 @@ use shade_protocol::{
         batch::{SendAction, SendFromAction},
         helpers::{
             allowance_query,
-            balance_query,
             batch_send_from_msg,
             batch_send_msg,
             register_receive,
@@ -51,6 +58,33 @@ use shade_protocol::{
 
 static ONE_HUNDRED_PERCENT: Uint128 = Uint128::new(10u128.pow(18));
 
+/* Target balance of an allocation out of `total`.
+ * Allocations must be passed in storage order, Amount before Portion, so the amounts can be set
+ * aside in `reserved_for_amount_adapters` before portions take their share of the rest
+ */
+pub fn desired_amount(
+    alloc_type: &AllocationType,
+    amount: Uint128,
+    total: Uint128,
+    reserved_for_amount_adapters: &mut Uint128,
+) -> Uint128 {
+    match alloc_type {
+        AllocationType::Amount => {
+            *reserved_for_amount_adapters += amount;
+            // since amount adapters' allocations are static
+            amount
+        }
+        AllocationType::Portion => {
+            // If statement to prevent overflow
+            if total > *reserved_for_amount_adapters {
+                amount.multiply_ratio(total - *reserved_for_amount_adapters, ONE_HUNDRED_PERCENT)
+            } else {
+                Uint128::zero()
+            }
+        }
+    }
+}
+
 pub fn receive(
     deps: DepsMut,
     env: Env,
@@ -64,7 +98,7 @@ pub fn receive(
     let asset = match ASSETS.may_load(deps.storage, info.sender.clone())? {
         Some(a) => a,
         None => {
-            return Err(StdError::generic_err("Not a registered asset"));
+            return Err(TreasuryManagerError::UnrecognizedAsset.into());
         }
     };
 
@@ -96,9 +130,7 @@ pub fn receive(
 
     let mut holding = HOLDING.load(deps.storage, holder.clone())?;
     if holding.status == Status::Closed {
-        return Err(StdError::generic_err(
-            "Cannot add holdings when status is closed",
-        ));
+        return Err(TreasuryManagerError::ClosedHolding.into());
     }
     if let Some(i) = holding
         .balances
@@ -126,6 +158,10 @@ pub fn update_config(
     info: MessageInfo,
     admin_auth: Option<RawContract>,
     treasury: Option<String>,
+    profit_recipient: Option<String>,
+    dust_threshold: Option<Uint128>,
+    max_messages: Option<u32>,
+    query_auth: Option<RawContract>,
 ) -> StdResult<Response> {
     let mut config = CONFIG.load(deps.storage)?;
 
@@ -140,7 +176,34 @@ pub fn update_config(
         config.admin_auth = admin_auth.into_valid(deps.api)?;
     }
     if let Some(treasury) = treasury {
-        config.treasury = deps.api.addr_validate(&treasury)?;
+        let treasury = deps.api.addr_validate(&treasury)?;
+        move_treasury(deps.storage, &mut config, treasury)?;
+    }
+    if let Some(profit_recipient) = profit_recipient {
+        let profit_recipient = deps.api.addr_validate(&profit_recipient)?;
+
+        // gains are credited to the recipient's holding, so it needs to be a holder
+        let mut holders = HOLDERS.load(deps.storage)?;
+        if !holders.contains(&profit_recipient) {
+            holders.push(profit_recipient.clone());
+            HOLDERS.save(deps.storage, &holders)?;
+            HOLDING.save(deps.storage, profit_recipient.clone(), &Holding {
+                balances: Vec::new(),
+                unbondings: Vec::new(),
+                status: Status::Active,
+            })?;
+        }
+
+        config.profit_recipient = Some(profit_recipient);
+    }
+    if let Some(dust_threshold) = dust_threshold {
+        config.dust_threshold = dust_threshold;
+    }
+    if let Some(max_messages) = max_messages {
+        config.max_messages = Some(max_messages);
+    }
+    if let Some(query_auth) = query_auth {
+        config.query_auth = Some(query_auth.into_valid(deps.api)?);
     }
 
     CONFIG.save(deps.storage, &config)?;
@@ -194,6 +257,128 @@ pub fn register_asset(
         })?))
 }
 
+pub fn refresh_asset(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    contract: &Contract,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::TreasuryManager,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    if ASSETS
+        .may_load(deps.storage, contract.address.clone())?
+        .is_none()
+    {
+        return Err(TreasuryManagerError::UnrecognizedAsset.into());
+    }
+
+    // Re-fetch in case the token migrated code or updated its metadata
+    ASSETS.save(
+        deps.storage,
+        contract.address.clone(),
+        &snip20::helpers::fetch_snip20(&contract, &deps.querier)?,
+    )?;
+
+    Ok(Response::new()
+        .add_message(register_receive(
+            env.contract.code_hash.clone(),
+            None,
+            &contract,
+        )?)
+        .set_data(to_binary(&ExecuteAnswer::RefreshAsset {
+            status: ResponseStatus::Success,
+        })?))
+}
+
+pub fn register_native_asset(
+    deps: DepsMut,
+    _env: &Env,
+    info: MessageInfo,
+    denom: String,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::TreasuryManager,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    let mut denoms = NATIVE_ASSETS.may_load(deps.storage)?.unwrap_or_default();
+    if denoms.contains(&denom) {
+        return Err(TreasuryManagerError::AlreadyRegistered(denom).into());
+    }
+    denoms.push(denom.clone());
+    NATIVE_ASSETS.save(deps.storage, &denoms)?;
+
+    ALLOCATIONS.save(deps.storage, Addr::unchecked(denom.clone()), &Vec::new())?;
+
+    UNBONDINGS.save(deps.storage, Addr::unchecked(denom), &Uint128::zero())?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::RegisterNativeAsset {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+pub fn deposit_native(deps: DepsMut, env: &Env, info: MessageInfo) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let denoms = NATIVE_ASSETS.may_load(deps.storage)?.unwrap_or_default();
+
+    // Default to treasury if not sent by a holder
+    let holder = match HOLDERS.load(deps.storage)?.contains(&info.sender) {
+        true => info.sender.clone(),
+        false => config.treasury,
+    };
+
+    let mut holding = HOLDING.load(deps.storage, holder.clone())?;
+    if holding.status == Status::Closed {
+        return Err(TreasuryManagerError::ClosedHolding.into());
+    }
+
+    for coin in info.funds {
+        if !denoms.contains(&coin.denom) {
+            return Err(TreasuryManagerError::UnrecognizedAsset.into());
+        }
+        let token = Addr::unchecked(coin.denom);
+
+        METRICS.push(deps.storage, env.block.time, Metric {
+            action: Action::FundsReceived,
+            context: Context::Receive,
+            timestamp: env.block.time.seconds(),
+            token: token.clone(),
+            amount: coin.amount,
+            user: info.sender.clone(),
+        })?;
+
+        if let Some(i) = holding.balances.iter().position(|b| b.token == token) {
+            holding.balances[i].amount += coin.amount;
+        } else {
+            holding.balances.push(Balance {
+                token,
+                amount: coin.amount,
+            });
+        }
+    }
+
+    HOLDING.save(deps.storage, holder, &holding)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::DepositNative {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
 pub fn allocate(
     deps: DepsMut,
     _env: &Env,
@@ -211,10 +396,18 @@ pub fn allocate(
     )?;
 
     if allocation.tolerance >= ONE_HUNDRED_PERCENT {
-        return Err(StdError::generic_err(format!(
-            "Tolerance {} >= 100%",
-            allocation.tolerance
-        )));
+        return Err(
+            TreasuryManagerError::ToleranceExceedsHundredPercent(allocation.tolerance).into(),
+        );
+    }
+
+    // probe the adapter so a bad contract can't freeze update's balance queries
+    if adapter::balance_query(deps.querier, &asset, allocation.contract.clone()).is_err() {
+        return Err(TreasuryManagerError::NotAnAdapter {
+            adapter: allocation.contract.address.clone(),
+            asset: asset.clone(),
+        }
+        .into());
     }
 
     let mut allocations = ALLOCATIONS
@@ -226,11 +419,9 @@ pub fn allocate(
         .iter()
         .position(|a| a.contract.address == allocation.contract.address);
 
-    match stale_alloc {
-        Some(i) => {
-            allocations.swap_remove(i);
-        }
-        None => {}
+    let last_rebalance = match stale_alloc {
+        Some(i) => allocations.swap_remove(i).last_rebalance,
+        None => None,
     };
 
     allocations.push(AllocationMeta {
@@ -239,6 +430,7 @@ pub fn allocate(
         amount: allocation.amount,
         alloc_type: allocation.alloc_type,
         tolerance: allocation.tolerance,
+        last_rebalance,
     });
 
     // ensure that the portion allocations don't go above 100%
@@ -254,9 +446,7 @@ pub fn allocate(
         .sum::<Uint128>()
         > ONE_HUNDRED_PERCENT
     {
-        return Err(StdError::generic_err(
-            "Invalid allocation total exceeding 100%",
-        ));
+        return Err(TreasuryManagerError::AllocationExceedsHundredPercent.into());
     }
 
     // Sort the allocations Amount < Portion
@@ -281,12 +471,7 @@ pub fn allocate(
 }
 
 pub fn claim(deps: DepsMut, env: &Env, info: MessageInfo, asset: Addr) -> StdResult<Response> {
-    let full_asset = match ASSETS.may_load(deps.storage, asset.clone())? {
-        Some(a) => a,
-        None => {
-            return Err(StdError::generic_err("Unrecognized asset"));
-        }
-    };
+    let full_asset = query::load_asset(deps.as_ref(), asset.as_str())?;
 
     let config = CONFIG.load(deps.storage)?;
     // if the claimer isn't a holder, it should default to the treasruy
@@ -334,12 +519,7 @@ pub fn claim(deps: DepsMut, env: &Env, info: MessageInfo, asset: Addr) -> StdRes
         }
     };
 
-    let reserves = balance_query(
-        &deps.querier,
-        env.contract.address.clone(),
-        VIEWING_KEY.load(deps.storage)?,
-        &full_asset.contract.clone(),
-    )?;
+    let reserves = query::asset_balance(deps.as_ref(), env.contract.address.clone(), &full_asset)?;
 
     let send_amount = {
         // if reserves and total claimed is less than the unbondings of the holder, we need to send
@@ -373,14 +553,7 @@ pub fn claim(deps: DepsMut, env: &Env, info: MessageInfo, asset: Addr) -> StdRes
     HOLDING.save(deps.storage, claimer.clone(), &holding)?;
 
     // Send claimed funds
-    messages.push(send_msg(
-        claimer.clone(),
-        send_amount,
-        None,
-        None,
-        None,
-        &full_asset.contract.clone(),
-    )?);
+    messages.push(send_asset_msg(claimer.clone(), send_amount, &full_asset)?);
 
     METRICS.push(deps.storage, env.block.time, Metric {
         action: Action::SendFunds,
@@ -399,12 +572,39 @@ pub fn claim(deps: DepsMut, env: &Env, info: MessageInfo, asset: Addr) -> StdRes
     )?))
 }
 
-pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdResult<Response> {
+pub fn update(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    asset: Addr,
+    force: bool,
+) -> StdResult<Response> {
     let config = CONFIG.load(deps.storage)?;
 
-    let full_asset = ASSETS.load(deps.storage, asset.clone())?;
+    // ignoring tolerances is reserved for deliberate rebalances
+    if force {
+        validate_admin(
+            &deps.querier,
+            AdminPermissions::TreasuryManager,
+            &info.sender,
+            &config.admin_auth,
+        )?;
+    }
 
-    let mut allocations = ALLOCATIONS.load(deps.storage, asset.clone())?;
+    let full_asset = query::load_asset(deps.as_ref(), asset.as_str())?;
+
+    let mut allocations = ALLOCATIONS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or_default();
+
+    // nothing to rebalance
+    if allocations.is_empty() {
+        return Ok(
+            Response::new().set_data(to_binary(&adapter::ExecuteAnswer::Update {
+                status: ResponseStatus::Success,
+            })?),
+        );
+    }
 
     // the sum of balances on 'amount' adapters
     let mut amount_total = Uint128::zero();
@@ -414,39 +614,29 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
     let mut stale_allocs = vec![];
     let mut messages = vec![];
     let mut adapter_info = vec![];
+    let mut samples = ADAPTER_SAMPLES
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or_default();
 
     /* this loop has 2 purposes
      * - check for stale allocaitons that need to be removed
      * - fill the amount_total and portion_total vars with data
      */
     for (i, a) in allocations.clone().iter().enumerate() {
-        let bal = adapter::balance_query(
-            deps.querier,
-            &full_asset.contract.address,
-            a.contract.clone(),
-        )?;
-        let mut unbonding = adapter::unbonding_query(
-            deps.querier,
-            &full_asset.contract.address,
-            a.contract.clone(),
-        )?;
-        let unbondable = adapter::unbondable_query(
-            deps.querier,
-            &full_asset.contract.address,
-            a.contract.clone(),
-        )?;
-        let claimable = adapter::claimable_query(
-            deps.querier,
-            &full_asset.contract.address,
-            a.contract.clone(),
-        )?;
+        let bal = adapter::balance_query(deps.querier, &asset, a.contract.clone())?;
+        let mut unbonding = adapter::unbonding_query(deps.querier, &asset, a.contract.clone())?;
+        let unbondable = adapter::unbondable_query(deps.querier, &asset, a.contract.clone())?;
+        let claimable = adapter::claimable_query(deps.querier, &asset, a.contract.clone())?;
         if !claimable.is_zero() {
-            messages.push(adapter::claim_msg(
-                &full_asset.contract.address.clone(),
-                a.contract.clone(),
-            )?);
+            messages.push(adapter::claim_msg(&asset, a.contract.clone())?);
             unbonding += claimable;
         }
+        sample_adapter(
+            &mut samples,
+            &a.contract.address,
+            env,
+            bal.saturating_sub(unbonding),
+        );
         // if all these values are zero we can safely drop the alloc
         if bal.is_zero()
             && a.amount.is_zero()
@@ -482,6 +672,8 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
         }
         ALLOCATIONS.save(deps.storage, asset.clone(), &allocations)?;
     }
+    samples.retain(|s| allocations.iter().any(|a| a.contract.address == s.adapter));
+    ADAPTER_SAMPLES.save(deps.storage, asset.clone(), &samples)?;
 
     // the holder is the entity that actually holds the tokens that the treasury manager can spend
     // holder_unbonding represents how much the holder has currently asked to unbond
@@ -519,23 +711,25 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
     let key = VIEWING_KEY.load(deps.storage)?;
 
     // Available treasury allowance
-    let mut allowance = allowance_query(
-        &deps.querier,
-        config.treasury.clone(),
-        env.contract.address.clone(),
-        key.clone(),
-        1,
-        &full_asset.contract.clone(),
-    )?
-    .allowance;
-
-    // snip20 balance query to get the treasury managers current snip20 balance
-    let mut balance = balance_query(
-        &deps.querier,
-        env.contract.address.clone(),
-        key.clone(),
-        &full_asset.contract.clone(),
-    )?;
+    let mut allowance = match &full_asset {
+        Asset::Snip20(contract) => {
+            allowance_query(
+                &deps.querier,
+                config.treasury.clone(),
+                env.contract.address.clone(),
+                key.clone(),
+                1,
+                contract,
+            )?
+            .allowance
+        }
+        // natives can't be spent from the treasury, holders deposit them instead
+        Asset::Native(_) => Uint128::zero(),
+    };
+
+    // the treasury managers current balance of the asset
+    let mut balance =
+        query::asset_balance(deps.as_ref(), env.contract.address.clone(), &full_asset)?;
 
     // total amount allocated to adapters + current snip20 balance
     // We subtract holder_unbonding to ensure that those tokens will be claimable
@@ -555,30 +749,22 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
     let mut allowance_used = Uint128::zero();
     let mut balance_used = Uint128::zero();
     let mut reserved_for_amount_adapters = Uint128::zero();
+    // adapters that had a send or unbond emitted this run
+    let mut rebalanced = vec![];
+    // send and unbond actions emitted this run, checked against max_messages
+    let mut message_count = 0;
+    // actions were skipped because max_messages was hit
+    let mut truncated = false;
 
     // loop through adapters with allocations
     for adapter in adapter_info {
         // calculate the target balance for each
-        let desired_amount = match adapter.alloc_type {
-            AllocationType::Amount => {
-                reserved_for_amount_adapters += adapter.amount;
-                // since amount adapters' allocations are static
-                adapter.amount
-            }
-            AllocationType::Portion => {
-                // Since the list of allocations is sorted, we can ensure that type::amount
-                // adapters will be processed first, so we can calculate the amount available for
-                // allocation with total - reserved_for_amount_adapters
-                // If statement to prevent overflow
-                if total > reserved_for_amount_adapters {
-                    adapter
-                        .amount
-                        .multiply_ratio(total - reserved_for_amount_adapters, ONE_HUNDRED_PERCENT)
-                } else {
-                    Uint128::zero()
-                }
-            }
-        };
+        let desired_amount = desired_amount(
+            &adapter.alloc_type,
+            adapter.amount,
+            total,
+            &mut reserved_for_amount_adapters,
+        );
         // threshold is the desired_amount * a percentage held in adapter.tolerance,
         // the treasury manager will only attempt to rebalance if the adapter crosses the threshold
         // in either direction
@@ -601,7 +787,11 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                 // target send amount to adapter
                 let mut desired_input = desired_amount - effective_balance;
                 // check if threshold is crossed
-                if desired_input <= threshold {
+                if !force && desired_input <= threshold {
+                    continue;
+                }
+                if cap_reached(config.max_messages, message_count) {
+                    truncated = true;
                     continue;
                 }
 
@@ -623,6 +813,9 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                         user: adapter.contract.address.clone(),
                     });
 
+                    rebalanced.push(adapter.contract.address.clone());
+                    message_count += 1;
+
                     // reduce snip20 balance for future loops
                     balance = balance - desired_input;
                     balance_used += desired_input;
@@ -648,6 +841,9 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                         user: adapter.contract.address.clone(),
                     });
 
+                    rebalanced.push(adapter.contract.address.clone());
+                    message_count += 1;
+
                     // reduce the desired_input to reflect the balance being sent, we know this will
                     // not overflow because if balance was > desired_input, we would have hit a
                     // continue statement
@@ -657,6 +853,11 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                 }
 
                 if !allowance.is_zero() {
+                    // the balance send above may have used the last slot
+                    if cap_reached(config.max_messages, message_count) {
+                        truncated = true;
+                        continue;
+                    }
                     // This will only execute after snip20 balance has been used up
                     // Fully covered by allowance
                     if desired_input < allowance {
@@ -677,6 +878,9 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                             user: adapter.contract.address.clone(),
                         });
 
+                        rebalanced.push(adapter.contract.address.clone());
+                        message_count += 1;
+
                         allowance_used += desired_input;
                         // this will not overflow due to check in if statement
                         allowance = allowance - desired_input;
@@ -703,6 +907,9 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                             user: adapter.contract.address.clone(),
                         });
 
+                        rebalanced.push(adapter.contract.address.clone());
+                        message_count += 1;
+
                         // account for allowance being sent out
                         allowance_used += allowance;
                         allowance = Uint128::zero();
@@ -715,7 +922,11 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                 let desired_output = effective_balance - desired_amount;
 
                 // check to see that the threshold has been crossed
-                if desired_output <= threshold {
+                if !force && desired_output <= threshold {
+                    continue;
+                }
+                if cap_reached(config.max_messages, message_count) {
+                    truncated = true;
                     continue;
                 }
 
@@ -733,20 +944,26 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
                         amount: desired_output,
                         user: adapter.contract.address.clone(),
                     });
+                    rebalanced.push(adapter.contract.address.clone());
+                    message_count += 1;
                 }
-                let unbondings = UNBONDINGS
-                    .load(deps.storage, full_asset.contract.address.clone())?
-                    + desired_output;
-                UNBONDINGS.save(
-                    deps.storage,
-                    full_asset.contract.address.clone(),
-                    &unbondings,
-                )?;
+                let unbondings = UNBONDINGS.load(deps.storage, asset.clone())? + desired_output;
+                UNBONDINGS.save(deps.storage, asset.clone(), &unbondings)?;
             }
             _ => {}
         }
     }
 
+    // Record the block height on allocations that were acted on
+    if !rebalanced.is_empty() {
+        for alloc in allocations.iter_mut() {
+            if rebalanced.contains(&alloc.contract.address) {
+                alloc.last_rebalance = Some(env.block.height);
+            }
+        }
+        ALLOCATIONS.save(deps.storage, asset.clone(), &allocations)?;
+    }
+
     // Credit treasury balance with allowance used by adding allowance_used to the existing balance
     // or creating a new balance struct with allowance_used as the balance
     let mut holding = HOLDING.load(deps.storage, config.treasury.clone())?;
@@ -763,35 +980,86 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
         });
     }
     HOLDING.save(deps.storage, config.treasury.clone(), &holding)?;
+    LAST_ALLOWANCE_USED.save(deps.storage, asset.clone(), &allowance_used)?;
 
     // Determine Gainz & Losses & credit to treasury
     holder_principal += allowance_used;
 
+    // Sweep unaccounted reserves under the dust threshold into the treasury holding, only
+    // reserves not backed by holder principal count as dust
+    let unaccounted = (total - allowance).saturating_sub(holder_principal);
+    let dust = std::cmp::min(balance, unaccounted);
+    if !dust.is_zero() && dust < config.dust_threshold {
+        let mut holding = HOLDING.load(deps.storage, config.treasury.clone())?;
+        if let Some(i) = holding.balances.iter().position(|u| u.token == asset) {
+            holding.balances[i].amount += dust;
+        } else {
+            holding.balances.push(Balance {
+                token: asset.clone(),
+                amount: dust,
+            });
+        }
+        HOLDING.save(deps.storage, config.treasury.clone(), &holding)?;
+        holder_principal += dust;
+    }
+
+    // losses that could not be debited from the treasury's recorded balance, the holder principal
+    // they leave unbacked was already realized by an earlier update
+    let mut excess_losses = EXCESS_LOSSES
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or_default();
+    let holder_principal = holder_principal.saturating_sub(excess_losses);
+
     // this will never overflow because total is a sum of allowance
     match (total - allowance).cmp(&holder_principal) {
         std::cmp::Ordering::Greater => {
-            let gains = (total - allowance) - holder_principal;
-            // debit gains to treasury
-            let mut holding = HOLDING.load(deps.storage, config.treasury.clone())?;
-            if let Some(i) = holding.balances.iter().position(|u| u.token == asset) {
-                holding.balances[i].amount += gains;
+            let mut gains = (total - allowance) - holder_principal;
+            // recovered funds back the unbacked principal before counting as gains
+            let recovered = std::cmp::min(gains, excess_losses);
+            excess_losses -= recovered;
+            gains -= recovered;
+
+            if !gains.is_zero() {
+                // debit gains to the profit recipient, defaulting to treasury
+                let recipient = config
+                    .profit_recipient
+                    .clone()
+                    .unwrap_or(config.treasury.clone());
+                let mut holding = HOLDING.load(deps.storage, recipient.clone())?;
+                if let Some(i) = holding.balances.iter().position(|u| u.token == asset) {
+                    holding.balances[i].amount += gains;
+                } else {
+                    holding.balances.push(Balance {
+                        token: asset.clone(),
+                        amount: gains,
+                    });
+                }
+                HOLDING.save(deps.storage, recipient.clone(), &holding)?;
+                metrics.push(Metric {
+                    action: Action::RealizeGains,
+                    context: Context::Update,
+                    timestamp: env.block.time.seconds(),
+                    token: asset.clone(),
+                    amount: gains,
+                    user: recipient,
+                });
             }
-            HOLDING.save(deps.storage, config.treasury.clone(), &holding)?;
-            metrics.push(Metric {
-                action: Action::RealizeGains,
-                context: Context::Update,
-                timestamp: env.block.time.seconds(),
-                token: asset.clone(),
-                amount: gains,
-                user: config.treasury.clone(),
-            });
         }
         std::cmp::Ordering::Less => {
             let losses = holder_principal - (total - allowance);
             // credit losses to treasury
             let mut holding = HOLDING.load(deps.storage, config.treasury.clone())?;
             if let Some(i) = holding.balances.iter().position(|u| u.token == asset) {
-                holding.balances[i].amount -= losses;
+                // losses can outpace the treasury's principal, clamp to zero and track the rest
+                // separately so update can't be frozen by an underflow
+                if losses > holding.balances[i].amount {
+                    excess_losses += losses - holding.balances[i].amount;
+                    holding.balances[i].amount = Uint128::zero();
+                } else {
+                    holding.balances[i].amount -= losses;
+                }
+            } else {
+                excess_losses += losses;
             }
             HOLDING.save(deps.storage, config.treasury.clone(), &holding)?;
             metrics.push(Metric {
@@ -805,32 +1073,64 @@ pub fn update(deps: DepsMut, env: &Env, _info: MessageInfo, asset: Addr) -> StdR
         }
         _ => {}
     }
+    EXCESS_LOSSES.save(deps.storage, asset.clone(), &excess_losses)?;
 
-    // exec batch balance send messages
-    if !send_actions.is_empty() {
-        messages.push(batch_send_msg(
-            send_actions,
-            None,
-            &full_asset.contract.clone(),
-        )?);
-    }
+    match &full_asset {
+        Asset::Snip20(contract) => {
+            // exec batch balance send messages
+            if !send_actions.is_empty() {
+                messages.push(batch_send_msg(send_actions, None, contract)?);
+            }
 
-    // exec batch allowance send messages
-    if !send_from_actions.is_empty() {
-        messages.push(batch_send_from_msg(
-            send_from_actions,
-            None,
-            &full_asset.contract.clone(),
-        )?);
+            // exec batch allowance send messages
+            if !send_from_actions.is_empty() {
+                messages.push(batch_send_from_msg(send_from_actions, None, contract)?);
+            }
+        }
+        // the bank has no batch send, so each adapter gets its own
+        Asset::Native(_) => {
+            for action in send_actions {
+                messages.push(send_asset_msg(
+                    Addr::unchecked(action.recipient),
+                    action.amount,
+                    &full_asset,
+                )?);
+            }
+        }
     }
 
+    record_flows(deps.storage, &asset, &metrics)?;
     METRICS.append(deps.storage, env.block.time, &mut metrics)?;
 
-    Ok(Response::new().add_messages(messages).set_data(to_binary(
-        &adapter::ExecuteAnswer::Update {
+    Ok(Response::new()
+        .add_messages(messages)
+        .add_attribute("truncated", truncated.to_string())
+        .set_data(to_binary(&adapter::ExecuteAnswer::Update {
             status: ResponseStatus::Success,
-        },
-    )?))
+        })?))
+}
+
+// Sends funds out of the manager, through the token for snip20s and the bank for natives
+fn send_asset_msg(recipient: Addr, amount: Uint128, asset: &Asset) -> StdResult<CosmosMsg> {
+    match asset {
+        Asset::Snip20(contract) => send_msg(recipient, amount, None, None, None, contract),
+        Asset::Native(denom) => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: denom.clone(),
+                amount,
+            }],
+        })),
+    }
+}
+
+// Allocations are processed in priority order, so once the cap is hit the rest wait for the next
+// update
+fn cap_reached(max_messages: Option<u32>, message_count: u32) -> bool {
+    match max_messages {
+        Some(max) => message_count >= max,
+        None => false,
+    }
 }
 
 pub fn unbond(
@@ -857,7 +1157,7 @@ pub fn unbond(
         }
     };
 
-    let full_asset = ASSETS.load(deps.storage, asset.clone())?;
+    let full_asset = query::load_asset(deps.as_ref(), asset.as_str())?;
 
     // Adjust holder balance
     let mut holding = HOLDING.load(deps.storage, unbonder.clone())?;
@@ -870,17 +1170,14 @@ pub fn unbond(
     {
         Some(i) => i,
         None => {
-            return Err(StdError::generic_err(format!(
-                "Cannot unbond, holder has no holdings of {}",
-                asset.clone()
-            )));
+            return Err(TreasuryManagerError::NoHoldings(asset.clone()).into());
         }
     };
 
     let mut unbond_amount = amount;
     // Check balance exceeds unbond amount
     if holding.balances[balance_i].amount < amount {
-        return Err(StdError::generic_err("Not enough funds to unbond"));
+        return Err(TreasuryManagerError::InsufficientUnbondFunds.into());
     } else {
         if holding.status == Status::Active {
             holding.balances[balance_i].amount = holding.balances[balance_i].amount - amount;
@@ -916,7 +1213,7 @@ pub fn unbond(
 
     // find the unbond_amount based off of amounts that the TM has unbonded independent of a holder
     unbond_amount = {
-        let u = UNBONDINGS.load(deps.storage, full_asset.contract.address.clone())?;
+        let u = UNBONDINGS.load(deps.storage, asset.clone())?;
         // if the independent unbondings is less than what the adapters are acutally unbonding, we
         // know another holder has asked to do some unbonding and the adapters are unbonding for
         // that holder
@@ -924,19 +1221,11 @@ pub fn unbond(
             if u <= unbond_amount {
                 // if amount > independent unbonding, we reduce independent unbondings to
                 // zero and return the amount we actually want to unbond from the adapters
-                UNBONDINGS.save(
-                    deps.storage,
-                    full_asset.contract.address.clone(),
-                    &Uint128::zero(),
-                )?;
+                UNBONDINGS.save(deps.storage, asset.clone(), &Uint128::zero())?;
                 unbond_amount - u
             } else {
                 // independent unbondings covers the amount
-                UNBONDINGS.save(
-                    deps.storage,
-                    full_asset.contract.address.clone(),
-                    &(u - unbond_amount),
-                )?;
+                UNBONDINGS.save(deps.storage, asset.clone(), &(u - unbond_amount))?;
                 Uint128::zero()
             }
         } else {
@@ -970,12 +1259,8 @@ pub fn unbond(
     }
 
     // Reserves to be sent immediately
-    let mut reserves = balance_query(
-        &deps.querier,
-        env.contract.address.clone(),
-        VIEWING_KEY.load(deps.storage)?,
-        &full_asset.contract.clone(),
-    )?;
+    let mut reserves =
+        query::asset_balance(deps.as_ref(), env.contract.address.clone(), &full_asset)?;
 
     // Remove pending unbondings from reserves
     if reserves > other_unbondings {
@@ -992,14 +1277,7 @@ pub fn unbond(
         if reserves < unbond_amount {
             // reserves can't cover unbond
             // Don't need batch send bc there's only one send msg
-            messages.push(send_msg(
-                unbonder.clone(),
-                reserves,
-                None,
-                None,
-                None,
-                &full_asset.contract.clone(),
-            )?);
+            messages.push(send_asset_msg(unbonder.clone(), reserves, &full_asset)?);
             metrics.push(Metric {
                 action: Action::SendFunds,
                 context: Context::Unbond,
@@ -1018,14 +1296,7 @@ pub fn unbond(
             HOLDING.save(deps.storage, unbonder, &holding)?;
         } else {
             // reserves can cover unbond
-            messages.push(send_msg(
-                unbonder.clone(),
-                amount,
-                None,
-                None,
-                None,
-                &full_asset.contract.clone(),
-            )?);
+            messages.push(send_asset_msg(unbonder.clone(), amount, &full_asset)?);
             metrics.push(Metric {
                 action: Action::SendFunds,
                 context: Context::Unbond,
@@ -1042,6 +1313,7 @@ pub fn unbond(
             }
             HOLDING.save(deps.storage, unbonder, &holding)?;
 
+            record_flows(deps.storage, &asset, &metrics)?;
             METRICS.append(deps.storage, env.block.time, &mut metrics)?;
             return Ok(Response::new().add_messages(messages).set_data(to_binary(
                 &adapter::ExecuteAnswer::Unbond {
@@ -1087,7 +1359,7 @@ pub fn unbond(
     if unbond_amount == tot_unbond_available {
         for a in alloc_meta.clone() {
             messages.push(adapter::unbond_msg(
-                &full_asset.contract.address.clone(),
+                &asset,
                 a.unbondable.clone(),
                 a.contract.clone(),
             )?);
@@ -1100,6 +1372,7 @@ pub fn unbond(
                 user: a.contract.address.clone(),
             });
         }
+        record_flows(deps.storage, &asset, &metrics)?;
         METRICS.append(deps.storage, env.block.time, &mut metrics)?;
         return Ok(Response::new().add_messages(messages).set_data(to_binary(
             &adapter::ExecuteAnswer::Unbond {
@@ -1135,7 +1408,7 @@ pub fn unbond(
     if unbond_amount == total_amount_unbonding {
         for (i, meta) in amounts.clone().iter().enumerate() {
             messages.push(adapter::unbond_msg(
-                &full_asset.contract.address.clone(),
+                &asset,
                 unbond_amounts[i],
                 meta.contract.clone(),
             )?);
@@ -1148,6 +1421,7 @@ pub fn unbond(
                 user: meta.contract.address.clone(),
             });
         }
+        record_flows(deps.storage, &asset, &metrics)?;
         METRICS.append(deps.storage, env.block.time, &mut metrics)?;
         return Ok(Response::new().add_messages(messages).set_data(to_binary(
             &adapter::ExecuteAnswer::Unbond {
@@ -1171,7 +1445,7 @@ pub fn unbond(
                 unbond_amounts[i] += Uint128::new(1);
             }
             messages.push(adapter::unbond_msg(
-                &full_asset.contract.address.clone(),
+                &asset,
                 unbond_amounts[i],
                 meta.contract.clone(),
             )?);
@@ -1184,6 +1458,7 @@ pub fn unbond(
                 user: meta.contract.address.clone(),
             });
         }
+        record_flows(deps.storage, &asset, &metrics)?;
         METRICS.append(deps.storage, env.block.time, &mut metrics)?;
         return Ok(Response::new().add_messages(messages).set_data(to_binary(
             &adapter::ExecuteAnswer::Unbond {
@@ -1199,7 +1474,7 @@ pub fn unbond(
         for (i, meta) in amounts.clone().iter().enumerate() {
             if !unbond_amounts[i].is_zero() {
                 messages.push(adapter::unbond_msg(
-                    &full_asset.contract.address.clone(),
+                    &asset,
                     unbond_amounts[i],
                     meta.contract.clone(),
                 )?);
@@ -1228,7 +1503,7 @@ pub fn unbond(
                 && unbond_from_portion + Uint128::new(1) <= meta.unbondable
             {
                 messages.push(adapter::unbond_msg(
-                    &full_asset.contract.address.clone(),
+                    &asset,
                     unbond_from_portion + Uint128::new(1),
                     meta.contract.clone(),
                 )?);
@@ -1242,7 +1517,7 @@ pub fn unbond(
                 });
             } else if !unbond_from_portion.is_zero() {
                 messages.push(adapter::unbond_msg(
-                    &full_asset.contract.address.clone(),
+                    &asset,
                     unbond_from_portion,
                     meta.contract.clone(),
                 )?);
@@ -1256,6 +1531,7 @@ pub fn unbond(
                 });
             }
         }
+        record_flows(deps.storage, &asset, &metrics)?;
         METRICS.append(deps.storage, env.block.time, &mut metrics)?;
         return Ok(Response::new().add_messages(messages).set_data(to_binary(
             &adapter::ExecuteAnswer::Unbond {
@@ -1270,7 +1546,7 @@ pub fn unbond(
             unbond_amounts.push(meta.unbondable);
             if !meta.unbondable.is_zero() {
                 messages.push(adapter::unbond_msg(
-                    &full_asset.contract.address,
+                    &asset,
                     meta.unbondable,
                     meta.contract.clone(),
                 )?);
@@ -1291,7 +1567,7 @@ pub fn unbond(
             for (i, meta) in amounts.clone().iter().enumerate() {
                 if !unbond_amounts[i].is_zero() {
                     messages.push(adapter::unbond_msg(
-                        &full_asset.contract.address,
+                        &asset,
                         unbond_amounts[i].clone(),
                         meta.contract.clone(),
                     )?);
@@ -1305,6 +1581,7 @@ pub fn unbond(
                     });
                 }
             }
+            record_flows(deps.storage, &asset, &metrics)?;
             METRICS.append(deps.storage, env.block.time, &mut metrics)?;
             return Ok(Response::new().add_messages(messages).set_data(to_binary(
                 &adapter::ExecuteAnswer::Unbond {
@@ -1336,7 +1613,7 @@ pub fn unbond(
                 }
                 if !unbond_amounts[i].is_zero() {
                     messages.push(adapter::unbond_msg(
-                        &full_asset.contract.address,
+                        &asset,
                         unbond_amounts[i],
                         meta.contract.clone(),
                     )?);
@@ -1350,6 +1627,7 @@ pub fn unbond(
                     });
                 }
             }
+            record_flows(deps.storage, &asset, &metrics)?;
             METRICS.append(deps.storage, env.block.time, &mut metrics)?;
             return Ok(Response::new().add_messages(messages).set_data(to_binary(
                 &adapter::ExecuteAnswer::Unbond {
@@ -1361,6 +1639,130 @@ pub fn unbond(
     }
 }
 
+pub fn cancel_unbond(
+    deps: DepsMut,
+    env: &Env,
+    info: MessageInfo,
+    asset: Addr,
+    amount: Uint128,
+) -> StdResult<Response> {
+    let config = CONFIG.load(deps.storage)?;
+    let holders = HOLDERS.load(deps.storage)?;
+
+    // if the sender isn't a holder, it should be an admin and default to the treasury
+    let unbonder = match holders.contains(&info.sender) {
+        true => info.sender,
+        false => {
+            validate_admin(
+                &deps.querier,
+                AdminPermissions::TreasuryManager,
+                &info.sender,
+                &config.admin_auth,
+            )?;
+            config.treasury
+        }
+    };
+
+    query::load_asset(deps.as_ref(), asset.as_str())?;
+
+    let mut holding = HOLDING.load(deps.storage, unbonder.clone())?;
+    if holding.status != Status::Active {
+        return Err(TreasuryManagerError::InactiveHolding.into());
+    }
+
+    // unbondings only reflect what hasn't already been sent out
+    let unbonding_i = match holding.unbondings.iter().position(|u| u.token == asset) {
+        Some(i) if holding.unbondings[i].amount >= amount => i,
+        _ => {
+            return Err(TreasuryManagerError::InsufficientUnbonding.into());
+        }
+    };
+    holding.unbondings[unbonding_i].amount -= amount;
+
+    if let Some(i) = holding.balances.iter().position(|b| b.token == asset) {
+        holding.balances[i].amount += amount;
+    } else {
+        holding.balances.push(Balance {
+            token: asset.clone(),
+            amount,
+        });
+    }
+    HOLDING.save(deps.storage, unbonder.clone(), &holding)?;
+
+    // adapters may still be unbonding this amount, which is now independent of any holder
+    UNBONDINGS.update(deps.storage, asset.clone(), |u| -> StdResult<_> {
+        Ok(u.unwrap_or_default() + amount)
+    })?;
+
+    METRICS.push(deps.storage, env.block.time, Metric {
+        action: Action::CancelUnbond,
+        context: Context::Unbond,
+        timestamp: env.block.time.seconds(),
+        token: asset,
+        amount,
+        user: unbonder,
+    })?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::CancelUnbond {
+            status: ResponseStatus::Success,
+            amount,
+        })?),
+    )
+}
+
+pub fn update_treasury(
+    deps: DepsMut,
+    _env: &Env,
+    info: MessageInfo,
+    treasury: Addr,
+) -> StdResult<Response> {
+    let mut config = CONFIG.load(deps.storage)?;
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::TreasuryManager,
+        &info.sender,
+        &config.admin_auth,
+    )?;
+
+    move_treasury(deps.storage, &mut config, treasury)?;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(
+        Response::new().set_data(to_binary(&ExecuteAnswer::UpdateTreasury {
+            status: ResponseStatus::Success,
+        })?),
+    )
+}
+
+/* Hands the treasury's holding and its place in HOLDERS to the new address,
+ * so principal and gains keep being credited to whoever is the treasury
+ */
+fn move_treasury(storage: &mut dyn Storage, config: &mut Config, treasury: Addr) -> StdResult<()> {
+    if treasury == config.treasury {
+        return Ok(());
+    }
+
+    let mut holders = HOLDERS.load(storage)?;
+    if holders.contains(&treasury) {
+        return Err(TreasuryManagerError::HolderExists.into());
+    }
+
+    let holding = HOLDING.load(storage, config.treasury.clone())?;
+    HOLDING.remove(storage, config.treasury.clone());
+    HOLDING.save(storage, treasury.clone(), &holding)?;
+
+    if let Some(i) = holders.iter().position(|h| *h == config.treasury) {
+        holders[i] = treasury.clone();
+    } else {
+        holders.push(treasury.clone());
+    }
+    HOLDERS.save(storage, &holders)?;
+
+    config.treasury = treasury;
+    Ok(())
+}
+
 pub fn add_holder(
     deps: DepsMut,
     env: &Env,
@@ -1377,7 +1779,7 @@ pub fn add_holder(
 
     let mut holders = HOLDERS.load(deps.storage)?;
     if holders.contains(&holder.clone()) {
-        return Err(StdError::generic_err("Holder already exists"));
+        return Err(TreasuryManagerError::HolderExists.into());
     }
     holders.push(holder.clone());
     HOLDERS.save(deps.storage, &holders)?;
@@ -1419,14 +1821,14 @@ pub fn remove_holder(
     )?;
 
     if holder == config.treasury {
-        return Err(StdError::generic_err("Cannot remove treasury as a holder"));
+        return Err(TreasuryManagerError::RemoveTreasury.into());
     }
 
     if let Some(mut holding) = HOLDING.may_load(deps.storage, holder.clone())? {
         holding.status = Status::Closed;
         HOLDING.save(deps.storage, holder.clone(), &holding)?;
     } else {
-        return Err(StdError::generic_err("Not an authorized holder"));
+        return Err(TreasuryManagerError::Unauthorized.into());
     }
 
     METRICS.push(deps.storage, env.block.time, Metric {
@@ -1444,3 +1846,54 @@ pub fn remove_holder(
         })?),
     )
 }
+
+/* Rolls the adapter's latest balance sample into previous, along with the flows recorded since,
+ * at most one sample is taken per block
+ */
+fn sample_adapter(samples: &mut Vec<AdapterSamples>, adapter: &Addr, env: &Env, balance: Uint128) {
+    let i = match samples.iter().position(|s| s.adapter == *adapter) {
+        Some(i) => i,
+        None => {
+            samples.push(AdapterSamples::new(adapter.clone()));
+            samples.len() - 1
+        }
+    };
+    let sample = &mut samples[i];
+
+    if let Some(latest) = &sample.latest {
+        if latest.height == env.block.height {
+            return;
+        }
+    }
+
+    sample.previous = sample.latest.take();
+    sample.deposited = sample.pending_deposited;
+    sample.withdrawn = sample.pending_withdrawn;
+    sample.pending_deposited = Uint128::zero();
+    sample.pending_withdrawn = Uint128::zero();
+    sample.latest = Some(BalanceSample {
+        height: env.block.height,
+        time: env.block.time.seconds(),
+        balance,
+    });
+}
+
+// Credits funds sent to or unbonded from adapters against their balance samples
+fn record_flows(storage: &mut dyn Storage, asset: &Addr, metrics: &Vec<Metric>) -> StdResult<()> {
+    let mut samples = match ADAPTER_SAMPLES.may_load(storage, asset.clone())? {
+        Some(s) => s,
+        None => return Ok(()),
+    };
+
+    for m in metrics {
+        if let Some(sample) = samples.iter_mut().find(|s| s.adapter == m.user) {
+            match m.action {
+                Action::SendFunds | Action::SendFundsFrom => sample.pending_deposited += m.amount,
+                Action::Unbond => sample.pending_withdrawn += m.amount,
+                _ => {}
+            }
+        }
+    }
+
+    ADAPTER_SAMPLES.save(storage, asset.clone(), &samples)
+}
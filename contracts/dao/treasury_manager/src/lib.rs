@@ -1,4 +1,5 @@
 pub mod contract;
 pub mod execute;
+pub mod migration;
 pub mod query;
 pub mod storage;
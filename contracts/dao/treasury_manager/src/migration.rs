@@ -0,0 +1,77 @@
+use crate::storage::*;
+use shade_protocol::{
+    c_std::{Addr, StdResult, Storage, Uint128},
+    cosmwasm_schema::cw_serde,
+    dao::treasury_manager::{AllocationMeta, AllocationType, Config},
+    secret_storage_plus::{Item, Map},
+    utils::asset::Contract,
+};
+
+/// Storage layout written by this code, bumped whenever a stored struct changes shape
+pub const SCHEMA_VERSION: u64 = 2;
+
+/// Config before profit_recipient & dust_threshold
+#[cw_serde]
+pub struct ConfigV1 {
+    pub admin_auth: Contract,
+    pub treasury: Addr,
+}
+
+/// AllocationMeta before last_rebalance
+#[cw_serde]
+pub struct AllocationMetaV1 {
+    pub nick: Option<String>,
+    pub contract: Contract,
+    pub alloc_type: AllocationType,
+    pub amount: Uint128,
+    pub tolerance: Uint128,
+}
+
+pub const CONFIG_V1: Item<ConfigV1> = Item::new("config");
+pub const ALLOCATIONS_V1: Map<Addr, Vec<AllocationMetaV1>> = Map::new("allocations");
+
+/// Rewrites storage up to SCHEMA_VERSION, contracts already on it are left untouched
+pub fn migrate_storage(storage: &mut dyn Storage) -> StdResult<()> {
+    // contracts instantiated before versioning was added are v1
+    let version = SCHEMA.may_load(storage)?.unwrap_or(1);
+
+    if version < 2 {
+        v1_to_v2(storage)?;
+    }
+
+    SCHEMA.save(storage, &SCHEMA_VERSION)
+}
+
+fn v1_to_v2(storage: &mut dyn Storage) -> StdResult<()> {
+    let config = CONFIG_V1.load(storage)?;
+    CONFIG.save(storage, &Config {
+        admin_auth: config.admin_auth,
+        treasury: config.treasury,
+        profit_recipient: None,
+        dust_threshold: Uint128::zero(),
+        max_messages: None,
+        query_auth: None,
+    })?;
+
+    for asset in ASSET_LIST.load(storage)? {
+        if let Some(allocations) = ALLOCATIONS_V1.may_load(storage, asset.clone())? {
+            ALLOCATIONS.save(
+                storage,
+                asset,
+                &allocations
+                    .into_iter()
+                    .map(|a| AllocationMeta {
+                        nick: a.nick,
+                        contract: a.contract,
+                        alloc_type: a.alloc_type,
+                        amount: a.amount,
+                        tolerance: a.tolerance,
+                        last_rebalance: None,
+                    })
+                    .collect(),
+            )?;
+        }
+    }
+
+    Ok(())
+}
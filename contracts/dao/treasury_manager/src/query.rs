@@ -1,9 +1,20 @@
-use crate::storage::*;
+use crate::{execute::desired_amount, storage::*};
 use shade_protocol::{
+    admin::helpers::{validate_admin, AdminPermissions},
     c_std::{Addr, Deps, Env, StdError, StdResult, Uint128},
-    dao::{adapter, manager, treasury_manager},
+    dao::{
+        adapter,
+        manager,
+        treasury_manager::{self, Auth, TreasuryManagerError},
+    },
+    query_auth::{self, helpers::authenticate_vk, QueryPermit},
     snip20::helpers::{allowance_query, balance_query},
-    utils::{cycle::parse_utc_datetime, storage::plus::period_storage::Period},
+    utils::{
+        cycle::parse_utc_datetime,
+        storage::plus::period_storage::Period,
+        version::VersionResponse,
+        Query,
+    },
 };
 
 pub fn config(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
@@ -12,6 +23,14 @@ pub fn config(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
     })
 }
 
+pub fn version(deps: Deps) -> StdResult<VersionResponse> {
+    Ok(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        // contracts instantiated before versioning was added are v1
+        schema: SCHEMA.may_load(deps.storage)?.unwrap_or(1),
+    })
+}
+
 pub fn metrics(
     deps: Deps,
     env: Env,
@@ -41,45 +60,116 @@ pub fn pending_allowance(
     env: Env,
     asset: Addr,
 ) -> StdResult<treasury_manager::QueryAnswer> {
-    let config = CONFIG.load(deps.storage)?;
-    let full_asset = match ASSETS.may_load(deps.storage, asset)? {
-        Some(a) => a,
-        None => {
-            return Err(StdError::generic_err("Not a registered asset"));
-        }
-    };
+    let full_asset = load_asset(deps, asset.as_str())?;
 
-    let allowance = allowance_query(
-        &deps.querier,
-        config.treasury,
-        env.contract.address,
-        VIEWING_KEY.load(deps.storage)?,
-        1,
-        &full_asset.contract.clone(),
-    )?
-    .allowance;
-
-    Ok(treasury_manager::QueryAnswer::PendingAllowance { amount: allowance })
+    Ok(treasury_manager::QueryAnswer::PendingAllowance {
+        amount: treasury_allowance(deps, env.contract.address, &full_asset)?,
+    })
+}
+
+// What the treasury has approved the manager to spend
+fn treasury_allowance(
+    deps: Deps,
+    address: Addr,
+    asset: &treasury_manager::Asset,
+) -> StdResult<Uint128> {
+    match asset {
+        treasury_manager::Asset::Snip20(contract) => Ok(allowance_query(
+            &deps.querier,
+            CONFIG.load(deps.storage)?.treasury,
+            address,
+            VIEWING_KEY.load(deps.storage)?,
+            1,
+            contract,
+        )?
+        .allowance),
+        // natives can't be spent from the treasury, holders deposit them instead
+        treasury_manager::Asset::Native(_) => Ok(Uint128::zero()),
+    }
+}
+
+pub fn last_allowance_used(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    load_asset(deps, asset.as_str())?;
+
+    Ok(treasury_manager::QueryAnswer::LastAllowanceUsed {
+        amount: LAST_ALLOWANCE_USED
+            .may_load(deps.storage, asset)?
+            .unwrap_or_default(),
+    })
+}
+
+pub fn excess_losses(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    if ALLOCATIONS.may_load(deps.storage, asset.clone())?.is_none() {
+        return Err(TreasuryManagerError::UnrecognizedAsset.into());
+    }
+
+    Ok(treasury_manager::QueryAnswer::ExcessLosses {
+        amount: EXCESS_LOSSES
+            .may_load(deps.storage, asset)?
+            .unwrap_or_default(),
+    })
+}
+
+// Native denoms aren't addresses, storage keyed by asset uses the denom itself for them
+pub fn asset_key(deps: Deps, asset: &str) -> StdResult<Addr> {
+    if NATIVE_ASSETS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .iter()
+        .any(|d| d == asset)
+    {
+        return Ok(Addr::unchecked(asset));
+    }
+
+    deps.api.addr_validate(asset)
+}
+
+// Resolves a registered asset, checking native denoms before snip20 addresses
+pub fn load_asset(deps: Deps, asset: &str) -> StdResult<treasury_manager::Asset> {
+    if NATIVE_ASSETS
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        .iter()
+        .any(|d| d == asset)
+    {
+        return Ok(treasury_manager::Asset::Native(asset.to_string()));
+    }
+
+    let asset = deps.api.addr_validate(asset)?;
+    match ASSETS.may_load(deps.storage, asset)? {
+        Some(full_asset) => Ok(treasury_manager::Asset::Snip20(full_asset.contract)),
+        None => Err(TreasuryManagerError::UnrecognizedAsset.into()),
+    }
 }
 
 pub fn reserves(
     deps: Deps,
     env: Env,
-    asset: Addr,
+    asset: treasury_manager::Asset,
     _holder: Addr,
 ) -> StdResult<manager::QueryAnswer> {
-    if let Some(full_asset) = ASSETS.may_load(deps.storage, asset)? {
-        let reserves = balance_query(
+    Ok(manager::QueryAnswer::Reserves {
+        amount: asset_balance(deps, env.contract.address, &asset)?,
+    })
+}
+
+// The manager's own balance of an asset, through the token for snip20s and the bank for natives
+pub fn asset_balance(
+    deps: Deps,
+    address: Addr,
+    asset: &treasury_manager::Asset,
+) -> StdResult<Uint128> {
+    match asset {
+        treasury_manager::Asset::Snip20(contract) => balance_query(
             &deps.querier,
-            env.contract.address,
+            address,
             VIEWING_KEY.load(deps.storage)?,
-            &full_asset.contract.clone(),
-        )?;
-
-        return Ok(manager::QueryAnswer::Reserves { amount: reserves });
+            contract,
+        ),
+        treasury_manager::Asset::Native(denom) => {
+            Ok(deps.querier.query_balance(address, denom)?.amount)
+        }
     }
-
-    Err(StdError::generic_err("Not a registered asset"))
 }
 
 pub fn assets(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
@@ -88,6 +178,21 @@ pub fn assets(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
     })
 }
 
+pub fn asset_contract(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    match ASSETS.may_load(deps.storage, asset)? {
+        Some(a) => Ok(treasury_manager::QueryAnswer::AssetContract {
+            contract: a.contract,
+        }),
+        None => Err(TreasuryManagerError::UnrecognizedAsset.into()),
+    }
+}
+
+pub fn native_assets(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
+    Ok(treasury_manager::QueryAnswer::NativeAssets {
+        denoms: NATIVE_ASSETS.may_load(deps.storage)?.unwrap_or_default(),
+    })
+}
+
 pub fn allocations(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
     Ok(treasury_manager::QueryAnswer::Allocations {
         allocations: match ALLOCATIONS.may_load(deps.storage, asset)? {
@@ -97,11 +202,111 @@ pub fn allocations(deps: Deps, asset: Addr) -> StdResult<treasury_manager::Query
     })
 }
 
-pub fn unbonding(deps: Deps, asset: Addr, holder: Addr) -> StdResult<manager::QueryAnswer> {
-    if ASSETS.may_load(deps.storage, asset.clone())?.is_none() {
-        return Err(StdError::generic_err("Not a registered asset"));
+pub fn idle_allocations(
+    deps: Deps,
+    asset: Addr,
+    since: u64,
+) -> StdResult<treasury_manager::QueryAnswer> {
+    Ok(treasury_manager::QueryAnswer::IdleAllocations {
+        allocations: ALLOCATIONS
+            .may_load(deps.storage, asset)?
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|a| match a.last_rebalance {
+                Some(height) => height < since,
+                None => true,
+            })
+            .collect(),
+    })
+}
+
+pub fn adapter_apr(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    load_asset(deps, asset.as_str())?;
+    let samples = ADAPTER_SAMPLES
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or_default();
+
+    Ok(treasury_manager::QueryAnswer::AdapterApr {
+        adapters: ALLOCATIONS
+            .load(deps.storage, asset)?
+            .into_iter()
+            .map(|a| treasury_manager::AllocationApr {
+                apr: samples
+                    .iter()
+                    .find(|s| s.adapter == a.contract.address)
+                    .and_then(|s| s.apr()),
+                nick: a.nick,
+                contract: a.contract,
+            })
+            .collect(),
+    })
+}
+
+pub fn funding_order(
+    deps: Deps,
+    env: Env,
+    asset: Addr,
+) -> StdResult<treasury_manager::QueryAnswer> {
+    let full_asset = load_asset(deps, asset.as_str())?;
+    // allocations are stored in the order update processes them, Amount before Portion
+    let allocations = ALLOCATIONS
+        .may_load(deps.storage, asset.clone())?
+        .unwrap_or_default();
+
+    let mut balances = vec![];
+    let mut adapter_total = Uint128::zero();
+    for alloc in &allocations {
+        let bal = adapter::balance_query(deps.querier, &asset, alloc.contract.clone())?;
+        adapter_total += bal;
+        balances.push(bal);
     }
 
+    let mut holder_unbonding = Uint128::zero();
+    for h in HOLDERS.load(deps.storage)? {
+        if let Some(u) = HOLDING
+            .load(deps.storage, h)?
+            .unbondings
+            .iter()
+            .find(|u| u.token == asset)
+        {
+            holder_unbonding += u.amount;
+        }
+    }
+
+    let allowance = treasury_allowance(deps, env.contract.address.clone(), &full_asset)?;
+    let balance = asset_balance(deps, env.contract.address, &full_asset)?;
+
+    // same capital update allocates against
+    let total = (adapter_total + balance).saturating_sub(holder_unbonding) + allowance;
+
+    let mut reserved_for_amount_adapters = Uint128::zero();
+    let mut funding = vec![];
+
+    for (alloc, balance) in allocations.into_iter().zip(balances) {
+        let desired = desired_amount(
+            &alloc.alloc_type,
+            alloc.amount,
+            total,
+            &mut reserved_for_amount_adapters,
+        );
+
+        funding.push(treasury_manager::AllocationFunding {
+            nick: alloc.nick,
+            contract: alloc.contract,
+            alloc_type: alloc.alloc_type,
+            balance,
+            desired,
+        });
+    }
+
+    Ok(treasury_manager::QueryAnswer::FundingOrder {
+        allocations: funding,
+    })
+}
+
+pub fn unbonding(deps: Deps, asset: Addr, holder: Addr) -> StdResult<manager::QueryAnswer> {
+    load_asset(deps, asset.as_str())?;
+
     let _config = CONFIG.load(deps.storage)?;
 
     match HOLDING.may_load(deps.storage, holder)? {
@@ -112,7 +317,7 @@ pub fn unbonding(deps: Deps, asset: Addr, holder: Addr) -> StdResult<manager::Qu
             },
         }),
         None => {
-            return Err(StdError::generic_err("Invalid holder"));
+            return Err(TreasuryManagerError::InvalidHolder.into());
         }
     }
 }
@@ -123,24 +328,14 @@ pub fn claimable(
     asset: Addr,
     holder: Addr,
 ) -> StdResult<manager::QueryAnswer> {
-    let full_asset = match ASSETS.may_load(deps.storage, asset.clone())? {
-        Some(a) => a,
-        None => {
-            return Err(StdError::generic_err("Not a registered asset"));
-        }
-    };
+    let full_asset = load_asset(deps, asset.as_str())?;
     let allocations = match ALLOCATIONS.may_load(deps.storage, asset.clone())? {
         Some(a) => a,
         None => vec![],
     };
     //TODO claiming needs ordered unbondings so other holders don't get bumped
 
-    let mut claimable = balance_query(
-        &deps.querier,
-        env.contract.address,
-        VIEWING_KEY.load(deps.storage)?,
-        &full_asset.contract.clone(),
-    )?;
+    let mut claimable = asset_balance(deps, env.contract.address, &full_asset)?;
 
     for alloc in allocations {
         claimable += adapter::claimable_query(deps.querier, &asset, alloc.contract.clone())?;
@@ -159,7 +354,7 @@ pub fn claimable(
                 Ok(manager::QueryAnswer::Claimable { amount: claimable })
             }
         }
-        None => Err(StdError::generic_err("Invalid holder")),
+        None => Err(TreasuryManagerError::InvalidHolder.into()),
     }
 }
 
@@ -169,12 +364,7 @@ pub fn unbondable(
     asset: Addr,
     holder: Addr,
 ) -> StdResult<manager::QueryAnswer> {
-    let full_asset = match ASSETS.may_load(deps.storage, asset.clone())? {
-        Some(a) => a,
-        None => {
-            return Err(StdError::generic_err("Not a registered asset"));
-        }
-    };
+    let full_asset = load_asset(deps, asset.as_str())?;
     let mut holder_balance = Uint128::zero();
 
     match HOLDING.may_load(deps.storage, holder.clone())? {
@@ -184,7 +374,7 @@ pub fn unbondable(
             }
         }
         None => {
-            return Err(StdError::generic_err("Invalid holder"));
+            return Err(TreasuryManagerError::InvalidHolder.into());
         }
     }
 
@@ -194,12 +384,7 @@ pub fn unbondable(
         });
     }
 
-    let mut unbondable = balance_query(
-        &deps.querier,
-        env.contract.address,
-        VIEWING_KEY.load(deps.storage)?,
-        &full_asset.contract.clone(),
-    )?;
+    let mut unbondable = asset_balance(deps, env.contract.address, &full_asset)?;
 
     let allocations = ALLOCATIONS
         .may_load(deps.storage, asset.clone())?
@@ -227,24 +412,18 @@ pub fn batch_balance(
     let holding = match HOLDING.may_load(deps.storage, holder.clone())? {
         Some(h) => h,
         None => {
-            return Err(StdError::generic_err("Invalid Holder"));
+            return Err(TreasuryManagerError::InvalidHolder.into());
         }
     };
 
     let mut balances = vec![];
 
     for asset in assets {
-        if let Some(asset) = ASSETS.may_load(deps.storage, asset)? {
-            balances.push(
-                match holding
-                    .balances
-                    .iter()
-                    .find(|b| b.token == asset.contract.address)
-                {
-                    Some(b) => b.amount,
-                    None => Uint128::zero(),
-                },
-            );
+        if load_asset(deps, asset.as_str()).is_ok() {
+            balances.push(match holding.balances.iter().find(|b| b.token == asset) {
+                Some(b) => b.amount,
+                None => Uint128::zero(),
+            });
         } else {
             balances.push(Uint128::zero());
         }
@@ -254,39 +433,149 @@ pub fn batch_balance(
 }
 
 pub fn balance(deps: Deps, asset: Addr, holder: Addr) -> StdResult<manager::QueryAnswer> {
-    if let Some(asset) = ASSETS.may_load(deps.storage, asset)? {
-        let holding = match HOLDING.may_load(deps.storage, holder.clone())? {
-            Some(h) => h,
-            None => {
-                return Err(StdError::generic_err("Invalid Holder"));
+    load_asset(deps, asset.as_str())?;
+    let holding = match HOLDING.may_load(deps.storage, holder.clone())? {
+        Some(h) => h,
+        None => {
+            return Err(TreasuryManagerError::InvalidHolder.into());
+        }
+    };
+    // TODO include unbonding so balance is more 'stable'
+    //      likely requires treasury rebalance changes
+    let balance = match holding.balances.iter().find(|b| b.token == asset) {
+        Some(b) => b.amount,
+        None => Uint128::zero(),
+    };
+
+    Ok(manager::QueryAnswer::Balance { amount: balance })
+}
+
+// Resolves the querying address from a viewing key or permit through query_auth
+pub fn authenticate(deps: Deps, auth: Auth) -> StdResult<Addr> {
+    let query_auth = CONFIG
+        .load(deps.storage)?
+        .query_auth
+        .ok_or(TreasuryManagerError::NoQueryAuth)?;
+
+    match auth {
+        Auth::ViewingKey { key, address } => {
+            let address = deps.api.addr_validate(&address)?;
+            if !authenticate_vk(address.clone(), key, &deps.querier, &query_auth)? {
+                return Err(TreasuryManagerError::InvalidViewingKey.into());
             }
-        };
-        // TODO include unbonding so balance is more 'stable'
-        //      likely requires treasury rebalance changes
-        let balance = match holding
-            .balances
-            .iter()
-            .find(|b| b.token == asset.contract.address)
+            Ok(address)
+        }
+        // only the signer is needed, the permit data is not used
+        Auth::Permit(permit) => match (query_auth::QueryMsg::ValidatePermit { permit }
+            .query(&deps.querier, &query_auth)?)
         {
-            Some(b) => b.amount,
-            None => Uint128::zero(),
-        };
+            query_auth::QueryAnswer::ValidatePermit { user, is_revoked } => {
+                if is_revoked {
+                    return Err(TreasuryManagerError::PermitRevoked.into());
+                }
+                Ok(user)
+            }
+            _ => Err(StdError::generic_err("Wrong query response")),
+        },
+    }
+}
 
-        Ok(manager::QueryAnswer::Balance { amount: balance })
-    } else {
-        Err(StdError::generic_err("Not a registered asset"))
+// Holder specific manager queries are open for the treasury, which makes them
+// cross-contract without auth, otherwise only the holder or an admin may see them
+pub fn authorize_holder(deps: Deps, holder: &Addr, user: Option<Addr>) -> StdResult<()> {
+    let config = CONFIG.load(deps.storage)?;
+    match user {
+        Some(user) if user == *holder => Ok(()),
+        Some(user) => validate_admin(
+            &deps.querier,
+            AdminPermissions::TreasuryManager,
+            &user,
+            &config.admin_auth,
+        ),
+        None if *holder == config.treasury => Ok(()),
+        None => Err(TreasuryManagerError::HolderAuthRequired.into()),
     }
 }
 
-pub fn holders(deps: Deps) -> StdResult<treasury_manager::QueryAnswer> {
+pub fn holders(deps: Deps, user: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::TreasuryManager,
+        &user,
+        &CONFIG.load(deps.storage)?.admin_auth,
+    )?;
+
     Ok(treasury_manager::QueryAnswer::Holders {
         holders: HOLDERS.load(deps.storage)?,
     })
 }
 
-pub fn holding(deps: Deps, holder: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+pub fn holding(deps: Deps, holder: Addr, user: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    // holders can see their own holding, admins can see any
+    if user != holder {
+        validate_admin(
+            &deps.querier,
+            AdminPermissions::TreasuryManager,
+            &user,
+            &CONFIG.load(deps.storage)?.admin_auth,
+        )?;
+    }
+
     match HOLDING.may_load(deps.storage, holder)? {
         Some(h) => Ok(treasury_manager::QueryAnswer::Holding { holding: h }),
-        None => Err(StdError::generic_err("Not a holder")),
+        None => Err(TreasuryManagerError::InvalidHolder.into()),
     }
 }
+
+pub fn holding_with_permit(
+    deps: Deps,
+    permit: QueryPermit,
+) -> StdResult<treasury_manager::QueryAnswer> {
+    let holder = authenticate(deps, Auth::Permit(permit))?;
+    holding(deps, holder.clone(), holder)
+}
+
+pub fn asset_holders(
+    deps: Deps,
+    asset: Addr,
+    user: Addr,
+) -> StdResult<treasury_manager::QueryAnswer> {
+    validate_admin(
+        &deps.querier,
+        AdminPermissions::TreasuryManager,
+        &user,
+        &CONFIG.load(deps.storage)?.admin_auth,
+    )?;
+
+    load_asset(deps, asset.as_str())?;
+
+    let mut holders = vec![];
+    for holder in HOLDERS.load(deps.storage)? {
+        let holding = HOLDING.load(deps.storage, holder.clone())?;
+        if let Some(b) = holding.balances.iter().find(|b| b.token == asset) {
+            if !b.amount.is_zero() {
+                holders.push(treasury_manager::HolderBalance {
+                    holder,
+                    amount: b.amount,
+                });
+            }
+        }
+    }
+
+    Ok(treasury_manager::QueryAnswer::AssetHolders { holders })
+}
+
+pub fn total_unbonding(deps: Deps, asset: Addr) -> StdResult<treasury_manager::QueryAnswer> {
+    load_asset(deps, asset.as_str())?;
+
+    // same sum `update` withholds from the adapters
+    let mut amount = Uint128::zero();
+    for holder in HOLDERS.load(deps.storage)? {
+        let holding = HOLDING.load(deps.storage, holder)?;
+        if let Some(u) = holding.unbondings.iter().find(|u| u.token == asset) {
+            amount += u.amount;
+        }
+    }
+
+    Ok(treasury_manager::QueryAnswer::TotalUnbonding { amount })
+}
@@ -1,21 +1,28 @@
 use shade_protocol::{
     c_std::{Addr, Uint128},
-    dao::treasury_manager::{AllocationMeta, Config, Holding, Metric},
+    dao::treasury_manager::{AdapterSamples, AllocationMeta, Config, Holding, Metric},
     secret_storage_plus::{Item, Map},
     snip20::helpers::Snip20Asset,
     utils::storage::plus::period_storage::PeriodStorage,
 };
 
 pub const CONFIG: Item<Config> = Item::new("config");
+pub const SCHEMA: Item<u64> = Item::new("schema_version");
 pub const VIEWING_KEY: Item<String> = Item::new("viewing_key");
 
 pub const ASSET_LIST: Item<Vec<Addr>> = Item::new("asset_list");
 pub const ASSETS: Map<Addr, Snip20Asset> = Map::new("assets");
+pub const NATIVE_ASSETS: Item<Vec<String>> = Item::new("native_assets");
 
 pub const ALLOCATIONS: Map<Addr, Vec<AllocationMeta>> = Map::new("allocations");
+pub const ADAPTER_SAMPLES: Map<Addr, Vec<AdapterSamples>> = Map::new("adapter_samples");
 pub const HOLDERS: Item<Vec<Addr>> = Item::new("holders");
 pub const HOLDING: Map<Addr, Holding> = Map::new("holding");
 pub const UNBONDINGS: Map<Addr, Uint128> = Map::new("unbondings");
+// Treasury allowance spent by the most recent update of each asset
+pub const LAST_ALLOWANCE_USED: Map<Addr, Uint128> = Map::new("last_allowance_used");
+// Losses realized in update that exceeded the treasury's recorded balance
+pub const EXCESS_LOSSES: Map<Addr, Uint128> = Map::new("excess_losses");
 
 pub const METRICS: PeriodStorage<Metric> =
     PeriodStorage::new("metrics-all", "metrics-recent", "metrics-timed");
@@ -0,0 +1,182 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, BlockInfo, ContractInfo, Timestamp, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, AllocationType, RawAllocation, SECONDS_PER_YEAR},
+        },
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn set_block(app: &mut App, height: u64, time: u64) {
+    app.set_block(BlockInfo {
+        height,
+        time: Timestamp::from_seconds(time),
+        chain_id: "chain_id".to_string(),
+    });
+}
+
+fn update(app: &mut App, manager: &ContractInfo, token: &ContractInfo) {
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(manager, app, Addr::unchecked("admin"), &[])
+    .unwrap();
+}
+
+fn deposit(app: &mut App, manager: &ContractInfo, token: &ContractInfo, amount: Uint128) {
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(token, app, Addr::unchecked("admin"), &[])
+    .unwrap();
+}
+
+// yield lands directly on the adapter, outside of the manager's flows
+fn reward(app: &mut App, adapter: &ContractInfo, token: &ContractInfo, amount: Uint128) {
+    snip20::ExecuteMsg::Transfer {
+        recipient: adapter.address.to_string().clone(),
+        amount,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(token, app, Addr::unchecked("rewarder"), &[])
+    .unwrap();
+}
+
+fn adapter_apr(app: &App, manager: &ContractInfo, token: &ContractInfo) -> Option<Uint128> {
+    match (treasury_manager::QueryMsg::AdapterApr {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::AdapterApr { adapters } => adapters[0].apr,
+        _ => panic!("Query failed"),
+    }
+}
+
+/* Adapter grows 10% a year, a deposit mid-way through must not
+ * show up as yield
+ */
+#[test]
+fn adapter_apr_excludes_flows() {
+    let mut app = App::default();
+    set_block(&mut app, 1, 0);
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![
+            snip20::InitialBalance {
+                address: admin.to_string().clone(),
+                amount: Uint128::new(2000),
+            },
+            snip20::InitialBalance {
+                address: "rewarder".to_string(),
+                amount: Uint128::new(310),
+            },
+        ]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: true,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Portion,
+            amount: Uint128::new(10u128.pow(18)),
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert_eq!(adapter_apr(&app, &manager, &token), None, "Unsampled");
+
+    // first sample, 1000 sent to the adapter
+    deposit(&mut app, &manager, &token, Uint128::new(1000));
+    set_block(&mut app, 10, 60);
+    update(&mut app, &manager, &token);
+    assert_eq!(adapter_apr(&app, &manager, &token), None, "Single sample");
+
+    // a year of 10% growth, another 1000 is sent after sampling
+    reward(&mut app, &adapter, &token, Uint128::new(100));
+    deposit(&mut app, &manager, &token, Uint128::new(1000));
+    set_block(&mut app, 20, 60 + SECONDS_PER_YEAR);
+    update(&mut app, &manager, &token);
+    assert_eq!(
+        adapter_apr(&app, &manager, &token),
+        Some(Uint128::new(10u128.pow(17))),
+        "First year"
+    );
+
+    // another year of 10% growth on 2100
+    reward(&mut app, &adapter, &token, Uint128::new(210));
+    set_block(&mut app, 30, 60 + 2 * SECONDS_PER_YEAR);
+    update(&mut app, &manager, &token);
+    assert_eq!(
+        adapter_apr(&app, &manager, &token),
+        Some(Uint128::new(10u128.pow(17))),
+        "Second year"
+    );
+}
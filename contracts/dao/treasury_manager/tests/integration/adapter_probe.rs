@@ -0,0 +1,120 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::treasury_manager::{self, AllocationType, RawAllocation},
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable},
+};
+
+fn token(app: &mut App, admin: &Addr, symbol: &str) -> ContractInfo {
+    snip20::InstantiateMsg {
+        name: symbol.to_lowercase(),
+        admin: Some(admin.to_string()),
+        symbol: symbol.into(),
+        decimals: 6,
+        initial_balances: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), app, admin.clone(), symbol, &[])
+    .unwrap()
+}
+
+fn allocation(contract: &ContractInfo) -> RawAllocation {
+    RawAllocation {
+        nick: None,
+        contract: RawContract::from(contract.clone()),
+        alloc_type: AllocationType::Portion,
+        amount: Uint128::new(10u128.pow(18)),
+        tolerance: Uint128::zero(),
+    }
+}
+
+#[test]
+fn allocate_rejects_non_adapters() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token_a = token(&mut app, &admin, "TKNA");
+    let token_b = token(&mut app, &admin, "TKNB");
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    // Adapter for a different token
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: true,
+        token: token_b.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token_a.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Not an adapter at all
+    assert!(
+        treasury_manager::ExecuteMsg::Allocate {
+            asset: token_a.address.to_string().clone(),
+            allocation: allocation(&token_b),
+        }
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .is_err()
+    );
+
+    // Adapter that doesn't support the asset
+    assert!(
+        treasury_manager::ExecuteMsg::Allocate {
+            asset: token_a.address.to_string().clone(),
+            allocation: allocation(&adapter),
+        }
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token_b.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token_b.address.to_string().clone(),
+        allocation: allocation(&adapter),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+}
@@ -0,0 +1,136 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, AllocationType, RawAllocation},
+        },
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn last_allowance_used(app: &App, manager: &ContractInfo, token: &ContractInfo) -> Uint128 {
+    match (treasury_manager::QueryMsg::LastAllowanceUsed {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::LastAllowanceUsed { amount } => amount,
+        _ => panic!("Query failed"),
+    }
+}
+
+/* An amount allocation is funded entirely from the treasury allowance,
+ * only the most recent update's usage is reported
+ */
+#[test]
+fn last_allowance_used() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let treasury = Addr::unchecked("treasury");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: treasury.to_string().clone(),
+            amount: Uint128::new(100),
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: treasury.to_string().clone(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: true,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Amount,
+            amount: Uint128::new(60),
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::IncreaseAllowance {
+        spender: manager.address.to_string().clone(),
+        amount: Uint128::new(100),
+        expiration: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, treasury.clone(), &[])
+    .unwrap();
+
+    // Nothing recorded before the first update
+    assert_eq!(last_allowance_used(&app, &manager, &token), Uint128::zero());
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+    assert_eq!(
+        last_allowance_used(&app, &manager, &token),
+        Uint128::new(60)
+    );
+
+    // Adapter is already funded, so this run spends nothing
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+    assert_eq!(last_allowance_used(&app, &manager, &token), Uint128::zero());
+}
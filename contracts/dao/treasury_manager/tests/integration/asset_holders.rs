@@ -0,0 +1,156 @@
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, Uint128},
+    contract_interfaces::{
+        dao::treasury_manager::{self, Auth, HolderBalance},
+        query_auth,
+        snip20,
+    },
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn vk(address: &str) -> Auth {
+    Auth::ViewingKey {
+        key: "password".to_string(),
+        address: address.to_string(),
+    }
+}
+
+/* Only holders with a nonzero balance in the asset are reported,
+ * the empty holder and the treasury are left out, and only admins can list them
+ */
+#[test]
+fn asset_holders_excludes_zero_balances() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let holders = vec![
+        (Addr::unchecked("holder_a"), Uint128::new(100)),
+        (Addr::unchecked("holder_b"), Uint128::new(50)),
+        (Addr::unchecked("holder_c"), Uint128::zero()),
+    ];
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+
+    for user in ["admin", "holder_a"] {
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: "password".to_string(),
+            padding: None,
+        }
+        .test_exec(&query_auth, &mut app, Addr::unchecked(user), &[])
+        .unwrap();
+    }
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(
+            holders
+                .iter()
+                .map(|(holder, amount)| snip20::InitialBalance {
+                    address: holder.to_string().clone(),
+                    amount: *amount,
+                })
+                .collect(),
+        ),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    for (holder, amount) in holders.iter() {
+        treasury_manager::ExecuteMsg::AddHolder {
+            holder: holder.to_string().clone(),
+        }
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .unwrap();
+
+        if amount.is_zero() {
+            continue;
+        }
+
+        snip20::ExecuteMsg::Send {
+            recipient: manager.address.to_string().clone(),
+            recipient_code_hash: None,
+            amount: *amount,
+            msg: None,
+            memo: None,
+            padding: None,
+        }
+        .test_exec(&token, &mut app, holder.clone(), &[])
+        .unwrap();
+    }
+
+    // a holder can't list the other holders
+    assert!(
+        treasury_manager::QueryMsg::AssetHolders {
+            asset: token.address.to_string().clone(),
+            auth: vk("holder_a"),
+        }
+        .test_query::<treasury_manager::QueryAnswer>(&manager, &app)
+        .is_err()
+    );
+
+    match (treasury_manager::QueryMsg::AssetHolders {
+        asset: token.address.to_string().clone(),
+        auth: vk("admin"),
+    }
+    .test_query(&manager, &app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::AssetHolders { holders } => {
+            assert_eq!(holders, vec![
+                HolderBalance {
+                    holder: Addr::unchecked("holder_a"),
+                    amount: Uint128::new(100),
+                },
+                HolderBalance {
+                    holder: Addr::unchecked("holder_b"),
+                    amount: Uint128::new(50),
+                },
+            ]);
+        }
+        _ => panic!("Query failed"),
+    };
+}
@@ -0,0 +1,204 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, AllocationType, Auth, Holding, RawAllocation},
+        },
+        query_auth,
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+/* Holder funds sit in a non-instant adapter so an unbond stays pending,
+ * cancelling it moves the full amount back to the holder's balance
+ */
+fn cancel_unbond(deposit: Uint128, unbond: Uint128) {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let holder = Addr::unchecked("holder");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        padding: None,
+    }
+    .test_exec(&query_auth, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: holder.to_string().clone(),
+            amount: deposit,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: false,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::AddHolder {
+        holder: holder.to_string().clone(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Portion,
+            amount: Uint128::new(10u128.pow(18)),
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, holder.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Unbond {
+        asset: token.address.to_string().clone(),
+        amount: unbond,
+    })
+    .test_exec(&manager, &mut app, holder.clone(), &[])
+    .unwrap();
+
+    let holding = |app: &App| -> Holding {
+        match (treasury_manager::QueryMsg::Holding {
+            holder: holder.to_string().clone(),
+            auth: Auth::ViewingKey {
+                key: "password".to_string(),
+                address: "admin".to_string(),
+            },
+        }
+        .test_query(&manager, app)
+        .unwrap())
+        {
+            treasury_manager::QueryAnswer::Holding { holding } => holding,
+            _ => panic!("Query failed"),
+        }
+    };
+
+    let pending = holding(&app);
+    assert_eq!(pending.balances[0].amount, deposit - unbond, "Pre-cancel balance");
+    assert_eq!(pending.unbondings[0].amount, unbond, "Pre-cancel unbonding");
+
+    // Can't cancel more than is unbonding
+    assert!(
+        treasury_manager::ExecuteMsg::CancelUnbond {
+            asset: token.address.to_string().clone(),
+            amount: unbond + Uint128::one(),
+        }
+        .test_exec(&manager, &mut app, holder.clone(), &[])
+        .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::CancelUnbond {
+        asset: token.address.to_string().clone(),
+        amount: unbond,
+    }
+    .test_exec(&manager, &mut app, holder.clone(), &[])
+    .unwrap();
+
+    let restored = holding(&app);
+    assert_eq!(restored.balances[0].amount, deposit, "Balance restored");
+    assert_eq!(restored.unbondings[0].amount, Uint128::zero(), "Unbonding cleared");
+}
+
+macro_rules! cancel_unbond_tests {
+    ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (deposit, unbond) = $value;
+                cancel_unbond(
+                    Uint128::new(deposit),
+                    Uint128::new(unbond),
+                );
+            }
+        )*
+    }
+}
+
+cancel_unbond_tests! {
+    cancel_unbond_0: (100, 100),
+    cancel_unbond_1: (1000, 250),
+}
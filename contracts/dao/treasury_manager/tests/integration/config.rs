@@ -70,6 +70,8 @@ pub fn update_config() {
             code_hash: "rando3".to_string(),
         }),
         Some(Addr::unchecked("rando").into()),
+        None,
+        None,
     )
     .unwrap();
     assert_eq!(
@@ -81,6 +83,10 @@ pub fn update_config() {
                 code_hash: "rando3".to_string(),
             },
             treasury: Addr::unchecked("rando"),
+            profit_recipient: None,
+            dust_threshold: Uint128::zero(),
+            max_messages: None,
+            query_auth: None,
         }
     );
 }
@@ -0,0 +1,209 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, AllocationType, Auth, RawAllocation},
+        },
+        query_auth,
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn holding_balance(app: &App, manager: &ContractInfo, holder: &Addr, token: &Addr) -> Uint128 {
+    match (treasury_manager::QueryMsg::Holding {
+        holder: holder.to_string(),
+        auth: Auth::ViewingKey {
+            key: "password".to_string(),
+            address: "admin".to_string(),
+        },
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::Holding { holding } => holding
+            .balances
+            .iter()
+            .find(|b| b.token == *token)
+            .map(|b| b.amount)
+            .unwrap_or(Uint128::zero()),
+        _ => panic!("Query failed"),
+    }
+}
+
+/* Unaccounted reserves too small to cross the adapter tolerance are left in the manager.
+ * Under the dust threshold they are swept to the treasury, otherwise realized as gains
+ * for the profit recipient.
+ */
+fn dust_sweep(dust: Uint128, dust_threshold: Uint128, swept: bool) {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let treasury = Addr::unchecked("treasury");
+    let rewards = Addr::unchecked("rewards");
+    let deposit = Uint128::new(100);
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        padding: None,
+    }
+    .test_exec(&query_auth, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string().clone(),
+            amount: deposit + dust,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: treasury.to_string().clone(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: true,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: Some(rewards.to_string().clone()),
+        dust_threshold: Some(dust_threshold),
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // 100% to the adapter with 10% tolerance
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Portion,
+            amount: Uint128::new(10u128.pow(18)),
+            tolerance: Uint128::new(10u128.pow(17)),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Unaccounted reserves, transfer skips the receive hook
+    snip20::ExecuteMsg::Transfer {
+        recipient: manager.address.to_string().clone(),
+        amount: dust,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let (treasury_expected, rewards_expected) = match swept {
+        true => (deposit + dust, Uint128::zero()),
+        false => (deposit, dust),
+    };
+
+    assert_eq!(
+        holding_balance(&app, &manager, &treasury, &token.address),
+        treasury_expected,
+        "Treasury holding"
+    );
+    assert_eq!(
+        holding_balance(&app, &manager, &rewards, &token.address),
+        rewards_expected,
+        "Profit recipient holding"
+    );
+}
+
+macro_rules! dust_sweep_tests {
+    ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (dust, dust_threshold, swept) = $value;
+                dust_sweep(
+                    Uint128::new(dust),
+                    Uint128::new(dust_threshold),
+                    swept,
+                );
+            }
+        )*
+    }
+}
+
+dust_sweep_tests! {
+    dust_sweep_below_threshold: (3, 5, true),
+    dust_sweep_above_threshold: (7, 5, false),
+    dust_sweep_disabled: (3, 0, false),
+}
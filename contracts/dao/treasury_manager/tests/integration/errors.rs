@@ -0,0 +1,116 @@
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, TreasuryManagerError},
+        },
+        snip20,
+    },
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable},
+};
+
+/* Holder deposits into the manager with no allocations,
+ * unbonding more than the deposit fails with InsufficientUnbondFunds
+ */
+fn unbond_error(deposit: Uint128, unbond: Uint128) {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let holder = Addr::unchecked("holder");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: holder.to_string().clone(),
+            amount: deposit,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::AddHolder {
+        holder: holder.to_string().clone(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, holder.clone(), &[])
+    .unwrap();
+
+    let err = manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Unbond {
+        asset: token.address.to_string().clone(),
+        amount: unbond,
+    })
+    .test_exec(&manager, &mut app, holder.clone(), &[])
+    .unwrap_err();
+
+    assert!(
+        err.root_cause()
+            .to_string()
+            .contains(&TreasuryManagerError::InsufficientUnbondFunds.to_string()),
+        "Unexpected error {}",
+        err.root_cause()
+    );
+}
+
+macro_rules! unbond_error_tests {
+    ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (deposit, unbond) = $value;
+                unbond_error(
+                    Uint128::new(deposit),
+                    Uint128::new(unbond),
+                );
+            }
+        )*
+    }
+}
+
+unbond_error_tests! {
+    unbond_error_0: (100, 101),
+    unbond_error_1: (1000, 5000),
+}
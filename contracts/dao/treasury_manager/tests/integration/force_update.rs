@@ -0,0 +1,170 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::{
+            adapter,
+            manager,
+            treasury_manager::{self, AllocationType, RawAllocation},
+        },
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn adapter_balance(app: &App, adapter: &ContractInfo, token: &ContractInfo) -> Uint128 {
+    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Balance {
+        asset: token.address.to_string().clone(),
+    })
+    .test_query(adapter, app)
+    .unwrap()
+    {
+        manager::QueryAnswer::Balance { amount } => amount,
+        _ => panic!("query failed"),
+    }
+}
+
+/* A deviation within tolerance is left alone by a normal update,
+ * but corrected when an admin forces the update
+ */
+#[test]
+fn force_update_ignores_tolerance() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let user = Addr::unchecked("user");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let viewing_key = "viewing_key".to_string();
+    let deposit = Uint128::new(100);
+    // 10%
+    let tolerance = Uint128::new(1 * 10u128.pow(17));
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string().clone(),
+            amount: deposit,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.clone().into(),
+        viewing_key: viewing_key.clone(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: true,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Amount,
+            amount: deposit,
+            tolerance,
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+    assert_eq!(adapter_balance(&app, &adapter, &token), deposit);
+
+    // 5% reduction is within the 10% tolerance
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Amount,
+            amount: Uint128::new(95),
+            tolerance,
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+        force: None,
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+    assert_eq!(adapter_balance(&app, &adapter, &token), deposit);
+
+    // Only admins can force
+    assert!(
+        treasury_manager::ExecuteMsg::Update {
+            asset: token.address.to_string().clone(),
+            force: Some(true),
+        }
+        .test_exec(&manager, &mut app, user.clone(), &[])
+        .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+        force: Some(true),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+    assert_eq!(adapter_balance(&app, &adapter, &token), Uint128::new(95));
+}
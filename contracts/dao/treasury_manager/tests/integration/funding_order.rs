@@ -0,0 +1,127 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, Uint128},
+    contract_interfaces::{
+        dao::treasury_manager::{self, AllocationType, RawAllocation},
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+/* Allocations registered out of order are reported Amount first,
+ * with portions splitting what's left after the amounts are reserved
+ */
+#[test]
+fn funding_order_mixed_types() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string().clone(),
+            amount: Uint128::new(1000),
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // (nick, type, amount) in registration order
+    let allocs = vec![
+        ("first_portion", AllocationType::Portion, 6 * 10u128.pow(17)),
+        ("amount", AllocationType::Amount, 200),
+        ("second_portion", AllocationType::Portion, 4 * 10u128.pow(17)),
+    ];
+
+    for (nick, alloc_type, amount) in allocs {
+        let adapter = mock_adapter::contract::Config {
+            owner: manager.address.clone(),
+            instant: true,
+            token: token.clone().into(),
+        }
+        .test_init(MockAdapter::default(), &mut app, admin.clone(), nick, &[])
+        .unwrap();
+
+        treasury_manager::ExecuteMsg::Allocate {
+            asset: token.address.to_string().clone(),
+            allocation: RawAllocation {
+                nick: Some(nick.to_string()),
+                contract: RawContract::from(adapter),
+                alloc_type,
+                amount: Uint128::new(amount),
+                tolerance: Uint128::zero(),
+            },
+        }
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .unwrap();
+    }
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: Uint128::new(1000),
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    match (treasury_manager::QueryMsg::FundingOrder {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(&manager, &app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::FundingOrder { allocations } => {
+            assert_eq!(
+                allocations
+                    .iter()
+                    .map(|a| (a.nick.clone().unwrap(), a.desired.u128()))
+                    .collect::<Vec<(String, u128)>>(),
+                vec![
+                    ("amount".to_string(), 200),
+                    ("first_portion".to_string(), 480),
+                    ("second_portion".to_string(), 320),
+                ],
+                "Funding order"
+            );
+        }
+        _ => panic!("Query failed"),
+    };
+}
@@ -7,10 +7,18 @@ use shade_protocol::c_std::{
 
 //use shade_protocol::secret_toolkit::snip20;
 
-use shade_multi_test::multi::{snip20::Snip20, treasury_manager::TreasuryManager};
+use shade_multi_test::multi::{
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
 use shade_protocol::{
-    dao::{manager, treasury_manager},
+    dao::{
+        manager,
+        treasury_manager::{self, Auth},
+    },
     multi_test::App,
+    query_auth,
     snip20,
     utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
 };
@@ -28,6 +36,19 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     let holder = Addr::unchecked("holder");
     let treasury = Addr::unchecked("treasury");
     let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+
+    // holder queries go through query_auth
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: viewing_key.clone(),
+        padding: None,
+    }
+    .test_exec(&query_auth, &mut app, holder.clone(), &[])
+    .unwrap();
+    let holder_auth = Auth::ViewingKey {
+        key: viewing_key.clone(),
+        address: holder.to_string(),
+    };
 
     let token = snip20::InstantiateMsg {
         name: "token".into(),
@@ -67,6 +88,17 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     .test_exec(&token, &mut app, holder.clone(), &[])
     .unwrap();
 
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
     // Register manager assets
     treasury_manager::ExecuteMsg::RegisterAsset {
         contract: token.clone().into(),
@@ -96,12 +128,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     // Balance Checks
 
     // manager reported holder balance
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Balance {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Balance {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Balance { amount } => {
             assert_eq!(amount, deposit, "Pre-unbond Manager Holder Balance");
@@ -128,12 +163,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     };
 
     // Manager reported total asset balance
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Balance {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Balance {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Balance { amount } => {
             assert_eq!(amount, deposit, "Pre-unbond Manager Total Balance");
@@ -162,12 +200,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     };
 
     // Unbondable
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Unbondable {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Unbondable {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Unbondable { amount } => {
             assert_eq!(amount, deposit, "Pre-unbond unbondable");
@@ -200,12 +241,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     .unwrap();
 
     // Unbondable
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Unbondable {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Unbondable {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Unbondable { amount } => {
             assert_eq!(
@@ -217,12 +261,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
         _ => panic!("Query failed"),
     };
 
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Unbondable {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Unbondable {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Unbondable { amount } => {
             assert_eq!(
@@ -235,12 +282,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     };
 
     // Unbonding
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Unbonding {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Unbonding {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Unbonding { amount } => {
             assert_eq!(amount, Uint128::zero(), "Post-unbond total unbonding");
@@ -248,12 +298,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
         _ => panic!("Query failed"),
     };
 
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Unbonding {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Unbonding {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Unbonding { amount } => {
             assert_eq!(amount, Uint128::zero(), "Post-unbond Holder Unbonding");
@@ -262,12 +315,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     };
 
     // Claimable (zero as its immediately claimed)
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Claimable {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Claimable {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Claimable { amount } => {
             assert_eq!(amount, Uint128::zero(), "Post-unbond total claimable");
@@ -275,12 +331,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
         _ => panic!("Query failed"),
     };
 
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Claimable {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Claimable {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Claimable { amount } => {
             assert_eq!(amount, Uint128::zero(), "Post-unbond holder claimable");
@@ -289,12 +348,15 @@ fn single_asset_holder_no_adapters(initial: Uint128, deposit: Uint128) {
     };
 
     // Manager reflects unbonded
-    match manager::QueryMsg::Manager(manager::SubQueryMsg::Balance {
-        asset: token.address.to_string().clone(),
-        holder: holder.to_string().clone(),
-    })
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Balance {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: holder_auth.clone(),
+    }
     .test_query(&manager, &app)
-    .unwrap()
+    .unwrap())
     {
         manager::QueryAnswer::Balance { amount } => {
             assert_eq!(amount.u128(), deposit.u128() - unbond_amount.u128());
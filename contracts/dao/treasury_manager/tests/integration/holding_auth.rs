@@ -0,0 +1,236 @@
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, Auth},
+        },
+        query_auth,
+        snip20,
+    },
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn vk(address: &str, key: &str) -> Auth {
+    Auth::ViewingKey {
+        key: key.to_string(),
+        address: address.to_string(),
+    }
+}
+
+fn holding(app: &App, manager: &ContractInfo, holder: &str, auth: Auth) -> bool {
+    treasury_manager::QueryMsg::Holding {
+        holder: holder.to_string(),
+        auth,
+    }
+    .test_query::<treasury_manager::QueryAnswer>(manager, app)
+    .is_ok()
+}
+
+fn holders(app: &App, manager: &ContractInfo, auth: Auth) -> bool {
+    treasury_manager::QueryMsg::Holders { auth }
+        .test_query::<treasury_manager::QueryAnswer>(manager, app)
+        .is_ok()
+}
+
+/* Holdings are only visible to their holder and admins,
+ * and only admins can list the holders
+ */
+#[test]
+fn holding_requires_auth() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+
+    for user in ["admin", "holder", "other"] {
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: "password".to_string(),
+            padding: None,
+        }
+        .test_exec(&query_auth, &mut app, Addr::unchecked(user), &[])
+        .unwrap();
+    }
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::AddHolder {
+        holder: "holder".to_string(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Auth can't be checked before query_auth is configured
+    assert!(!holding(&app, &manager, "holder", vk("holder", "password")));
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert!(!holding(&app, &manager, "holder", vk("holder", "wrong")));
+    assert!(!holding(&app, &manager, "holder", vk("other", "password")));
+    assert!(holding(&app, &manager, "holder", vk("holder", "password")));
+    assert!(holding(&app, &manager, "holder", vk("admin", "password")));
+
+    assert!(!holders(&app, &manager, vk("holder", "password")));
+    assert!(holders(&app, &manager, vk("admin", "password")));
+}
+
+fn balance(holder: &str, asset: &ContractInfo) -> manager::SubQueryMsg {
+    manager::SubQueryMsg::Balance {
+        asset: asset.address.to_string(),
+        holder: holder.to_string(),
+    }
+}
+
+/* Holder specific manager queries are open only for the treasury,
+ * anyone else has to authenticate as the holder or an admin
+ */
+#[test]
+fn manager_queries_require_auth() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+
+    for user in ["admin", "holder", "other"] {
+        query_auth::ExecuteMsg::SetViewingKey {
+            key: "password".to_string(),
+            padding: None,
+        }
+        .test_exec(&query_auth, &mut app, Addr::unchecked(user), &[])
+        .unwrap();
+    }
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::AddHolder {
+        holder: "holder".to_string(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // the treasury's own position stays open for the treasury contract
+    assert_eq!(
+        treasury_manager::QueryMsg::Manager(balance("treasury", &token))
+            .test_query::<manager::QueryAnswer>(&manager, &app)
+            .unwrap(),
+        manager::QueryAnswer::Balance {
+            amount: Uint128::zero()
+        }
+    );
+
+    for query in [
+        balance("holder", &token),
+        manager::SubQueryMsg::BatchBalance {
+            assets: vec![token.address.to_string()],
+            holder: "holder".to_string(),
+        },
+        manager::SubQueryMsg::Unbonding {
+            asset: token.address.to_string(),
+            holder: "holder".to_string(),
+        },
+    ] {
+        assert!(
+            treasury_manager::QueryMsg::Manager(query.clone())
+                .test_query::<manager::QueryAnswer>(&manager, &app)
+                .is_err()
+        );
+        assert!(
+            treasury_manager::QueryMsg::ManagerWithAuth {
+                query: query.clone(),
+                auth: vk("other", "password"),
+            }
+            .test_query::<manager::QueryAnswer>(&manager, &app)
+            .is_err()
+        );
+        assert!(
+            treasury_manager::QueryMsg::ManagerWithAuth {
+                query: query.clone(),
+                auth: vk("holder", "wrong"),
+            }
+            .test_query::<manager::QueryAnswer>(&manager, &app)
+            .is_err()
+        );
+        for user in ["holder", "admin"] {
+            assert!(
+                treasury_manager::QueryMsg::ManagerWithAuth {
+                    query: query.clone(),
+                    auth: vk(user, "password"),
+                }
+                .test_query::<manager::QueryAnswer>(&manager, &app)
+                .is_ok()
+            );
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, Binary, Uint128},
+    contract_interfaces::{
+        dao::treasury_manager::{self, Balance, Status},
+        query_auth::{self, PermitData, QueryPermit},
+        snip20,
+    },
+    multi_test::App,
+    query_authentication::transaction::{PermitSignature, PubKey},
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn permit(signature: &str) -> QueryPermit {
+    QueryPermit {
+        params: PermitData {
+            key: "key".to_string(),
+            data: Binary::from_base64("c29tZSBzdHJpbmc=").unwrap(),
+        },
+        signature: PermitSignature {
+            pub_key: PubKey::new(
+                Binary::from_base64("A9NjbriiP7OXCpoTov9ox/35+h5k0y1K0qCY/B09YzAP").unwrap(),
+            ),
+            signature: Binary::from_base64(signature).unwrap(),
+        },
+        account_number: None,
+        chain_id: Some(String::from("chain")),
+        sequence: None,
+        memo: None,
+    }
+}
+
+const VALID_SIGNATURE: &str =
+    "XRzykrPmMs0ZhksNXX+eU0TM21fYBZXZogr5wYZGGy11t2ntfySuQNQJEw6D4QKvPsiU9gYMsQ259dOzMZNAEg==";
+const INVALID_SIGNATURE: &str =
+    "YRzykrPmMs0ZhksNXX+eU0TM21fYBZXZogr5wYZGGy11t2ntfySuQNQJEw6D4QKvPsiU9gYMsQ259dOzMZNAEg==";
+
+/* A holder's permit returns only their holding,
+ * a permit with a bad signature is rejected
+ */
+#[test]
+fn holding_with_permit() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let auth = init_query_auth(&mut app, &admin, &admin_auth);
+
+    // The permit signer becomes the holder
+    let holder = match (query_auth::QueryMsg::ValidatePermit {
+        permit: permit(VALID_SIGNATURE),
+    }
+    .test_query(&auth, &app)
+    .unwrap())
+    {
+        query_auth::QueryAnswer::ValidatePermit { user, .. } => user,
+        _ => panic!("Query failed"),
+    };
+    let deposit = Uint128::new(100);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: holder.to_string().clone(),
+            amount: deposit,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    // Without query auth configured permits can't be checked
+    assert!(
+        treasury_manager::QueryMsg::HoldingWithPermit {
+            permit: permit(VALID_SIGNATURE),
+        }
+        .test_query::<treasury_manager::QueryAnswer>(&manager, &app)
+        .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(auth.clone().into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::AddHolder {
+        holder: holder.to_string().clone(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, holder.clone(), &[])
+    .unwrap();
+
+    match (treasury_manager::QueryMsg::HoldingWithPermit {
+        permit: permit(VALID_SIGNATURE),
+    }
+    .test_query(&manager, &app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::Holding { holding } => {
+            assert_eq!(holding.balances, vec![Balance {
+                token: token.address.clone(),
+                amount: deposit,
+            }]);
+            assert_eq!(holding.status, Status::Active);
+        }
+        _ => panic!("Query failed"),
+    };
+
+    assert!(
+        treasury_manager::QueryMsg::HoldingWithPermit {
+            permit: permit(INVALID_SIGNATURE),
+        }
+        .test_query::<treasury_manager::QueryAnswer>(&manager, &app)
+        .is_err()
+    );
+}
@@ -0,0 +1,182 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, BlockInfo, ContractInfo, Timestamp, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, AllocationMeta, AllocationType, RawAllocation},
+        },
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn set_height(app: &mut App, height: u64) {
+    app.set_block(BlockInfo {
+        height,
+        time: Timestamp::from_seconds(height * 6),
+        chain_id: "chain_id".to_string(),
+    });
+}
+
+fn update(app: &mut App, manager: &ContractInfo, token: &ContractInfo) {
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(manager, app, Addr::unchecked("admin"), &[])
+    .unwrap();
+}
+
+fn deposit(app: &mut App, manager: &ContractInfo, token: &ContractInfo, amount: Uint128) {
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(token, app, Addr::unchecked("admin"), &[])
+    .unwrap();
+}
+
+fn allocations(app: &App, manager: &ContractInfo, token: &ContractInfo) -> Vec<AllocationMeta> {
+    match (treasury_manager::QueryMsg::Allocations {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::Allocations { allocations } => allocations,
+        _ => panic!("Query failed"),
+    }
+}
+
+fn idle_allocations(
+    app: &App,
+    manager: &ContractInfo,
+    token: &ContractInfo,
+    since: u64,
+) -> Vec<AllocationMeta> {
+    match (treasury_manager::QueryMsg::IdleAllocations {
+        asset: token.address.to_string().clone(),
+        since,
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::IdleAllocations { allocations } => allocations,
+        _ => panic!("Query failed"),
+    }
+}
+
+#[test]
+fn last_rebalance_tracks_actions() {
+    let mut app = App::default();
+    set_height(&mut app, 1);
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string().clone(),
+            amount: Uint128::new(1000),
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: true,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Portion,
+            amount: Uint128::new(10u128.pow(18)),
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert_eq!(allocations(&app, &manager, &token)[0].last_rebalance, None);
+    assert_eq!(idle_allocations(&app, &manager, &token, 0).len(), 1);
+
+    // Funds sent to the adapter
+    deposit(&mut app, &manager, &token, Uint128::new(100));
+    set_height(&mut app, 10);
+    update(&mut app, &manager, &token);
+    assert_eq!(
+        allocations(&app, &manager, &token)[0].last_rebalance,
+        Some(10)
+    );
+
+    // Nothing to do, height is left alone
+    set_height(&mut app, 20);
+    update(&mut app, &manager, &token);
+    assert_eq!(
+        allocations(&app, &manager, &token)[0].last_rebalance,
+        Some(10)
+    );
+    assert_eq!(idle_allocations(&app, &manager, &token, 10).len(), 0);
+    assert_eq!(idle_allocations(&app, &manager, &token, 15).len(), 1);
+
+    // New funds need sending again
+    deposit(&mut app, &manager, &token, Uint128::new(100));
+    set_height(&mut app, 30);
+    update(&mut app, &manager, &token);
+    assert_eq!(
+        allocations(&app, &manager, &token)[0].last_rebalance,
+        Some(30)
+    );
+    assert_eq!(idle_allocations(&app, &manager, &token, 15).len(), 0);
+}
@@ -0,0 +1,247 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, AllocationType, Auth, RawAllocation},
+        },
+        query_auth,
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn excess_losses(app: &App, manager: &ContractInfo, token: &ContractInfo) -> Uint128 {
+    match (treasury_manager::QueryMsg::ExcessLosses {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::ExcessLosses { amount } => amount,
+        _ => panic!("Query failed"),
+    }
+}
+
+/* Holder funds are fully allocated to a single adapter, which then loses more than the
+ * treasury has recorded as its own balance. Update must still complete, and the unabsorbed
+ * losses add up across updates without counting the same shortfall twice.
+ */
+fn loss_exceeds_treasury_balance(deposit: Uint128, loss: Uint128) {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let holder = Addr::unchecked("holder");
+    let treasury = Addr::unchecked("treasury");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        padding: None,
+    }
+    .test_exec(&query_auth, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let viewing_key = "viewing_key".to_string();
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: holder.to_string().clone(),
+            amount: deposit,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: viewing_key.clone(),
+        treasury: treasury.to_string().clone(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: true,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::AddHolder {
+        holder: holder.to_string().clone(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // 100% to the adapter
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Portion,
+            amount: Uint128::new(10u128.pow(18)),
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, holder.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Adapter loses funds, treasury holds no principal to absorb it
+    mock_adapter::contract::ExecuteMsg::GiveMeMoney { amount: loss }
+        .test_exec(&adapter, &mut app, admin.clone(), &[])
+        .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    match (treasury_manager::QueryMsg::Holding {
+        holder: treasury.to_string().clone(),
+        auth: Auth::ViewingKey {
+            key: "password".to_string(),
+            address: "admin".to_string(),
+        },
+    }
+    .test_query(&manager, &app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::Holding { holding } => {
+            let balance = holding
+                .balances
+                .iter()
+                .find(|b| b.token == token.address)
+                .map(|b| b.amount)
+                .unwrap_or(Uint128::zero());
+            assert_eq!(balance, Uint128::zero(), "Treasury balance clamped");
+        }
+        _ => panic!("Query failed"),
+    };
+
+    match (treasury_manager::QueryMsg::ManagerWithAuth {
+        query: manager::SubQueryMsg::Balance {
+            asset: token.address.to_string().clone(),
+            holder: holder.to_string().clone(),
+        },
+        auth: Auth::ViewingKey {
+            key: "password".to_string(),
+            address: admin.to_string(),
+        },
+    }
+    .test_query(&manager, &app)
+    .unwrap())
+    {
+        manager::QueryAnswer::Balance { amount } => {
+            assert_eq!(amount, deposit, "Holder balance untouched");
+        }
+        _ => panic!("Query failed"),
+    };
+    assert_eq!(excess_losses(&app, &manager, &token), loss);
+
+    // Nothing new was lost
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+    assert_eq!(excess_losses(&app, &manager, &token), loss);
+
+    mock_adapter::contract::ExecuteMsg::GiveMeMoney {
+        amount: Uint128::one(),
+    }
+    .test_exec(&adapter, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+    assert_eq!(excess_losses(&app, &manager, &token), loss + Uint128::one());
+}
+
+macro_rules! loss_exceeds_treasury_balance_tests {
+    ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (deposit, loss) = $value;
+                loss_exceeds_treasury_balance(
+                    Uint128::new(deposit),
+                    Uint128::new(loss),
+                );
+            }
+        )*
+    }
+}
+
+loss_exceeds_treasury_balance_tests! {
+    loss_exceeds_treasury_balance_0: (100, 50),
+    loss_exceeds_treasury_balance_1: (1000, 999),
+}
@@ -0,0 +1,173 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::{
+            adapter,
+            manager,
+            treasury_manager::{self, AllocationType, RawAllocation},
+        },
+        snip20,
+    },
+    multi_test::{App, AppResponse},
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn adapter_balance(app: &App, adapter: &ContractInfo, token: &ContractInfo) -> Uint128 {
+    match adapter::QueryMsg::Adapter(adapter::SubQueryMsg::Balance {
+        asset: token.address.to_string().clone(),
+    })
+    .test_query(adapter, app)
+    .unwrap()
+    {
+        manager::QueryAnswer::Balance { amount } => amount,
+        _ => panic!("query failed"),
+    }
+}
+
+fn truncated(resp: &AppResponse) -> bool {
+    resp.events.iter().any(|e| {
+        e.attributes
+            .iter()
+            .any(|a| a.key == "truncated" && a.value == "true")
+    })
+}
+
+/* With a cap of 2 messages only the amount allocations are funded on the first update,
+ * the portion allocation is left for the next call
+ */
+#[test]
+fn update_max_messages() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let deposit = Uint128::new(100);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string().clone(),
+            amount: deposit,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: Some(2),
+        query_auth: None,
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Portion is allocated first but amount allocations are served before it
+    let allocations = vec![
+        (
+            "portion",
+            AllocationType::Portion,
+            Uint128::new(10u128.pow(18)),
+        ),
+        ("amount_a", AllocationType::Amount, Uint128::new(30)),
+        ("amount_b", AllocationType::Amount, Uint128::new(30)),
+    ];
+    let mut adapters = vec![];
+    for (nick, alloc_type, amount) in allocations {
+        let adapter = mock_adapter::contract::Config {
+            owner: manager.address.clone(),
+            instant: true,
+            token: token.clone().into(),
+        }
+        .test_init(MockAdapter::default(), &mut app, admin.clone(), nick, &[])
+        .unwrap();
+
+        treasury_manager::ExecuteMsg::Allocate {
+            asset: token.address.to_string().clone(),
+            allocation: RawAllocation {
+                nick: Some(nick.to_string()),
+                contract: RawContract::from(adapter.clone()),
+                alloc_type,
+                amount,
+                tolerance: Uint128::zero(),
+            },
+        }
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .unwrap();
+
+        adapters.push(adapter);
+    }
+
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let update = |app: &mut App| -> AppResponse {
+        manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+            asset: token.address.to_string().clone(),
+        })
+        .test_exec(&manager, app, admin.clone(), &[])
+        .unwrap()
+    };
+
+    let resp = update(&mut app);
+    assert!(truncated(&resp), "First update truncated");
+    assert_eq!(adapter_balance(&app, &adapters[0], &token), Uint128::zero());
+    assert_eq!(
+        adapter_balance(&app, &adapters[1], &token),
+        Uint128::new(30)
+    );
+    assert_eq!(
+        adapter_balance(&app, &adapters[2], &token),
+        Uint128::new(30)
+    );
+
+    let resp = update(&mut app);
+    assert!(!truncated(&resp), "Second update completes");
+    assert_eq!(
+        adapter_balance(&app, &adapters[0], &token),
+        Uint128::new(40)
+    );
+}
@@ -0,0 +1,91 @@
+use shade_protocol::{
+    c_std::{
+        testing::{mock_dependencies, mock_env},
+        Addr,
+        Uint128,
+    },
+    contract_interfaces::dao::treasury_manager::{
+        AllocationMeta,
+        AllocationType,
+        Config,
+        MigrateMsg,
+    },
+    utils::asset::Contract,
+};
+use treasury_manager::{
+    contract::migrate,
+    migration::{AllocationMetaV1, ConfigV1, ALLOCATIONS_V1, CONFIG_V1, SCHEMA_VERSION},
+    storage::{ALLOCATIONS, ASSET_LIST, CONFIG, SCHEMA},
+};
+
+/* Storage written by a pre-versioning contract is rewritten into the current layout,
+ * migrating a second time leaves it untouched
+ */
+#[test]
+fn migrate_v1() {
+    let mut deps = mock_dependencies();
+
+    let admin_auth = Contract {
+        address: Addr::unchecked("admin_auth"),
+        code_hash: "admin_auth_hash".to_string(),
+    };
+    let adapter = Contract {
+        address: Addr::unchecked("adapter"),
+        code_hash: "adapter_hash".to_string(),
+    };
+    let asset = Addr::unchecked("token");
+
+    CONFIG_V1
+        .save(deps.as_mut().storage, &ConfigV1 {
+            admin_auth: admin_auth.clone(),
+            treasury: Addr::unchecked("treasury"),
+        })
+        .unwrap();
+    ASSET_LIST
+        .save(deps.as_mut().storage, &vec![asset.clone()])
+        .unwrap();
+    ALLOCATIONS_V1
+        .save(deps.as_mut().storage, asset.clone(), &vec![
+            AllocationMetaV1 {
+                nick: Some("Adapter".to_string()),
+                contract: adapter.clone(),
+                alloc_type: AllocationType::Portion,
+                amount: Uint128::new(10u128.pow(18)),
+                tolerance: Uint128::new(10u128.pow(16)),
+            },
+        ])
+        .unwrap();
+
+    // current structs can't be read from the v1 layout
+    assert!(CONFIG.load(deps.as_ref().storage).is_err());
+
+    let expected_config = Config {
+        admin_auth,
+        treasury: Addr::unchecked("treasury"),
+        profit_recipient: None,
+        dust_threshold: Uint128::zero(),
+        max_messages: None,
+        query_auth: None,
+    };
+    let expected_allocations = vec![AllocationMeta {
+        nick: Some("Adapter".to_string()),
+        contract: adapter,
+        alloc_type: AllocationType::Portion,
+        amount: Uint128::new(10u128.pow(18)),
+        tolerance: Uint128::new(10u128.pow(16)),
+        last_rebalance: None,
+    }];
+
+    for _ in 0..2 {
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        assert_eq!(CONFIG.load(deps.as_ref().storage).unwrap(), expected_config);
+        assert_eq!(
+            ALLOCATIONS
+                .load(deps.as_ref().storage, asset.clone())
+                .unwrap(),
+            expected_allocations
+        );
+        assert_eq!(SCHEMA.load(deps.as_ref().storage).unwrap(), SCHEMA_VERSION);
+    }
+}
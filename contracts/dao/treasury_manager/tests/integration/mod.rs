@@ -1,9 +1,32 @@
+pub mod adapter_apr;
+pub mod adapter_probe;
+pub mod allowance_used;
+pub mod asset_holders;
 pub mod batch;
+pub mod cancel_unbond;
 pub mod config;
+pub mod dust;
+pub mod errors;
 pub mod execute_error;
+pub mod force_update;
+pub mod funding_order;
 pub mod holder_integration;
+pub mod holding_auth;
+pub mod holding_permit;
+pub mod idle;
+pub mod losses;
+pub mod max_messages;
+pub mod migrate;
 pub mod multiple_holders;
+pub mod native;
+pub mod profit_recipient;
 pub mod query;
+pub mod receive;
+pub mod refresh_asset;
 pub mod scrt_staking_integration;
 pub mod tm_unbond;
 pub mod tolerance;
+pub mod total_unbonding;
+pub mod update_empty;
+pub mod update_treasury;
+pub mod version;
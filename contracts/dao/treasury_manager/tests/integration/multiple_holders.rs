@@ -125,6 +125,7 @@ pub fn multiple_holders(
         Uint128::new(500),
         treasury_manager::holding_query(
             &app,
+            "admin",
             &contracts,
             SupportedContracts::TreasuryManager(0),
             HOLDER.to_string(),
@@ -280,6 +281,7 @@ pub fn multiple_holders(
     update_dao(&mut app, "admin", &contracts, "SSCRT", num_managers).unwrap();
     match treasury_manager::holding_query(
         &app,
+        "admin",
         &contracts,
         SupportedContracts::TreasuryManager(0),
         HOLDER.to_string(),
@@ -0,0 +1,360 @@
+use shade_multi_test::multi::{admin::init_admin_auth, treasury_manager::TreasuryManager};
+use shade_protocol::{
+    c_std::{coins, Addr, Coin, Uint128},
+    contract_interfaces::dao::{
+        manager,
+        treasury_manager::{self, AllocationType, RawAllocation},
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+// Adapter holding a native denom in its bank balance, unbonds are paid out immediately
+mod native_adapter {
+    use shade_protocol::{
+        c_std::{
+            coins,
+            to_binary,
+            Addr,
+            BankMsg,
+            Binary,
+            Deps,
+            DepsMut,
+            Empty,
+            Env,
+            MessageInfo,
+            Response,
+            StdError,
+            StdResult,
+        },
+        contract_interfaces::dao::adapter,
+        cosmwasm_schema::cw_serde,
+        multi_test::{Contract, ContractWrapper},
+        utils::{
+            generic_response::ResponseStatus,
+            storage::plus::Item,
+            InstantiateCallback,
+            MultiTestable,
+        },
+    };
+
+    #[cw_serde]
+    pub struct InstantiateMsg {
+        pub owner: Addr,
+        pub denom: String,
+    }
+
+    impl InstantiateCallback for InstantiateMsg {
+        const BLOCK_SIZE: usize = 256;
+    }
+
+    const CONFIG: Item<InstantiateMsg> = Item::new("config");
+
+    fn instantiate(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: InstantiateMsg,
+    ) -> StdResult<Response> {
+        CONFIG.save(deps.storage, &msg)?;
+        Ok(Response::new())
+    }
+
+    fn execute(
+        deps: DepsMut,
+        _env: Env,
+        _info: MessageInfo,
+        msg: adapter::ExecuteMsg,
+    ) -> StdResult<Response> {
+        let config = CONFIG.load(deps.storage)?;
+        match msg {
+            adapter::ExecuteMsg::Adapter(adapter::SubExecuteMsg::Unbond { asset, amount }) => {
+                if asset != config.denom {
+                    return Err(StdError::generic_err("Unrecognized Asset"));
+                }
+                Ok(Response::new()
+                    .add_message(BankMsg::Send {
+                        to_address: config.owner.to_string(),
+                        amount: coins(amount.u128(), config.denom),
+                    })
+                    .set_data(to_binary(&adapter::ExecuteAnswer::Unbond {
+                        status: ResponseStatus::Success,
+                        amount,
+                    })?))
+            }
+            _ => Ok(Response::new()),
+        }
+    }
+
+    fn query(deps: Deps, env: Env, msg: adapter::QueryMsg) -> StdResult<Binary> {
+        let config = CONFIG.load(deps.storage)?;
+        let adapter::QueryMsg::Adapter(msg) = msg;
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address, config.denom)?
+            .amount;
+
+        to_binary(&match msg {
+            adapter::SubQueryMsg::Balance { .. } => {
+                adapter::QueryAnswer::Balance { amount: balance }
+            }
+            adapter::SubQueryMsg::Unbonding { .. } => adapter::QueryAnswer::Unbonding {
+                amount: Default::default(),
+            },
+            adapter::SubQueryMsg::Claimable { .. } => adapter::QueryAnswer::Claimable {
+                amount: Default::default(),
+            },
+            adapter::SubQueryMsg::Unbondable { .. } => {
+                adapter::QueryAnswer::Unbondable { amount: balance }
+            }
+            adapter::SubQueryMsg::Reserves { .. } => {
+                adapter::QueryAnswer::Reserves { amount: balance }
+            }
+        })
+    }
+
+    pub struct NativeAdapter;
+
+    impl MultiTestable for NativeAdapter {
+        fn contract(&self) -> Box<dyn Contract<Empty>> {
+            Box::new(ContractWrapper::new_with_empty(execute, instantiate, query))
+        }
+
+        fn default() -> Self {
+            NativeAdapter
+        }
+    }
+}
+
+/* A registered native denom reports the manager's bank balance as reserves
+ */
+fn native_reserves(balance: Uint128) {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &manager.address, vec![Coin {
+                denom: "uscrt".to_string(),
+                amount: balance,
+            }])
+            .unwrap();
+    });
+
+    // Unregistered denoms are rejected
+    assert!(
+        manager::QueryMsg::Manager(manager::SubQueryMsg::Reserves {
+            asset: "uscrt".to_string(),
+            holder: "treasury".to_string(),
+        })
+        .test_query::<manager::QueryAnswer>(&manager, &app)
+        .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::RegisterNativeAsset {
+        denom: "uscrt".to_string(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Duplicate registration fails
+    assert!(
+        treasury_manager::ExecuteMsg::RegisterNativeAsset {
+            denom: "uscrt".to_string(),
+        }
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .is_err()
+    );
+
+    match (treasury_manager::QueryMsg::NativeAssets {}
+        .test_query(&manager, &app)
+        .unwrap())
+    {
+        treasury_manager::QueryAnswer::NativeAssets { denoms } => {
+            assert_eq!(denoms, vec!["uscrt".to_string()]);
+        }
+        _ => panic!("Query failed"),
+    };
+
+    match manager::QueryMsg::Manager(manager::SubQueryMsg::Reserves {
+        asset: "uscrt".to_string(),
+        holder: "treasury".to_string(),
+    })
+    .test_query(&manager, &app)
+    .unwrap()
+    {
+        manager::QueryAnswer::Reserves { amount } => {
+            assert_eq!(amount, balance, "Native reserves");
+        }
+        _ => panic!("Query failed"),
+    };
+}
+
+macro_rules! native_reserves_tests {
+    ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                native_reserves(Uint128::new($value));
+            }
+        )*
+    }
+}
+
+native_reserves_tests! {
+    native_reserves_0: 1,
+    native_reserves_1: 1000,
+}
+
+/* Deposited native funds are sent to the adapter on update,
+ * unbonding pays out reserves first and the rest once the adapter returns it,
+ * the holder queries resolve the denom like the execute paths do
+ */
+#[test]
+fn native_allocation_unbond() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let treasury = Addr::unchecked("treasury");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    app.init_modules(|router, _, storage| {
+        router
+            .bank
+            .init_balance(storage, &treasury, coins(1000, "uscrt"))
+            .unwrap();
+    });
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: treasury.to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let adapter = native_adapter::InstantiateMsg {
+        owner: manager.address.clone(),
+        denom: "uscrt".to_string(),
+    }
+    .test_init(
+        native_adapter::NativeAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    // Unregistered denoms can't be deposited
+    assert!(
+        treasury_manager::ExecuteMsg::DepositNative {}
+            .test_exec(&manager, &mut app, treasury.clone(), &coins(1000, "uscrt"))
+            .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::RegisterNativeAsset {
+        denom: "uscrt".to_string(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: "uscrt".to_string(),
+        allocation: RawAllocation {
+            nick: Some("Native".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Amount,
+            amount: Uint128::new(600),
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::DepositNative {}
+        .test_exec(&manager, &mut app, treasury.clone(), &coins(1000, "uscrt"))
+        .unwrap();
+
+    let holder_query = |app: &App, query: manager::SubQueryMsg| -> manager::QueryAnswer {
+        manager::QueryMsg::Manager(query)
+            .test_query(&manager, app)
+            .unwrap()
+    };
+    assert_eq!(
+        holder_query(&app, manager::SubQueryMsg::Balance {
+            asset: "uscrt".to_string(),
+            holder: treasury.to_string(),
+        }),
+        manager::QueryAnswer::Balance {
+            amount: Uint128::new(1000)
+        }
+    );
+
+    treasury_manager::ExecuteMsg::Update {
+        asset: "uscrt".to_string(),
+        force: None,
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let balance = |app: &App, address: &Addr| {
+        app.wrap()
+            .query_balance(address.to_string(), "uscrt")
+            .unwrap()
+            .amount
+    };
+    assert_eq!(balance(&app, &adapter.address), Uint128::new(600));
+    assert_eq!(balance(&app, &manager.address), Uint128::new(400));
+
+    // Reserves cover 400, the adapter unbonds the rest
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Unbond {
+        asset: "uscrt".to_string(),
+        amount: Uint128::new(700),
+    })
+    .test_exec(&manager, &mut app, treasury.clone(), &[])
+    .unwrap();
+    assert_eq!(balance(&app, &treasury), Uint128::new(400));
+    assert_eq!(balance(&app, &adapter.address), Uint128::new(300));
+    assert_eq!(balance(&app, &manager.address), Uint128::new(300));
+    assert_eq!(
+        holder_query(&app, manager::SubQueryMsg::Unbonding {
+            asset: "uscrt".to_string(),
+            holder: treasury.to_string(),
+        }),
+        manager::QueryAnswer::Unbonding {
+            amount: Uint128::new(300)
+        }
+    );
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Claim {
+        asset: "uscrt".to_string(),
+    })
+    .test_exec(&manager, &mut app, treasury.clone(), &[])
+    .unwrap();
+    assert_eq!(balance(&app, &treasury), Uint128::new(700));
+    assert_eq!(balance(&app, &manager.address), Uint128::zero());
+}
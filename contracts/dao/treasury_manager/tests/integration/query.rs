@@ -116,9 +116,14 @@ pub fn query() {
         .is_empty(),
     );
     assert!(
-        !treasury_manager::holders_query(&app, &contracts, SupportedContracts::TreasuryManager(0),)
-            .unwrap()
-            .is_empty(),
+        !treasury_manager::holders_query(
+            &app,
+            "admin",
+            &contracts,
+            SupportedContracts::TreasuryManager(0),
+        )
+        .unwrap()
+        .is_empty(),
     );
     assert_eq!(
         treasury_manager::batch_balance_query(
@@ -0,0 +1,160 @@
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::treasury_manager::{self, Auth},
+        query_auth,
+        snip20,
+    },
+    multi_test::App,
+    utils::{ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn holding_balance(app: &App, manager: &ContractInfo, holder: &Addr, token: &Addr) -> Uint128 {
+    match (treasury_manager::QueryMsg::Holding {
+        holder: holder.to_string(),
+        auth: Auth::ViewingKey {
+            key: "password".to_string(),
+            address: "admin".to_string(),
+        },
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::Holding { holding } => holding
+            .balances
+            .iter()
+            .find(|b| b.token == *token)
+            .map(|b| b.amount)
+            .unwrap_or(Uint128::zero()),
+        _ => panic!("Query failed"),
+    }
+}
+
+/* Snip20 sends are routed through ExecuteMsg::Receive,
+ * crediting holders directly and everyone else to the treasury
+ */
+fn receive_credits_holding(holder_deposit: Uint128, other_deposit: Uint128) {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let holder = Addr::unchecked("holder");
+    let other = Addr::unchecked("other");
+    let treasury = Addr::unchecked("treasury");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        padding: None,
+    }
+    .test_exec(&query_auth, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![
+            snip20::InitialBalance {
+                address: holder.to_string().clone(),
+                amount: holder_deposit,
+            },
+            snip20::InitialBalance {
+                address: other.to_string().clone(),
+                amount: other_deposit,
+            },
+        ]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: treasury.to_string().clone(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::AddHolder {
+        holder: holder.to_string().clone(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    for (sender, amount) in vec![(holder.clone(), holder_deposit), (other, other_deposit)] {
+        snip20::ExecuteMsg::Send {
+            recipient: manager.address.to_string().clone(),
+            recipient_code_hash: None,
+            amount,
+            msg: None,
+            memo: None,
+            padding: None,
+        }
+        .test_exec(&token, &mut app, sender, &[])
+        .unwrap();
+    }
+
+    assert_eq!(
+        holding_balance(&app, &manager, &holder, &token.address),
+        holder_deposit,
+        "Holder credited"
+    );
+    assert_eq!(
+        holding_balance(&app, &manager, &treasury, &token.address),
+        other_deposit,
+        "Non-holder deposit credited to treasury"
+    );
+}
+
+macro_rules! receive_credits_holding_tests {
+    ($($name:ident: $value:expr,)*) => {
+        $(
+            #[test]
+            fn $name() {
+                let (holder_deposit, other_deposit) = $value;
+                receive_credits_holding(
+                    Uint128::new(holder_deposit),
+                    Uint128::new(other_deposit),
+                );
+            }
+        )*
+    }
+}
+
+receive_credits_holding_tests! {
+    receive_credits_holding_0: (100, 50),
+    receive_credits_holding_1: (1, 999),
+}
@@ -0,0 +1,109 @@
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo},
+    contract_interfaces::{dao::treasury_manager, snip20},
+    multi_test::App,
+    utils::{
+        asset::{Contract, RawContract},
+        ExecuteCallback,
+        InstantiateCallback,
+        MultiTestable,
+        Query,
+    },
+};
+
+fn asset_contract(app: &App, manager: &ContractInfo, token: &ContractInfo) -> Contract {
+    match (treasury_manager::QueryMsg::AssetContract {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::AssetContract { contract } => contract,
+        _ => panic!("Query failed"),
+    }
+}
+
+#[test]
+fn refresh_asset_updates_code_hash() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let refreshed = RawContract {
+        address: token.address.to_string().clone(),
+        code_hash: "refreshed_code_hash".to_string(),
+    };
+
+    // Only registered assets can be refreshed
+    assert!(
+        treasury_manager::ExecuteMsg::RefreshAsset {
+            asset: refreshed.clone(),
+        }
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert_eq!(
+        asset_contract(&app, &manager, &token).code_hash,
+        token.code_hash
+    );
+
+    // Admin only
+    assert!(
+        treasury_manager::ExecuteMsg::RefreshAsset {
+            asset: refreshed.clone(),
+        }
+        .test_exec(&manager, &mut app, Addr::unchecked("rando"), &[])
+        .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::RefreshAsset {
+        asset: refreshed.clone(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert_eq!(
+        asset_contract(&app, &manager, &token).code_hash,
+        refreshed.code_hash
+    );
+}
@@ -0,0 +1,165 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, AllocationType, RawAllocation},
+        },
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+/* Two holders unbond from a non-instant adapter,
+ * the pending amounts are summed across holders
+ */
+#[test]
+fn total_unbonding_sums_holders() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let holders = vec![
+        (
+            Addr::unchecked("holder_a"),
+            Uint128::new(100),
+            Uint128::new(40),
+        ),
+        (
+            Addr::unchecked("holder_b"),
+            Uint128::new(50),
+            Uint128::new(30),
+        ),
+    ];
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(
+            holders
+                .iter()
+                .map(|(holder, deposit, _)| snip20::InitialBalance {
+                    address: holder.to_string().clone(),
+                    amount: *deposit,
+                })
+                .collect(),
+        ),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: false,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Portion,
+            amount: Uint128::new(10u128.pow(18)),
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    for (holder, deposit, _) in holders.iter() {
+        treasury_manager::ExecuteMsg::AddHolder {
+            holder: holder.to_string().clone(),
+        }
+        .test_exec(&manager, &mut app, admin.clone(), &[])
+        .unwrap();
+
+        snip20::ExecuteMsg::Send {
+            recipient: manager.address.to_string().clone(),
+            recipient_code_hash: None,
+            amount: *deposit,
+            msg: None,
+            memo: None,
+            padding: None,
+        }
+        .test_exec(&token, &mut app, holder.clone(), &[])
+        .unwrap();
+    }
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    for (holder, _, unbond) in holders.iter() {
+        manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Unbond {
+            asset: token.address.to_string().clone(),
+            amount: *unbond,
+        })
+        .test_exec(&manager, &mut app, holder.clone(), &[])
+        .unwrap();
+    }
+
+    match (treasury_manager::QueryMsg::TotalUnbonding {
+        asset: token.address.to_string().clone(),
+    }
+    .test_query(&manager, &app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::TotalUnbonding { amount } => {
+            assert_eq!(amount, Uint128::new(70), "Total unbonding");
+        }
+        _ => panic!("Query failed"),
+    };
+
+    // Unregistered assets are rejected
+    assert!(
+        treasury_manager::QueryMsg::TotalUnbonding {
+            asset: "other".to_string(),
+        }
+        .test_query::<treasury_manager::QueryAnswer>(&manager, &app)
+        .is_err()
+    );
+}
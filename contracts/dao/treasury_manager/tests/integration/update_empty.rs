@@ -0,0 +1,76 @@
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{from_binary, to_binary, Addr},
+    contract_interfaces::{
+        dao::{adapter, manager, treasury_manager},
+        snip20,
+    },
+    multi_test::App,
+    utils::{
+        generic_response::ResponseStatus,
+        ExecuteCallback,
+        InstantiateCallback,
+        MultiTestable,
+    },
+};
+
+/* Updating a registered asset that has no allocations
+ * is a successful no-op
+ */
+#[test]
+fn update_without_allocations() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: None,
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let resp = manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    match from_binary(&resp.data.unwrap()).ok().unwrap() {
+        adapter::ExecuteAnswer::Update { status } => {
+            assert_eq!(status, ResponseStatus::Success);
+        }
+        _ => panic!("Update bad response"),
+    }
+}
@@ -0,0 +1,235 @@
+use mock_adapter;
+use shade_multi_test::multi::{
+    admin::init_admin_auth,
+    mock_adapter::MockAdapter,
+    query_auth::init_query_auth,
+    snip20::Snip20,
+    treasury_manager::TreasuryManager,
+};
+use shade_protocol::{
+    c_std::{to_binary, Addr, ContractInfo, Uint128},
+    contract_interfaces::{
+        dao::{
+            manager,
+            treasury_manager::{self, AllocationType, Auth, RawAllocation},
+        },
+        query_auth,
+        snip20,
+    },
+    multi_test::App,
+    utils::{asset::RawContract, ExecuteCallback, InstantiateCallback, MultiTestable, Query},
+};
+
+fn admin_auth() -> Auth {
+    Auth::ViewingKey {
+        key: "password".to_string(),
+        address: "admin".to_string(),
+    }
+}
+
+fn holding_balance(app: &App, manager: &ContractInfo, holder: &Addr, token: &Addr) -> Uint128 {
+    match (treasury_manager::QueryMsg::Holding {
+        holder: holder.to_string(),
+        auth: admin_auth(),
+    }
+    .test_query(manager, app)
+    .unwrap())
+    {
+        treasury_manager::QueryAnswer::Holding { holding } => holding
+            .balances
+            .iter()
+            .find(|b| b.token == *token)
+            .map(|b| b.amount)
+            .unwrap_or(Uint128::zero()),
+        _ => panic!("Query failed"),
+    }
+}
+
+/* The treasury's principal moves with it to the new address,
+ * and gains realized afterwards are credited to the new treasury
+ */
+#[test]
+fn update_treasury() {
+    let mut app = App::default();
+
+    let deposit = Uint128::new(100);
+    let gains = Uint128::new(10);
+
+    let admin = Addr::unchecked("admin");
+    let treasury = Addr::unchecked("treasury");
+    let new_treasury = Addr::unchecked("new_treasury");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+    let query_auth = init_query_auth(&mut app, &admin, &admin_auth);
+    query_auth::ExecuteMsg::SetViewingKey {
+        key: "password".to_string(),
+        padding: None,
+    }
+    .test_exec(&query_auth, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    let token = snip20::InstantiateMsg {
+        name: "token".into(),
+        admin: Some("admin".into()),
+        symbol: "TKN".into(),
+        decimals: 6,
+        initial_balances: Some(vec![snip20::InitialBalance {
+            address: admin.to_string().clone(),
+            amount: deposit + gains,
+        }]),
+        prng_seed: to_binary("").ok().unwrap(),
+        config: None,
+        query_auth: None,
+    }
+    .test_init(Snip20::default(), &mut app, admin.clone(), "token", &[])
+    .unwrap();
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: treasury.to_string().clone(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let adapter = mock_adapter::contract::Config {
+        owner: manager.address.clone(),
+        instant: true,
+        token: token.clone().into(),
+    }
+    .test_init(
+        MockAdapter::default(),
+        &mut app,
+        admin.clone(),
+        "adapter",
+        &[],
+    )
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::RegisterAsset {
+        contract: token.clone().into(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // 100% to the adapter
+    treasury_manager::ExecuteMsg::Allocate {
+        asset: token.address.to_string().clone(),
+        allocation: RawAllocation {
+            nick: Some("Adapter".to_string()),
+            contract: RawContract::from(adapter.clone()),
+            alloc_type: AllocationType::Portion,
+            amount: Uint128::new(10u128.pow(18)),
+            tolerance: Uint128::zero(),
+        },
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Non-holder deposit is credited to treasury
+    snip20::ExecuteMsg::Send {
+        recipient: manager.address.to_string().clone(),
+        recipient_code_hash: None,
+        amount: deposit,
+        msg: None,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    // Only admins can move the treasury
+    assert!(
+        treasury_manager::ExecuteMsg::UpdateTreasury {
+            treasury: new_treasury.to_string().clone(),
+        }
+        .test_exec(&manager, &mut app, treasury.clone(), &[])
+        .is_err()
+    );
+
+    treasury_manager::ExecuteMsg::UpdateTreasury {
+        treasury: new_treasury.to_string().clone(),
+    }
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    match (treasury_manager::QueryMsg::Config {}
+        .test_query(&manager, &app)
+        .unwrap())
+    {
+        treasury_manager::QueryAnswer::Config { config } => {
+            assert_eq!(config.treasury, new_treasury);
+        }
+        _ => panic!("Query failed"),
+    };
+
+    match (treasury_manager::QueryMsg::Holders { auth: admin_auth() }
+        .test_query(&manager, &app)
+        .unwrap())
+    {
+        treasury_manager::QueryAnswer::Holders { holders } => {
+            assert!(holders.contains(&new_treasury), "New treasury is a holder");
+            assert!(!holders.contains(&treasury), "Old treasury replaced");
+        }
+        _ => panic!("Query failed"),
+    };
+
+    assert_eq!(
+        holding_balance(&app, &manager, &new_treasury, &token.address),
+        deposit,
+        "Principal moved to the new treasury"
+    );
+    assert!(
+        treasury_manager::QueryMsg::Holding {
+            holder: treasury.to_string(),
+            auth: admin_auth(),
+        }
+        .test_query::<treasury_manager::QueryAnswer>(&manager, &app)
+        .is_err(),
+        "Old treasury has no holding"
+    );
+
+    // Adapter accrues rewards
+    snip20::ExecuteMsg::Transfer {
+        recipient: adapter.address.to_string().clone(),
+        amount: gains,
+        memo: None,
+        padding: None,
+    }
+    .test_exec(&token, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    manager::ExecuteMsg::Manager(manager::SubExecuteMsg::Update {
+        asset: token.address.to_string().clone(),
+    })
+    .test_exec(&manager, &mut app, admin.clone(), &[])
+    .unwrap();
+
+    assert_eq!(
+        holding_balance(&app, &manager, &new_treasury, &token.address),
+        deposit + gains,
+        "Gains credited to the new treasury"
+    );
+}
@@ -0,0 +1,38 @@
+use ::treasury_manager::migration::SCHEMA_VERSION;
+use shade_multi_test::multi::{admin::init_admin_auth, treasury_manager::TreasuryManager};
+use shade_protocol::{
+    c_std::Addr,
+    contract_interfaces::dao::treasury_manager,
+    multi_test::App,
+    utils::{version::VersionResponse, InstantiateCallback, MultiTestable, Query},
+};
+
+#[test]
+fn version_after_instantiate() {
+    let mut app = App::default();
+
+    let admin = Addr::unchecked("admin");
+    let admin_auth = init_admin_auth(&mut app, &admin);
+
+    let manager = treasury_manager::InstantiateMsg {
+        admin_auth: admin_auth.into(),
+        viewing_key: "viewing_key".to_string(),
+        treasury: "treasury".to_string(),
+    }
+    .test_init(
+        TreasuryManager::default(),
+        &mut app,
+        admin.clone(),
+        "manager",
+        &[],
+    )
+    .unwrap();
+
+    let version: VersionResponse = treasury_manager::QueryMsg::Version {}
+        .test_query(&manager, &app)
+        .unwrap();
+    assert_eq!(version, VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        schema: SCHEMA_VERSION,
+    });
+}
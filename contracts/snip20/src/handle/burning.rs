@@ -36,6 +36,7 @@ pub fn try_burn(
         amount,
         denom,
         memo,
+        None,
         &env.block,
     )?;
 
@@ -70,6 +71,7 @@ pub fn try_burn_from(
         amount,
         denom,
         memo,
+        None,
         &env.block,
     )?;
 
@@ -117,6 +119,7 @@ pub fn try_batch_burn_from(
             action.amount,
             denom.clone(),
             action.memo,
+            None,
             &env.block,
         )?;
     }
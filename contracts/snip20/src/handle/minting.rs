@@ -20,7 +20,7 @@ fn try_mint_impl(
     block: &shade_protocol::c_std::BlockInfo,
 ) -> StdResult<()> {
     Balance::add(storage, amount, recipient)?;
-    store_mint(storage, minter, recipient, amount, denom, memo, block)?;
+    store_mint(storage, minter, recipient, amount, denom, memo, None, block)?;
     Ok(())
 }
 
@@ -82,7 +82,7 @@ pub fn try_redeem(
 
     let denom = CoinInfo::load(deps.storage)?.symbol;
 
-    store_redeem(deps.storage, &sender, amount, denom, &env.block)?;
+    store_redeem(deps.storage, &sender, amount, denom, None, &env.block)?;
 
     Ok(Response::new()
         .add_message(CosmosMsg::Bank(BankMsg::Send {
@@ -120,6 +120,7 @@ pub fn try_deposit(deps: DepsMut, env: Env, info: MessageInfo) -> StdResult<Resp
         &sender,
         amount,
         "uscrt".to_string(),
+        None,
         &env.block,
     )?;
 
@@ -53,7 +53,7 @@ pub fn try_transfer_impl(
     Balance::transfer(storage, amount, some_owner, recipient)?;
 
     store_transfer(
-        storage, some_owner, sender, recipient, amount, denom, memo, block,
+        storage, some_owner, sender, recipient, amount, denom, memo, None, block,
     )?;
     Ok(())
 }
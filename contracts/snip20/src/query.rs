@@ -135,7 +135,7 @@ pub fn transaction_history(
     page: u32,
     page_size: u32,
 ) -> StdResult<QueryAnswer> {
-    let transfer = RichTx::get(deps.storage, &account, page, page_size)?;
+    let transfer = RichTx::get(deps.storage, &account, page, page_size, false, None)?;
     Ok(QueryAnswer::TransactionHistory {
         txs: transfer.0,
         total: Some(transfer.1),
@@ -3,11 +3,18 @@ use crate::{
         treasury,
         utils::{DeployedContracts, SupportedContracts},
     },
-    multi::{admin::init_admin_auth, treasury_manager::TreasuryManager},
+    multi::{
+        admin::init_admin_auth,
+        query_auth::init_query_auth,
+        treasury_manager::TreasuryManager,
+    },
 };
 use shade_protocol::{
     c_std::{Addr, StdError, StdResult, Uint128},
-    contract_interfaces::dao::{manager, treasury_manager},
+    contract_interfaces::{
+        dao::{manager, treasury_manager},
+        query_auth,
+    },
     multi_test::App,
     utils::{
         asset::{Contract, RawContract},
@@ -43,6 +50,22 @@ pub fn init(
             contract
         }
     };
+    let query_auth = match contracts.get(&SupportedContracts::QueryAuth) {
+        Some(auth) => auth.clone(),
+        None => {
+            let info = init_query_auth(chain, &Addr::unchecked(sender), &admin_auth.clone().into());
+            // sender authenticates the holder queries
+            query_auth::ExecuteMsg::SetViewingKey {
+                key: "password".to_string(),
+                padding: None,
+            }
+            .test_exec(&info, chain, Addr::unchecked(sender), &[])
+            .unwrap();
+            let contract = Contract::from(info);
+            contracts.insert(SupportedContracts::QueryAuth, contract.clone());
+            contract
+        }
+    };
     let treasury_manager = Contract::from(
         match (treasury_manager::InstantiateMsg {
             admin_auth: admin_auth.into(),
@@ -60,6 +83,21 @@ pub fn init(
             Err(e) => return Err(StdError::generic_err(e.to_string())),
         },
     );
+    treasury_manager::ExecuteMsg::UpdateConfig {
+        admin_auth: None,
+        treasury: None,
+        profit_recipient: None,
+        dust_threshold: None,
+        max_messages: None,
+        query_auth: Some(query_auth.into()),
+    }
+    .test_exec(
+        &treasury_manager.clone().into(),
+        chain,
+        Addr::unchecked(sender),
+        &[],
+    )
+    .unwrap();
     contracts.insert(SupportedContracts::TreasuryManager(id), treasury_manager);
     Ok(())
 }
@@ -146,11 +184,19 @@ pub fn pending_allowance_query(
 
 pub fn holding_query(
     chain: &App,
+    sender: &str,
     contracts: &DeployedContracts,
     treasury_manager_contract: SupportedContracts,
     holder: String,
 ) -> StdResult<treasury_manager::Holding> {
-    let res = treasury_manager::QueryMsg::Holding { holder }.test_query(
+    let res = treasury_manager::QueryMsg::Holding {
+        holder,
+        auth: treasury_manager::Auth::ViewingKey {
+            key: "password".to_string(),
+            address: sender.to_string(),
+        },
+    }
+    .test_query(
         &contracts
             .get(&treasury_manager_contract)
             .unwrap()
@@ -168,10 +214,17 @@ pub fn holding_query(
 
 pub fn holders_query(
     chain: &App,
+    sender: &str,
     contracts: &DeployedContracts,
     treasury_manager_contract: SupportedContracts,
 ) -> StdResult<Vec<Addr>> {
-    let res = treasury_manager::QueryMsg::Holders {}.test_query(
+    let res = treasury_manager::QueryMsg::Holders {
+        auth: treasury_manager::Auth::ViewingKey {
+            key: "password".to_string(),
+            address: sender.to_string(),
+        },
+    }
+    .test_query(
         &contracts
             .get(&treasury_manager_contract)
             .unwrap()
@@ -237,6 +290,37 @@ pub fn allocations_query(
     }
 }
 
+pub fn idle_allocations_query(
+    chain: &App,
+    contracts: &DeployedContracts,
+    treasury_manager_contract: SupportedContracts,
+    snip20_symbol: &str,
+    since: u64,
+) -> StdResult<Vec<treasury_manager::AllocationMeta>> {
+    let res = treasury_manager::QueryMsg::IdleAllocations {
+        asset: contracts
+            .get(&SupportedContracts::Snip20(snip20_symbol.to_string()))
+            .unwrap()
+            .address
+            .to_string(),
+        since,
+    }
+    .test_query(
+        &contracts
+            .get(&treasury_manager_contract)
+            .unwrap()
+            .clone()
+            .into(),
+        &chain,
+    )?;
+    match res {
+        treasury_manager::QueryAnswer::IdleAllocations { allocations } => Ok(allocations),
+        _ => Err(StdError::generic_err(format!(
+            "Failed to.test_query treasury_manager idle allocations",
+        ))),
+    }
+}
+
 pub fn metrics_query(
     chain: &App,
     contracts: &DeployedContracts,
@@ -433,10 +517,16 @@ pub fn update_config_exec(
     treasury_manager_contract: SupportedContracts,
     admin_auth: Option<RawContract>,
     treasury: Option<String>,
+    profit_recipient: Option<String>,
+    dust_threshold: Option<Uint128>,
 ) -> StdResult<()> {
     match (treasury_manager::ExecuteMsg::UpdateConfig {
         admin_auth,
         treasury,
+        profit_recipient,
+        dust_threshold,
+        max_messages: None,
+        query_auth: None,
     }
     .test_exec(
         &contracts
@@ -4,6 +4,7 @@ use std::collections::HashMap;
 #[derive(Clone, Eq, PartialEq, Hash)]
 pub enum SupportedContracts {
     AdminAuth,
+    QueryAuth,
     Snip20(String),
     Treasury,
     TreasuryManager(usize),
@@ -79,8 +79,24 @@ pub mod governance {
 #[cfg(feature = "query_auth")]
 pub mod query_auth {
     use query_auth;
+    use shade_protocol::{
+        c_std::Binary,
+        multi_test::App,
+        query_auth::InstantiateMsg,
+        utils::InstantiateCallback,
+    };
 
     multi_derive::implement_multi!(QueryAuth, query_auth);
+
+    // Multitest helper
+    pub fn init_query_auth(app: &mut App, admin: &Addr, admin_auth: &ContractInfo) -> ContractInfo {
+        InstantiateMsg {
+            admin_auth: admin_auth.clone().into(),
+            prng_seed: Binary::from("random".as_bytes()),
+        }
+        .test_init(QueryAuth::default(), app, admin.clone(), "query_auth", &[])
+        .unwrap()
+    }
 }
 
 #[cfg(feature = "treasury_manager")]
@@ -101,6 +117,12 @@ pub mod mock_adapter {
     multi_derive::implement_multi!(MockAdapter, mock_adapter);
 }
 
+#[cfg(feature = "sky")]
+pub mod sky {
+    use sky;
+    multi_derive::implement_multi!(Sky, sky);
+}
+
 #[cfg(feature = "scrt_staking")]
 pub mod scrt_staking {
     use scrt_staking;
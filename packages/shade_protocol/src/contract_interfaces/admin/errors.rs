@@ -15,6 +15,8 @@ pub enum Error {
     IsShutdown,
     IsUnderMaintenance,
     InvalidPermissionFormat,
+    UnauthorizedPendingSuper,
+    InvalidMinPermissionLen,
 }
 
 impl_into_u8!(Error);
@@ -34,8 +36,10 @@ impl CodeType for Error {
                     "Contract is under maintenance. Oly registry updated may be made. Permission validation is disabled."
                 }
                 Error::InvalidPermissionFormat => {
-                    "{} must be > 10 characters and only contains 0-9, A-Z, and underscores"
+                    "{} must be at least {} characters and only contains 0-9, A-Z, and underscores"
                 }
+                Error::UnauthorizedPendingSuper => "{} is not the pending super admin",
+                Error::InvalidMinPermissionLen => "Minimum permission length must be at least 1",
             },
             context,
         )
@@ -66,9 +70,17 @@ pub fn is_shutdown() -> StdError {
 pub fn is_under_maintenance() -> StdError {
     DetailedError::from_code(ADMIN_TARGET, Error::IsUnderMaintenance, vec![]).to_error()
 }
-pub fn invalid_permission_format(permission: &str) -> StdError {
+pub fn invalid_permission_format(permission: &str, min_len: u32) -> StdError {
     DetailedError::from_code(ADMIN_TARGET, Error::InvalidPermissionFormat, vec![
         permission,
+        &min_len.to_string(),
     ])
     .to_error()
 }
+pub fn unauthorized_pending_super(address: &str) -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::UnauthorizedPendingSuper, vec![address])
+        .to_error()
+}
+pub fn invalid_min_permission_len() -> StdError {
+    DetailedError::from_code(ADMIN_TARGET, Error::InvalidMinPermissionLen, vec![]).to_error()
+}
@@ -1,6 +1,6 @@
 use crate::{
     admin::errors::{is_shutdown, is_under_maintenance},
-    utils::{ExecuteCallback, InstantiateCallback, Query},
+    utils::{version::VersionResponse, ExecuteCallback, InstantiateCallback, Query},
 };
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, StdResult};
@@ -46,9 +46,16 @@ impl InstantiateCallback for InstantiateMsg {
 pub enum ExecuteMsg {
     UpdateRegistry { action: RegistryAction },
     UpdateRegistryBulk { actions: Vec<RegistryAction> },
+    /// Revoking a permission the user doesn't hold is a no-op. With `remove_if_empty` the user
+    /// is also unregistered once their last permission is gone.
+    RevokePermission { user: String, permission: String, remove_if_empty: bool },
     TransferSuper { new_super: String },
+    ProposeSuper { new_super: String },
+    AcceptSuper {},
+    CancelSuperTransfer {},
     SelfDestruct {},
     ToggleStatus { new_status: AdminAuthStatus },
+    SetMinPermissionLen { min_permission_len: u32 },
 }
 
 #[cw_serde]
@@ -84,6 +91,12 @@ pub enum QueryMsg {
     GetPermissions { user: String },
     #[returns(ValidateAdminPermissionResponse)]
     ValidateAdminPermission { permission: String, user: String },
+    #[returns(VersionResponse)]
+    Version {},
+    #[returns(PermissionLogResponse)]
+    GetPermissionLog { page: u32, page_size: u32 },
+    #[returns(PendingSuperResponse)]
+    GetPendingSuper {},
 }
 
 impl Query for QueryMsg {
@@ -94,11 +107,21 @@ impl Query for QueryMsg {
 pub struct ConfigResponse {
     pub super_admin: Addr,
     pub status: AdminAuthStatus,
+    /// Shortest permission name that can be granted or validated.
+    pub min_permission_len: u32,
 }
 
 #[cw_serde]
 pub struct PermissionsResponse {
     pub permissions: Vec<String>,
+    /// The super admin implicitly holds every permission, listed or not.
+    pub is_super: bool,
+}
+
+/// The address proposed as the next super admin, if a transfer is in progress.
+#[cw_serde]
+pub struct PendingSuperResponse {
+    pub pending_super: Option<Addr>,
 }
 
 #[cw_serde]
@@ -110,3 +133,25 @@ pub struct AdminsResponse {
 pub struct ValidateAdminPermissionResponse {
     pub has_permission: bool,
 }
+
+#[cw_serde]
+pub enum PermissionAction {
+    Grant,
+    Revoke,
+}
+
+/// Audit log entry, written whenever a permission is granted or revoked.
+#[cw_serde]
+pub struct PermissionChange {
+    pub actor: Addr,
+    pub target: Addr,
+    pub permission: String,
+    pub action: PermissionAction,
+    pub block_height: u64,
+}
+
+#[cw_serde]
+pub struct PermissionLogResponse {
+    pub changes: Vec<PermissionChange>,
+    pub total: u64,
+}
@@ -1,5 +1,5 @@
 use crate::{
-    c_std::{Addr, Binary, Uint128},
+    c_std::{Addr, Binary, Decimal, Uint128},
     contract_interfaces::dao::adapter,
     utils::{
         asset::Contract,
@@ -104,6 +104,7 @@ pub enum ExecuteAnswer {
 pub enum QueryMsg {
     Config {},
     //Ratio {},
+    ImpermanentLoss {},
     Adapter(adapter::SubQueryMsg),
 }
 
@@ -114,6 +115,12 @@ impl Query for QueryMsg {
 #[cw_serde]
 pub enum QueryAnswer {
     Config { config: Config },
+    // Values are in token_b at the current pool price
+    ImpermanentLoss {
+        held_value: Uint128,
+        lp_value: Uint128,
+        loss: Decimal,
+    },
     // Should add to %100
     //Ratio { token_a: Uint128, token_b: Uint128 },
 }
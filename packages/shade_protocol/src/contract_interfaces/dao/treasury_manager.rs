@@ -1,6 +1,6 @@
 use crate::{
-    c_std::{Addr, Api, Binary, StdResult, Uint128},
-    contract_interfaces::dao::manager,
+    c_std::{Addr, Api, Binary, StdError, StdResult, Uint128},
+    contract_interfaces::{dao::manager, query_auth::QueryPermit},
     utils::{
         asset::{Contract, RawContract},
         generic_response::ResponseStatus,
@@ -23,6 +23,7 @@ pub enum Context {
 #[cw_serde]
 pub enum Action {
     Unbond,
+    CancelUnbond,
     Claim,
     FundsReceived,
     SendFunds,
@@ -48,6 +49,14 @@ pub struct Metric {
 pub struct Config {
     pub admin_auth: Contract,
     pub treasury: Addr,
+    // credited with gains realized in update instead of the treasury
+    pub profit_recipient: Option<Addr>,
+    // unaccounted reserves below this are swept into the treasury holding on update
+    pub dust_threshold: Uint128,
+    // most send and unbond actions a single update emits, the rest wait for the next call
+    pub max_messages: Option<u32>,
+    // validates holder permits for HoldingWithPermit
+    pub query_auth: Option<Contract>,
 }
 
 #[cw_serde]
@@ -124,6 +133,147 @@ pub struct AllocationMeta {
     pub alloc_type: AllocationType,
     pub amount: Uint128,
     pub tolerance: Uint128,
+    // block height of the last update that sent to or unbonded from this adapter
+    pub last_rebalance: Option<u64>,
+}
+
+// Identifies the querier of holder data, checked against the configured query_auth
+#[cw_serde]
+pub enum Auth {
+    ViewingKey { key: String, address: String },
+    Permit(QueryPermit),
+}
+
+#[derive(thiserror::Error, Debug, PartialEq)]
+pub enum TreasuryManagerError {
+    #[error("Not a registered asset")]
+    UnrecognizedAsset,
+    #[error("{0} is already registered")]
+    AlreadyRegistered(String),
+    #[error("Not an authorized holder")]
+    Unauthorized,
+    #[error("Invalid holder")]
+    InvalidHolder,
+    #[error("Holder already exists")]
+    HolderExists,
+    #[error("Cannot remove treasury as a holder")]
+    RemoveTreasury,
+    #[error("Holding is not active")]
+    InactiveHolding,
+    #[error("Cannot add holdings when status is closed")]
+    ClosedHolding,
+    #[error("Cannot unbond, holder has no holdings of {0}")]
+    NoHoldings(Addr),
+    #[error("Not enough funds to unbond")]
+    InsufficientUnbondFunds,
+    #[error("Not enough unbonding to cancel")]
+    InsufficientUnbonding,
+    #[error("Tolerance {0} >= 100%")]
+    ToleranceExceedsHundredPercent(Uint128),
+    #[error("Invalid allocation total exceeding 100%")]
+    AllocationExceedsHundredPercent,
+    #[error("{adapter} is not an adapter for {asset}")]
+    NotAnAdapter { adapter: Addr, asset: Addr },
+    #[error("Query auth is not configured")]
+    NoQueryAuth,
+    #[error("Permit has been revoked")]
+    PermitRevoked,
+    #[error("Invalid viewing key")]
+    InvalidViewingKey,
+    #[error("Holder queries require auth unless the holder is the treasury")]
+    HolderAuthRequired,
+}
+
+impl From<TreasuryManagerError> for StdError {
+    fn from(err: TreasuryManagerError) -> Self {
+        StdError::generic_err(err.to_string())
+    }
+}
+
+#[cw_serde]
+pub struct HolderBalance {
+    pub holder: Addr,
+    pub amount: Uint128,
+}
+
+// Assets the manager can hold, snip20 tokens or native bank denoms
+#[cw_serde]
+pub enum Asset {
+    Snip20(Contract),
+    Native(String),
+}
+
+#[cw_serde]
+pub struct AllocationFunding {
+    pub nick: Option<String>,
+    pub contract: Contract,
+    pub alloc_type: AllocationType,
+    pub balance: Uint128,
+    // target balance update would rebalance towards
+    pub desired: Uint128,
+}
+
+pub const SECONDS_PER_YEAR: u64 = 31_536_000;
+
+#[cw_serde]
+pub struct BalanceSample {
+    pub height: u64,
+    pub time: u64,
+    pub balance: Uint128,
+}
+
+// Adapter balances sampled on update, used to estimate realized yield
+#[cw_serde]
+pub struct AdapterSamples {
+    pub adapter: Addr,
+    pub previous: Option<BalanceSample>,
+    pub latest: Option<BalanceSample>,
+    // capital moved between the previous and latest samples
+    pub deposited: Uint128,
+    pub withdrawn: Uint128,
+    // capital moved since the latest sample, rolled into the window on the next sample
+    pub pending_deposited: Uint128,
+    pub pending_withdrawn: Uint128,
+}
+
+impl AdapterSamples {
+    pub fn new(adapter: Addr) -> Self {
+        AdapterSamples {
+            adapter,
+            previous: None,
+            latest: None,
+            deposited: Uint128::zero(),
+            withdrawn: Uint128::zero(),
+            pending_deposited: Uint128::zero(),
+            pending_withdrawn: Uint128::zero(),
+        }
+    }
+
+    /// Annualized yield between the two samples, net of deposits & withdrawals (10^18 = 100%)
+    /// Losses are reported as zero
+    pub fn apr(&self) -> Option<Uint128> {
+        let (previous, latest) = (self.previous.as_ref()?, self.latest.as_ref()?);
+        let elapsed = latest.time.checked_sub(previous.time)?;
+        let principal = previous.balance + self.deposited;
+        if elapsed == 0 || principal.is_zero() {
+            return None;
+        }
+        let earned = (latest.balance + self.withdrawn).saturating_sub(principal);
+
+        Some(
+            earned
+                .multiply_ratio(10u128.pow(18), principal)
+                .multiply_ratio(SECONDS_PER_YEAR, elapsed),
+        )
+    }
+}
+
+#[cw_serde]
+pub struct AllocationApr {
+    pub nick: Option<String>,
+    pub contract: Contract,
+    // None until the adapter has been sampled by two updates
+    pub apr: Option<Uint128>,
 }
 
 #[cw_serde]
@@ -148,6 +298,9 @@ impl InstantiateCallback for InstantiateMsg {
     const BLOCK_SIZE: usize = 256;
 }
 
+#[cw_serde]
+pub struct MigrateMsg {}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     Receive {
@@ -160,10 +313,30 @@ pub enum ExecuteMsg {
     UpdateConfig {
         admin_auth: Option<RawContract>,
         treasury: Option<String>,
+        profit_recipient: Option<String>,
+        dust_threshold: Option<Uint128>,
+        max_messages: Option<u32>,
+        query_auth: Option<RawContract>,
+    },
+    /// Moves the treasury's holding over to the new address
+    UpdateTreasury {
+        treasury: String,
     },
     RegisterAsset {
         contract: RawContract,
     },
+    RegisterNativeAsset {
+        denom: String,
+    },
+    // Native funds can't trigger a receive hook, so they're deposited with the funds attached
+    DepositNative {},
+    RefreshAsset {
+        asset: RawContract,
+    },
+    CancelUnbond {
+        asset: String,
+        amount: Uint128,
+    },
     Allocate {
         asset: String,
         allocation: RawAllocation,
@@ -174,6 +347,11 @@ pub enum ExecuteMsg {
     RemoveHolder {
         holder: String,
     },
+    /// Same as the manager update, `force` (admin only) ignores allocation tolerances
+    Update {
+        asset: String,
+        force: Option<bool>,
+    },
     Manager(manager::SubExecuteMsg),
 }
 
@@ -194,9 +372,25 @@ pub enum ExecuteAnswer {
         config: Config,
         status: ResponseStatus,
     },
+    UpdateTreasury {
+        status: ResponseStatus,
+    },
     RegisterAsset {
         status: ResponseStatus,
     },
+    RegisterNativeAsset {
+        status: ResponseStatus,
+    },
+    DepositNative {
+        status: ResponseStatus,
+    },
+    RefreshAsset {
+        status: ResponseStatus,
+    },
+    CancelUnbond {
+        status: ResponseStatus,
+        amount: Uint128,
+    },
     Allocate {
         status: ResponseStatus,
     },
@@ -213,22 +407,62 @@ pub enum ExecuteAnswer {
 pub enum QueryMsg {
     Config {},
     Assets {},
+    NativeAssets {},
+    AssetContract {
+        asset: String,
+    },
     Allocations {
         asset: String,
     },
     PendingAllowance {
         asset: String,
     },
-    Holders {},
+    LastAllowanceUsed {
+        asset: String,
+    },
+    // Holder principal left unbacked by losses the treasury's balance couldn't absorb
+    ExcessLosses {
+        asset: String,
+    },
+    Holders {
+        auth: Auth,
+    },
     Holding {
         holder: String,
+        auth: Auth,
+    },
+    HoldingWithPermit {
+        permit: QueryPermit,
+    },
+    AssetHolders {
+        asset: String,
+        auth: Auth,
+    },
+    TotalUnbonding {
+        asset: String,
+    },
+    IdleAllocations {
+        asset: String,
+        since: u64,
+    },
+    FundingOrder {
+        asset: String,
+    },
+    AdapterApr {
+        asset: String,
     },
     Metrics {
         date: Option<String>,
         epoch: Option<Uint128>,
         period: Period,
     },
+    Version {},
     Manager(manager::SubQueryMsg),
+    // Manager queries for holders other than the treasury, answered for the holder or an admin
+    ManagerWithAuth {
+        query: manager::SubQueryMsg,
+        auth: Auth,
+    },
 }
 
 impl Query for QueryMsg {
@@ -239,9 +473,18 @@ impl Query for QueryMsg {
 pub enum QueryAnswer {
     Config { config: Config },
     Assets { assets: Vec<Addr> },
+    NativeAssets { denoms: Vec<String> },
+    AssetContract { contract: Contract },
     Allocations { allocations: Vec<AllocationMeta> },
     PendingAllowance { amount: Uint128 },
+    LastAllowanceUsed { amount: Uint128 },
+    ExcessLosses { amount: Uint128 },
     Holders { holders: Vec<Addr> },
     Holding { holding: Holding },
+    AssetHolders { holders: Vec<HolderBalance> },
+    TotalUnbonding { amount: Uint128 },
+    IdleAllocations { allocations: Vec<AllocationMeta> },
+    FundingOrder { allocations: Vec<AllocationFunding> },
+    AdapterApr { adapters: Vec<AllocationApr> },
     Metrics { metrics: Vec<Metric> },
 }
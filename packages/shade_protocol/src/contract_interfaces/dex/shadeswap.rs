@@ -49,6 +49,23 @@ impl Query for PairQuery {
     const BLOCK_SIZE: usize = 256;
 }
 
+#[cw_serde]
+pub enum StakingQuery {
+    GetStakerLpTokenInfo { key: String, staker: Addr },
+}
+
+impl Query for StakingQuery {
+    const BLOCK_SIZE: usize = 256;
+}
+
+#[cw_serde]
+pub enum StakingQueryResponse {
+    StakerLpTokenInfo {
+        staked_lp_token: Uint128,
+        total_staked_lp_token: Uint128,
+    },
+}
+
 #[cw_serde]
 pub enum TokenType {
     CustomToken {
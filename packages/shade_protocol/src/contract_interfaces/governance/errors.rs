@@ -40,5 +40,8 @@ errors!("governance";
     VotingMoreThanBalance, "Total vote is greater than available balance", voting_balance,
     VotingMsgNotSet, "Msg missing voting information", voting_msg,
     VotingTimeReached, "Voting time was reached on {}", voting_time,
-    VotingNotInState, "Not in public voting phase", voting_not_state
+    VotingNotInState, "Not in public voting phase", voting_not_state,
+    InvalidVotingPeriod, "Voting period {} is outside the allowed range", invalid_voting_period,
+    ContractNameTaken, "Contract name {} is already in use", contract_name_taken,
+    NotProposer, "Only the proposer can cancel proposal {}", not_proposer
 );
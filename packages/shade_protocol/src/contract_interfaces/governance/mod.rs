@@ -13,7 +13,7 @@ use crate::{
         assembly::{Assembly, AssemblyMsg},
         contract::AllowedContract,
         profile::{Profile, UpdateProfile},
-        proposal::{Proposal, ProposalMsg},
+        proposal::{Proposal, ProposalMsg, StatusFilter},
         vote::Vote,
     },
     utils::{asset::Contract, generic_response::ResponseStatus},
@@ -47,6 +47,15 @@ pub struct Config {
     // Migration information
     pub migrated_from: Option<Contract>,
     pub migrated_to: Option<Contract>,
+
+    // Range a proposal's own voting period must fall in, overrides are refused while unset
+    pub voting_period_bounds: Option<VotingPeriodBounds>,
+}
+
+#[cw_serde]
+pub struct VotingPeriodBounds {
+    pub min: u64,
+    pub max: u64,
 }
 
 #[cfg(feature = "governance-impl")]
@@ -107,6 +116,14 @@ impl ItemStorage for RuntimeState {
     const ITEM: Item<'static, Self, Json> = Item::new("runtime-state-");
 }
 
+#[cw_serde]
+pub struct SchemaVersion(pub u64);
+
+#[cfg(feature = "governance-impl")]
+impl ItemStorage for SchemaVersion {
+    const ITEM: Item<'static, Self, Json> = Item::new("schema-version-");
+}
+
 #[cw_serde]
 pub enum MigrationDataAsk {
     Assembly,
@@ -131,6 +148,7 @@ pub enum ExecuteMsg {
         treasury: Option<Addr>,
         funding_token: Option<Contract>,
         vote_token: Option<Contract>,
+        voting_period_bounds: Option<VotingPeriodBounds>,
         padding: Option<String>,
     },
     SetRuntimeState {
@@ -151,6 +169,11 @@ pub enum ExecuteMsg {
         proposal: u32,
         padding: Option<String>,
     },
+    /// Cancels a proposal that is still being funded, funders are refunded
+    CancelProposal {
+        proposal: u32,
+        padding: Option<String>,
+    },
     /// Forces a proposal update,
     /// proposals automatically update on interaction
     /// but this is a cheaper alternative
@@ -193,6 +216,8 @@ pub enum ExecuteMsg {
 
         // Optionals, if none the proposal is assumed to be a text proposal
         msgs: Option<Vec<ProposalMsg>>,
+        // Overrides the profile's voting deadlines for this proposal
+        voting_period: Option<u64>,
         padding: Option<String>,
     },
 
@@ -305,6 +330,7 @@ pub enum ExecuteAnswer {
     ReceiveBalance { status: ResponseStatus },
     Trigger { status: ResponseStatus },
     Cancel { status: ResponseStatus },
+    CancelProposal { status: ResponseStatus },
     Update { status: ResponseStatus },
     Receive { status: ResponseStatus },
     ClaimFunding { status: ResponseStatus },
@@ -348,9 +374,15 @@ pub enum QueryMsg {
 
     TotalProposals {},
 
+    // At most 30 proposals are read at once, a status only returns the matching ones among them
     Proposals {
         start: u32,
         end: u32,
+        status: Option<StatusFilter>,
+    },
+
+    ProposalVotes {
+        proposal: u32,
     },
 
     TotalAssemblies {},
@@ -381,6 +413,8 @@ pub enum QueryMsg {
         end: u16,
     },
 
+    Version {},
+
     WithVK {
         user: Addr,
         key: String,
@@ -411,6 +445,14 @@ pub enum QueryAnswer {
 
     Proposals {
         props: Vec<Proposal>,
+        // Start of the following page, None once there's nothing left
+        next: Option<u32>,
+    },
+
+    ProposalVotes {
+        votes: Vote,
+        // Voting power snapshotted when public voting opened
+        total_power: Option<Uint128>,
     },
 
     Assemblies {
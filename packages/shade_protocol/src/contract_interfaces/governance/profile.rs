@@ -136,9 +136,11 @@ impl MapStorage<'static, u16> for ProfileData {
 pub struct VoteProfile {
     // Deadline for voting
     pub deadline: u64,
-    // Expected participation threshold
+    // Participation quorum, measured on all votes cast against the voting power,
+    // proposals that don't reach it expire
     pub threshold: Count,
-    // Expected yes votes
+    // Yes votes needed, a percentage is taken of yes + no only,
+    // proposals that reach quorum but not this are rejected
     pub yes_threshold: Count,
     // Expected veto votes
     pub veto_threshold: Count,
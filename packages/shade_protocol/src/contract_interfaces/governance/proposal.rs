@@ -45,6 +45,14 @@ pub struct Proposal {
     // Status History
     pub status_history: Vec<Status>,
 
+    // Total voting power when public voting opened, quorum is measured against it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voting_power: Option<Uint128>,
+
+    // Voting period used instead of the profile deadlines, when the proposer set one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voting_period: Option<u64>,
+
     // Funders
     // Leave as an option so we can hide the data if None
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -81,6 +89,14 @@ impl Proposal {
 
         Self::save_status_history(storage, id, self.status_history.clone())?;
 
+        if let Some(power) = self.voting_power {
+            Self::save_voting_power(storage, id, power)?;
+        }
+
+        if let Some(period) = self.voting_period {
+            Self::save_voting_period(storage, id, period)?;
+        }
+
         if let Some(funder_list) = self.funders.clone() {
             let mut funders = vec![];
             for (funder, funding) in funder_list.iter() {
@@ -144,6 +160,8 @@ impl Proposal {
             },
             status,
             status_history,
+            voting_power: Self::voting_power(storage, id)?,
+            voting_period: Self::voting_period(storage, id)?,
             funders,
         })
     }
@@ -199,6 +217,22 @@ impl Proposal {
         StatusHistory(data).save(storage, id)
     }
 
+    pub fn voting_power(storage: &dyn Storage, id: u32) -> StdResult<Option<Uint128>> {
+        Ok(ProposalVotingPower::may_load(storage, id)?.map(|power| power.0))
+    }
+
+    pub fn save_voting_power(storage: &mut dyn Storage, id: u32, data: Uint128) -> StdResult<()> {
+        ProposalVotingPower(data).save(storage, id)
+    }
+
+    pub fn voting_period(storage: &dyn Storage, id: u32) -> StdResult<Option<u64>> {
+        Ok(ProposalVotingPeriod::may_load(storage, id)?.map(|period| period.0))
+    }
+
+    pub fn save_voting_period(storage: &mut dyn Storage, id: u32, data: u64) -> StdResult<()> {
+        ProposalVotingPeriod(data).save(storage, id)
+    }
+
     pub fn funders(storage: &dyn Storage, id: u32) -> StdResult<Vec<Addr>> {
         let funders = match Funders::may_load(storage, id)? {
             None => vec![],
@@ -349,10 +383,44 @@ pub enum Status {
     // If proposal is a msg then it was executed and was successful
     Success,
     // Proposal never got executed after a cancel deadline,
-    // assumed that tx failed everytime it got triggered
+    // assumed that tx failed everytime it got triggered,
+    // or its proposer withdrew it while it was being funded
     Canceled,
 }
 
+// A status without its period, used to filter proposals
+#[cw_serde]
+pub enum StatusFilter {
+    AssemblyVote,
+    Funding,
+    Voting,
+    Expired,
+    Rejected,
+    Vetoed,
+    Passed,
+    Success,
+    Canceled,
+}
+
+impl StatusFilter {
+    // Expects the effective status, open periods past their end should already be resolved
+    // into whatever status comes next
+    pub fn matches(&self, status: &Status) -> bool {
+        match (self, status) {
+            (Self::AssemblyVote, Status::AssemblyVote { .. })
+            | (Self::Funding, Status::Funding { .. })
+            | (Self::Voting, Status::Voting { .. })
+            | (Self::Expired, Status::Expired)
+            | (Self::Rejected, Status::Rejected)
+            | (Self::Vetoed, Status::Vetoed { .. })
+            | (Self::Passed, Status::Passed { .. })
+            | (Self::Success, Status::Success)
+            | (Self::Canceled, Status::Canceled) => true,
+            _ => false,
+        }
+    }
+}
+
 impl Status {
     pub fn passed(storage: &dyn Storage, profile: u16, time: &Timestamp) -> StdResult<Status> {
         let seconds = time.seconds();
@@ -377,6 +445,24 @@ impl MapStorage<'static, u32> for StatusHistory {
     const MAP: Map<'static, u32, Self> = Map::new("proposal_status_history-");
 }
 
+#[cfg(feature = "governance-impl")]
+#[cw_serde]
+struct ProposalVotingPower(pub Uint128);
+
+#[cfg(feature = "governance-impl")]
+impl MapStorage<'static, u32> for ProposalVotingPower {
+    const MAP: Map<'static, u32, Self> = Map::new("proposal_voting_power-");
+}
+
+#[cfg(feature = "governance-impl")]
+#[cw_serde]
+struct ProposalVotingPeriod(pub u64);
+
+#[cfg(feature = "governance-impl")]
+impl MapStorage<'static, u32> for ProposalVotingPeriod {
+    const MAP: Map<'static, u32, Self> = Map::new("proposal_voting_period-");
+}
+
 #[cfg(feature = "governance-impl")]
 #[cw_serde]
 struct Funders(pub Vec<Addr>);
@@ -27,6 +27,8 @@ pub struct ArbPair {
     pub token1_decimals: Uint128,
     pub token1_amount: Option<Uint128>,
     pub dex: Dex,
+    // basis points taken off the simulated return, for pairs whose quote leaves out their fee
+    pub fee: Option<Uint128>,
 }
 
 impl ArbPair {
@@ -172,6 +174,9 @@ impl ArbPair {
                 }
             }
         }
+        if let Some(fee) = self.fee {
+            swap_result = swap_result - swap_result.multiply_ratio(fee, 10_000u128);
+        }
         Ok(swap_result)
     }
 
@@ -265,6 +270,13 @@ impl ArbPair {
 pub struct Cycle {
     pub pair_addrs: Vec<ArbPair>,
     pub start_addr: Contract,
+    // disabled cycles are kept in storage but skipped when scanning for opportunities
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_enabled() -> bool {
+    true
 }
 
 impl Cycle {
@@ -302,6 +314,11 @@ impl Cycle {
                     hash_vec.push(arb_pair.pair_contract.unwrap().code_hash.clone());
                 }
             }
+            if arb_pair.fee.unwrap_or_default() > Uint128::new(10_000) {
+                return Err(StdError::generic_err(
+                    "Pair fee can't exceed 10000 basis points",
+                ));
+            }
             if arb_pair.token0 == cur_asset {
                 cur_asset = arb_pair.token1;
             } else if arb_pair.token1 == cur_asset {
@@ -2,18 +2,18 @@
 pub mod cycles;
 
 use crate::{
-    contract_interfaces::{dao::adapter, sky::cycles::Cycle},
+    contract_interfaces::{dao::adapter, sky::cycles::{ArbPair, Cycle}, snip20::QueryPermit},
     utils::{
         asset::Contract,
-        storage::plus::ItemStorage,
+        storage::plus::{ItemStorage, MapStorage},
         ExecuteCallback,
         InstantiateCallback,
         Query,
     },
 };
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Decimal, Uint128};
-use secret_storage_plus::Item;
+use cosmwasm_std::{Addr, Binary, Decimal, Uint128};
+use secret_storage_plus::{Item, Map};
 
 #[cw_serde]
 pub struct Config {
@@ -23,12 +23,26 @@ pub struct Config {
     pub sscrt_token: Contract,
     pub treasury: Contract,
     pub payback_rate: Decimal,
+    // smallest input amount a cycle will be checked or run with
+    pub min_amount: Uint128,
+    // longest cycle (in pairs) that can be registered
+    pub max_hops: u32,
+    pub balance_auth: BalanceAuth,
+    // blocks that must pass before the same cycle can be executed again
+    pub execution_cooldown: u64,
 }
 
 impl ItemStorage for Config {
     const ITEM: Item<'static, Config> = Item::new("item_config");
 }
 
+// How sky authenticates its snip20 balance queries
+#[cw_serde]
+pub enum BalanceAuth {
+    ViewingKey,
+    Permit { permit: QueryPermit },
+}
+
 #[cw_serde]
 pub struct ViewingKeys(pub String);
 
@@ -50,6 +64,36 @@ impl ItemStorage for Cycles {
     const ITEM: Item<'static, Cycles> = Item::new("item_cycles");
 }
 
+#[cw_serde]
+pub struct SchemaVersion(pub u64);
+
+impl ItemStorage for SchemaVersion {
+    const ITEM: Item<'static, SchemaVersion> = Item::new("item_schema_version");
+}
+
+// block height a cycle was last executed at, keyed by cycle index
+#[cw_serde]
+pub struct LastExecution(pub u64);
+
+impl MapStorage<'static, u64> for LastExecution {
+    const MAP: Map<'static, u64, LastExecution> = Map::new("map_last_execution");
+}
+
+#[cw_serde]
+pub struct TokenProfit {
+    pub token: Addr,
+    pub amount: Uint128,
+}
+
+// profit booked by executed cycles over the contract's lifetime, per start token
+#[cw_serde]
+#[derive(Default)]
+pub struct TotalProfit(pub Vec<TokenProfit>);
+
+impl ItemStorage for TotalProfit {
+    const ITEM: Item<'static, TotalProfit> = Item::new("item_total_profit");
+}
+
 #[cw_serde]
 pub struct InstantiateMsg {
     pub shade_admin: Contract,
@@ -59,12 +103,22 @@ pub struct InstantiateMsg {
     pub treasury: Contract,
     pub viewing_key: String,
     pub payback_rate: Decimal,
+    pub min_amount: Uint128,
+    pub max_hops: u32,
+    pub balance_auth: Option<BalanceAuth>,
+    pub execution_cooldown: u64,
 }
 
 impl InstantiateCallback for InstantiateMsg {
     const BLOCK_SIZE: usize = 256;
 }
 
+#[cw_serde]
+pub struct MigrateMsg {
+    // basis points given to stored pairs that were saved without a fee
+    pub default_fee: Option<Uint128>,
+}
+
 #[cw_serde]
 pub enum ExecuteMsg {
     UpdateConfig {
@@ -74,6 +128,10 @@ pub enum ExecuteMsg {
         sscrt_token: Option<Contract>,
         treasury: Option<Contract>,
         payback_rate: Option<Decimal>,
+        min_amount: Option<Uint128>,
+        max_hops: Option<u32>,
+        balance_auth: Option<BalanceAuth>,
+        execution_cooldown: Option<u64>,
         padding: Option<String>,
     },
     SetCycles {
@@ -93,6 +151,11 @@ pub enum ExecuteMsg {
         index: Uint128,
         padding: Option<String>,
     },
+    SetCycleEnabled {
+        index: Uint128,
+        enabled: bool,
+        padding: Option<String>,
+    },
     ArbCycle {
         amount: Uint128,
         index: Uint128,
@@ -102,6 +165,13 @@ pub enum ExecuteMsg {
         amount: Uint128,
         padding: Option<String>,
     },
+    Receive {
+        sender: String,
+        from: String,
+        amount: Uint128,
+        memo: Option<Binary>,
+        msg: Option<Binary>,
+    },
     Adapter(adapter::SubExecuteMsg),
 }
 
@@ -129,6 +199,9 @@ pub enum ExecuteAnswer {
     RemoveCycle {
         status: bool,
     },
+    SetCycleEnabled {
+        status: bool,
+    },
     ExecuteArbCycle {
         status: bool,
         swap_amounts: Vec<Uint128>,
@@ -138,6 +211,9 @@ pub enum ExecuteAnswer {
         status: bool,
         payback_amount: Uint128,
     },
+    Receive {
+        status: bool,
+    },
 }
 
 #[cw_serde]
@@ -147,6 +223,13 @@ pub enum QueryMsg {
     GetCycles {},
     IsCycleProfitable { amount: Uint128, index: Uint128 },
     IsAnyCycleProfitable { amount: Uint128 },
+    SimulateCycle {
+        pair_addrs: Vec<ArbPair>,
+        start_addr: Contract,
+        amount: Uint128,
+    },
+    TotalProfit {},
+    Version {},
     Adapter(adapter::SubQueryMsg),
 }
 
@@ -172,11 +255,17 @@ pub enum QueryAnswer {
         direction: Cycle,
         swap_amounts: Vec<Uint128>,
         profit: Uint128,
+        enabled: bool,
     },
     IsAnyCycleProfitable {
+        // stored index of each profitable cycle
+        index: Vec<Uint128>,
         is_profitable: Vec<bool>,
         direction: Vec<Cycle>,
         swap_amounts: Vec<Vec<Uint128>>,
         profit: Vec<Uint128>,
     },
+    TotalProfit {
+        profits: Vec<TokenProfit>,
+    },
 }
@@ -133,6 +133,7 @@ impl InstantiateMsg {
                     balance.amount,
                     self.symbol.clone(),
                     Some("Initial Balance".to_string()),
+                    None,
                     &env.block,
                 )?;
             }
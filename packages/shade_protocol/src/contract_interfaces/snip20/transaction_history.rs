@@ -45,18 +45,21 @@ impl Tx {
         let id = UserTXTotal::load(storage, for_address.clone())?.0;
         let start_index = page as u64 * page_size as u64;
 
+        let end_index = start_index + page_size as u64;
+
         // Since we dont know where the legacy txs are then we iterate over everything
-        let mut total = 0u64;
+        let mut seen = 0u64;
         let mut txs = vec![];
         for i in 0..id {
+            if seen >= end_index {
+                break;
+            }
             match StoredRichTx::load(storage, (for_address.clone(), i))?.into_legacy() {
                 Ok(tx) => {
-                    total += 1;
-                    if total >= (start_index + page_size as u64) {
-                        break;
-                    } else if total >= start_index {
+                    if seen >= start_index {
                         txs.push(tx);
                     }
+                    seen += 1;
                 }
                 Err(_) => {}
             }
@@ -99,27 +102,46 @@ pub struct RichTx {
     pub memo: Option<String>,
     pub block_time: Timestamp,
     pub block_height: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<Coin>,
 }
 
 #[cfg(feature = "snip20-impl")]
 impl RichTx {
+    // With reverse set pages are counted from the newest tx, so page 0 holds the most recent ones
     pub fn get(
         storage: &dyn Storage,
         for_address: &Addr,
         page: u32,
         page_size: u32,
+        reverse: bool,
+        filter: Option<Vec<TxCode>>,
     ) -> StdResult<(Vec<Self>, u64)> {
         let id = UserTXTotal::load(storage, for_address.clone())?.0;
         let start_index = page as u64 * page_size as u64;
-        let size: u64;
-        if (start_index + page_size as u64) > id {
-            size = id;
-        } else {
-            size = page_size as u64 + start_index;
+
+        if let Some(filter) = filter {
+            return Self::get_filtered(
+                storage,
+                for_address,
+                id,
+                start_index,
+                page_size,
+                reverse,
+                &filter,
+            );
         }
 
+        // Pages past the end are empty, the last page only holds what's left
+        let end_index = (start_index + page_size as u64).min(id);
+
+        let indexes: Box<dyn Iterator<Item = u64>> = match reverse {
+            true => Box::new((id.saturating_sub(end_index)..id.saturating_sub(start_index)).rev()),
+            false => Box::new(start_index..end_index),
+        };
+
         let mut txs = vec![];
-        for index in start_index..size {
+        for index in indexes {
             let stored_tx = StoredRichTx::load(storage, (for_address.clone(), index))?;
             txs.push(stored_tx.into_humanized()?);
         }
@@ -127,12 +149,124 @@ impl RichTx {
         let length = txs.len() as u64;
         Ok((txs, length))
     }
+
+    /* The user's index is dense over every tx type, so matching txs can't be located directly,
+     * pages are counted over the filtered txs while walking the index from the requested end
+     */
+    fn get_filtered(
+        storage: &dyn Storage,
+        for_address: &Addr,
+        id: u64,
+        start_index: u64,
+        page_size: u32,
+        reverse: bool,
+        filter: &[TxCode],
+    ) -> StdResult<(Vec<Self>, u64)> {
+        let end_index = start_index + page_size as u64;
+
+        let indexes: Box<dyn Iterator<Item = u64>> = match reverse {
+            true => Box::new((0..id).rev()),
+            false => Box::new(0..id),
+        };
+
+        let mut seen = 0u64;
+        let mut txs = vec![];
+        for index in indexes {
+            if seen >= end_index {
+                break;
+            }
+            let stored_tx = StoredRichTx::load(storage, (for_address.clone(), index))?;
+            if !filter.contains(&TxCode::from_u8(stored_tx.action.tx_type)?) {
+                continue;
+            }
+            if seen >= start_index {
+                txs.push(stored_tx.into_humanized()?);
+            }
+            seen += 1;
+        }
+
+        let length = txs.len() as u64;
+        Ok((txs, length))
+    }
+}
+
+// Upper bound on stored txs scanned by find_txs_by_memo
+#[cfg(feature = "snip20-impl")]
+pub const MAX_MEMO_SCAN: u64 = 256;
+
+// Scans the most recent txs of an address, newest first, for memos containing needle
+#[cfg(feature = "snip20-impl")]
+pub fn find_txs_by_memo(
+    storage: &dyn Storage,
+    for_address: &Addr,
+    needle: &str,
+) -> StdResult<Vec<RichTx>> {
+    let id = match UserTXTotal::may_load(storage, for_address.clone())? {
+        Some(total) => total.0,
+        None => return Ok(vec![]),
+    };
+
+    let mut txs = vec![];
+    for index in (id.saturating_sub(MAX_MEMO_SCAN)..id).rev() {
+        let stored_tx = StoredRichTx::load(storage, (for_address.clone(), index))?;
+        let found = match &stored_tx.memo {
+            Some(memo) => memo.contains(needle),
+            None => false,
+        };
+        if found {
+            txs.push(stored_tx.into_humanized()?);
+        }
+    }
+
+    Ok(txs)
+}
+
+// Number of txs in an address' history, zero if it has none
+#[cfg(feature = "snip20-impl")]
+pub fn get_tx_count(storage: &dyn Storage, for_address: &Addr) -> StdResult<u64> {
+    Ok(UserTXTotal::may_load(storage, for_address.clone())?
+        .map(|total| total.0)
+        .unwrap_or(0))
+}
+
+// Transfers share the tx index, so they're counted by walking it like Tx::get does
+#[cfg(feature = "snip20-impl")]
+pub fn get_transfer_count(storage: &dyn Storage, for_address: &Addr) -> StdResult<u64> {
+    let mut count = 0u64;
+    for index in 0..get_tx_count(storage, for_address)? {
+        let stored_tx = StoredRichTx::load(storage, (for_address.clone(), index))?;
+        if stored_tx.action.tx_type == TxCode::Transfer.to_u8() {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+// Txs of an address within [from_height, to_height], txs are appended in block order
+#[cfg(feature = "snip20-impl")]
+pub fn get_txs_in_range(
+    storage: &dyn Storage,
+    for_address: &Addr,
+    from_height: u64,
+    to_height: u64,
+) -> StdResult<Vec<RichTx>> {
+    let mut txs = vec![];
+    for index in 0..get_tx_count(storage, for_address)? {
+        let stored_tx = StoredRichTx::load(storage, (for_address.clone(), index))?;
+        if stored_tx.block_height > to_height {
+            break;
+        }
+        if stored_tx.block_height >= from_height {
+            txs.push(stored_tx.into_humanized()?);
+        }
+    }
+    Ok(txs)
 }
 
 // Stored types:
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 #[repr(u8)]
-enum TxCode {
+pub enum TxCode {
     Transfer = 0,
     Mint = 1,
     Burn = 2,
@@ -263,6 +397,9 @@ struct StoredRichTx {
     memo: Option<String>,
     block_time: Timestamp,
     block_height: u64,
+    // Entries stored before fees were tracked load as None
+    #[serde(default)]
+    fee: Option<Coin>,
 }
 
 impl StoredRichTx {
@@ -271,6 +408,7 @@ impl StoredRichTx {
         action: StoredTxAction,
         coins: Coin,
         memo: Option<String>,
+        fee: Option<Coin>,
         block: &BlockInfo,
     ) -> Self {
         Self {
@@ -280,6 +418,7 @@ impl StoredRichTx {
             memo,
             block_time: block.time,
             block_height: block.height,
+            fee,
         }
     }
 
@@ -291,6 +430,7 @@ impl StoredRichTx {
             memo: self.memo,
             block_time: self.block_time,
             block_height: self.block_height,
+            fee: self.fee,
         })
     }
 
@@ -369,6 +509,7 @@ pub fn store_transfer(
     amount: Uint128,
     denom: String,
     memo: Option<String>,
+    fee: Option<Coin>,
     block: &BlockInfo,
 ) -> StdResult<()> {
     let id = increment_tx_count(storage)?;
@@ -381,6 +522,7 @@ pub fn store_transfer(
         StoredTxAction::transfer(owner.clone(), sender.clone(), receiver.clone()),
         coins,
         memo,
+        fee,
         block,
     );
 
@@ -409,6 +551,7 @@ pub fn store_mint(
     amount: Uint128,
     denom: String,
     memo: Option<String>,
+    fee: Option<Coin>,
     block: &BlockInfo,
 ) -> StdResult<()> {
     let id = increment_tx_count(storage)?;
@@ -417,7 +560,7 @@ pub fn store_mint(
         amount: amount.into(),
     };
     let action = StoredTxAction::mint(minter.clone(), recipient.clone());
-    let tx = StoredRichTx::new(id, action, coins, memo, block);
+    let tx = StoredRichTx::new(id, action, coins, memo, fee, block);
 
     if minter != recipient {
         UserTXTotal::append(storage, recipient, &tx)?;
@@ -435,6 +578,7 @@ pub fn store_burn(
     amount: Uint128,
     denom: String,
     memo: Option<String>,
+    fee: Option<Coin>,
     block: &BlockInfo,
 ) -> StdResult<()> {
     let id = increment_tx_count(storage)?;
@@ -443,7 +587,7 @@ pub fn store_burn(
         amount: amount.into(),
     };
     let action = StoredTxAction::burn(owner.clone(), burner.clone());
-    let tx = StoredRichTx::new(id, action, coins, memo, block);
+    let tx = StoredRichTx::new(id, action, coins, memo, fee, block);
 
     if burner != owner {
         UserTXTotal::append(storage, owner, &tx)?;
@@ -459,6 +603,7 @@ pub fn store_deposit(
     recipient: &Addr,
     amount: Uint128,
     denom: String,
+    fee: Option<Coin>,
     block: &BlockInfo,
 ) -> StdResult<()> {
     let id = increment_tx_count(storage)?;
@@ -467,7 +612,7 @@ pub fn store_deposit(
         amount: amount.into(),
     };
     let action = StoredTxAction::deposit();
-    let tx = StoredRichTx::new(id, action, coins, None, block);
+    let tx = StoredRichTx::new(id, action, coins, None, fee, block);
 
     UserTXTotal::append(storage, recipient, &tx)?;
 
@@ -480,6 +625,7 @@ pub fn store_redeem(
     redeemer: &Addr,
     amount: Uint128,
     denom: String,
+    fee: Option<Coin>,
     block: &BlockInfo,
 ) -> StdResult<()> {
     let id = increment_tx_count(storage)?;
@@ -488,9 +634,355 @@ pub fn store_redeem(
         amount: amount.into(),
     };
     let action = StoredTxAction::redeem();
-    let tx = StoredRichTx::new(id, action, coins, None, block);
+    let tx = StoredRichTx::new(id, action, coins, None, fee, block);
 
     UserTXTotal::append(storage, redeemer, &tx)?;
 
     Ok(())
 }
+
+#[cfg(all(test, feature = "snip20-impl"))]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{testing::MockStorage, to_vec};
+
+    fn block() -> BlockInfo {
+        BlockInfo {
+            height: 1,
+            time: Timestamp::from_seconds(1),
+            chain_id: "chain_id".to_string(),
+        }
+    }
+
+    #[test]
+    fn transfer_with_fee() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        let fee = Coin {
+            denom: "TKN".to_string(),
+            amount: Uint128::new(5).into(),
+        };
+
+        store_transfer(
+            &mut storage,
+            &alice,
+            &alice,
+            &bob,
+            Uint128::new(100),
+            "TKN".to_string(),
+            None,
+            Some(fee.clone()),
+            &block(),
+        )
+        .unwrap();
+
+        let (txs, _) = RichTx::get(&storage, &bob, 0, 10, false, None).unwrap();
+        assert_eq!(txs[0].fee, Some(fee));
+    }
+
+    fn store_transfers(storage: &mut dyn Storage, from: &Addr, to: &Addr, count: u128) {
+        for amount in 1..=count {
+            store_transfer(
+                storage,
+                from,
+                from,
+                to,
+                Uint128::new(amount),
+                "TKN".to_string(),
+                None,
+                None,
+                &block(),
+            )
+            .unwrap();
+        }
+    }
+
+    fn rich_amounts(
+        storage: &dyn Storage,
+        addr: &Addr,
+        page: u32,
+        page_size: u32,
+        reverse: bool,
+    ) -> Vec<u128> {
+        let (txs, length) = RichTx::get(storage, addr, page, page_size, reverse, None).unwrap();
+        assert_eq!(length, txs.len() as u64);
+        txs.iter().map(|tx| tx.coins.amount.u128()).collect()
+    }
+
+    fn legacy_amounts(storage: &dyn Storage, addr: &Addr, page: u32, page_size: u32) -> Vec<u128> {
+        let (txs, length) = Tx::get(storage, addr, page, page_size).unwrap();
+        assert_eq!(length, txs.len() as u64);
+        txs.iter().map(|tx| tx.coins.amount.u128()).collect()
+    }
+
+    #[test]
+    fn pagination() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        store_transfers(&mut storage, &alice, &bob, 4);
+
+        // Exact multiple
+        assert_eq!(rich_amounts(&storage, &bob, 0, 2, false), vec![1, 2]);
+        assert_eq!(rich_amounts(&storage, &bob, 1, 2, false), vec![3, 4]);
+        assert!(rich_amounts(&storage, &bob, 2, 2, false).is_empty());
+
+        // Partial last page
+        assert_eq!(rich_amounts(&storage, &bob, 0, 3, false), vec![1, 2, 3]);
+        assert_eq!(rich_amounts(&storage, &bob, 1, 3, false), vec![4]);
+
+        // Past the end
+        assert!(rich_amounts(&storage, &bob, 5, 3, false).is_empty());
+    }
+
+    #[test]
+    fn reverse_pagination() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        store_transfers(&mut storage, &alice, &bob, 5);
+
+        assert_eq!(rich_amounts(&storage, &bob, 0, 2, true), vec![5, 4]);
+        assert_eq!(rich_amounts(&storage, &bob, 1, 2, true), vec![3, 2]);
+        assert_eq!(rich_amounts(&storage, &bob, 2, 2, true), vec![1]);
+        assert!(rich_amounts(&storage, &bob, 3, 2, true).is_empty());
+
+        // Same txs as ascending, only walked from the other end
+        assert_eq!(rich_amounts(&storage, &bob, 0, 5, true), vec![
+            5, 4, 3, 2, 1
+        ]);
+        assert_eq!(rich_amounts(&storage, &bob, 0, 5, false), vec![
+            1, 2, 3, 4, 5
+        ]);
+    }
+
+    #[test]
+    fn filtered_pagination() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        // bob's index: transfer 1, mint 10, transfer 2, mint 20, transfer 3
+        for amount in 1..=3u128 {
+            store_transfer(
+                &mut storage,
+                &alice,
+                &alice,
+                &bob,
+                Uint128::new(amount),
+                "TKN".to_string(),
+                None,
+                None,
+                &block(),
+            )
+            .unwrap();
+            if amount < 3 {
+                store_mint(
+                    &mut storage,
+                    &alice,
+                    &bob,
+                    Uint128::new(amount * 10),
+                    "TKN".to_string(),
+                    None,
+                    None,
+                    &block(),
+                )
+                .unwrap();
+            }
+        }
+
+        let amounts = |page: u32, page_size: u32, reverse: bool, filter: Vec<TxCode>| {
+            let (txs, length) =
+                RichTx::get(&storage, &bob, page, page_size, reverse, Some(filter)).unwrap();
+            assert_eq!(length, txs.len() as u64);
+            txs.iter()
+                .map(|tx| tx.coins.amount.u128())
+                .collect::<Vec<_>>()
+        };
+
+        // Pages are over the matching txs, not the raw index
+        assert_eq!(amounts(0, 2, false, vec![TxCode::Mint]), vec![10, 20]);
+        assert!(amounts(1, 2, false, vec![TxCode::Mint]).is_empty());
+        assert_eq!(amounts(1, 1, true, vec![TxCode::Mint]), vec![10]);
+
+        assert_eq!(amounts(1, 2, false, vec![TxCode::Transfer]), vec![3]);
+        assert_eq!(amounts(0, 2, true, vec![TxCode::Transfer]), vec![3, 2]);
+
+        assert!(amounts(0, 10, false, vec![TxCode::Burn]).is_empty());
+        assert_eq!(
+            amounts(0, 10, false, vec![TxCode::Mint, TxCode::Transfer]).len(),
+            5
+        );
+    }
+
+    #[test]
+    fn legacy_pagination() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+        store_transfers(&mut storage, &alice, &bob, 2);
+        // Non transfer txs are skipped without taking up a slot in the page
+        store_mint(
+            &mut storage,
+            &alice,
+            &bob,
+            Uint128::new(100),
+            "TKN".to_string(),
+            None,
+            None,
+            &block(),
+        )
+        .unwrap();
+        store_transfers(&mut storage, &alice, &bob, 2);
+
+        assert_eq!(legacy_amounts(&storage, &bob, 0, 2), vec![1, 2]);
+        assert_eq!(legacy_amounts(&storage, &bob, 1, 2), vec![1, 2]);
+        assert!(legacy_amounts(&storage, &bob, 2, 2).is_empty());
+
+        assert_eq!(legacy_amounts(&storage, &bob, 0, 3), vec![1, 2, 1]);
+        assert_eq!(legacy_amounts(&storage, &bob, 1, 3), vec![2]);
+        assert!(legacy_amounts(&storage, &bob, 5, 3).is_empty());
+    }
+
+    #[test]
+    fn tx_counts() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        store_transfers(&mut storage, &alice, &bob, 3);
+        store_deposit(
+            &mut storage,
+            &bob,
+            Uint128::new(100),
+            "uscrt".to_string(),
+            None,
+            &block(),
+        )
+        .unwrap();
+
+        assert_eq!(get_tx_count(&storage, &bob).unwrap(), 4);
+        assert_eq!(get_transfer_count(&storage, &bob).unwrap(), 3);
+
+        let carol = Addr::unchecked("carol");
+        assert_eq!(get_tx_count(&storage, &carol).unwrap(), 0);
+        assert_eq!(get_transfer_count(&storage, &carol).unwrap(), 0);
+    }
+
+    #[test]
+    fn txs_in_range() {
+        let mut storage = MockStorage::new();
+        let bob = Addr::unchecked("bob");
+
+        for height in [1u64, 5, 5, 10, 20] {
+            store_deposit(
+                &mut storage,
+                &bob,
+                Uint128::new(height as u128),
+                "uscrt".to_string(),
+                None,
+                &BlockInfo {
+                    height,
+                    time: Timestamp::from_seconds(height),
+                    chain_id: "chain_id".to_string(),
+                },
+            )
+            .unwrap();
+        }
+
+        let heights = |from: u64, to: u64| {
+            get_txs_in_range(&storage, &bob, from, to)
+                .unwrap()
+                .iter()
+                .map(|tx| tx.block_height)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(heights(5, 10), vec![5, 5, 10]);
+        assert_eq!(heights(0, 100), vec![1, 5, 5, 10, 20]);
+        assert_eq!(heights(20, 20), vec![20]);
+        assert!(heights(11, 19).is_empty());
+        assert!(heights(21, 30).is_empty());
+        assert!(
+            get_txs_in_range(&storage, &Addr::unchecked("carol"), 0, 100)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn find_by_memo() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        for memo in vec![
+            Some("invoice-001".to_string()),
+            None,
+            Some("invoice-002".to_string()),
+            Some("rent".to_string()),
+        ] {
+            store_transfer(
+                &mut storage,
+                &alice,
+                &alice,
+                &bob,
+                Uint128::new(100),
+                "TKN".to_string(),
+                memo,
+                None,
+                &block(),
+            )
+            .unwrap();
+        }
+
+        let found = find_txs_by_memo(&storage, &bob, "invoice-002").unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].memo, Some("invoice-002".to_string()));
+
+        let found = find_txs_by_memo(&storage, &bob, "invoice").unwrap();
+        assert_eq!(
+            found.iter().map(|tx| tx.memo.clone()).collect::<Vec<_>>(),
+            vec![
+                Some("invoice-002".to_string()),
+                Some("invoice-001".to_string())
+            ]
+        );
+
+        assert!(find_txs_by_memo(&storage, &bob, "missing").unwrap().is_empty());
+        assert!(
+            find_txs_by_memo(&storage, &Addr::unchecked("carol"), "invoice")
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn stored_without_fee() {
+        #[cw_serde]
+        struct OldStoredRichTx {
+            id: u64,
+            action: StoredTxAction,
+            coins: Coin,
+            memo: Option<String>,
+            block_time: Timestamp,
+            block_height: u64,
+        }
+
+        let old = OldStoredRichTx {
+            id: 1,
+            action: StoredTxAction::deposit(),
+            coins: Coin {
+                denom: "uscrt".to_string(),
+                amount: Uint128::new(100).into(),
+            },
+            memo: None,
+            block_time: Timestamp::from_seconds(1),
+            block_height: 1,
+        };
+
+        let stored: StoredRichTx = cosmwasm_std::from_slice(&to_vec(&old).unwrap()).unwrap();
+        assert_eq!(stored.fee, None);
+    }
+}
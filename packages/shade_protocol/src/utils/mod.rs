@@ -21,6 +21,9 @@ pub mod flexible_msg;
 #[cfg(feature = "utils")]
 pub mod generic_response;
 
+#[cfg(feature = "utils")]
+pub mod version;
+
 pub mod storage;
 
 #[cfg(feature = "dao-utils")]
@@ -0,0 +1,162 @@
+use crate::{
+    c_std::{Order, StdResult, Storage},
+    serde::{de::DeserializeOwned, Serialize},
+};
+use secret_storage_plus::{Item, KeyDeserialize, Map, PrimaryKey, Serde};
+
+/// Storage struct whose layout replaced an older one
+pub trait Migratable: Serialize + DeserializeOwned {
+    type Previous: Serialize + DeserializeOwned;
+
+    /// Schema version of this layout, must be greater than the one of `Previous`
+    const VERSION: u32;
+
+    fn migrate(prev: Self::Previous) -> Self;
+}
+
+/// Loads every entry of `old` and saves it back in its new shape through `new`,
+/// both maps are expected to share the same namespace.
+/// `version` records the schema version of the map, once it reaches `T::VERSION` the map is
+/// left untouched so reruns are a no-op.
+/// Returns the number of migrated entries.
+pub fn migrate_map<K, T, Ser>(
+    storage: &mut dyn Storage,
+    version: Item<'static, u32>,
+    old: Map<'static, K, T::Previous, Ser>,
+    new: Map<'static, K, T, Ser>,
+) -> StdResult<u64>
+where
+    K: PrimaryKey<'static> + KeyDeserialize<Output = K> + 'static,
+    T: Migratable,
+    Ser: Serde,
+{
+    if version.may_load(storage)?.unwrap_or_default() >= T::VERSION {
+        return Ok(0);
+    }
+
+    let entries = old
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<(K, T::Previous)>>>()?;
+
+    let total = entries.len() as u64;
+    for (key, prev) in entries {
+        new.save(storage, key, &T::migrate(prev))?;
+    }
+    version.save(storage, &T::VERSION)?;
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmwasm_std::{testing::MockStorage, Addr};
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize)]
+    struct OldAllocation {
+        amount: u64,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Allocation {
+        amount: u64,
+        tolerance: u64,
+    }
+
+    impl Migratable for Allocation {
+        type Previous = OldAllocation;
+
+        const VERSION: u32 = 1;
+
+        fn migrate(prev: OldAllocation) -> Self {
+            Allocation {
+                amount: prev.amount,
+                tolerance: 0,
+            }
+        }
+    }
+
+    const OLD_ALLOCATIONS: Map<Addr, OldAllocation> = Map::new("allocations");
+    const ALLOCATIONS: Map<Addr, Allocation> = Map::new("allocations");
+    const ALLOCATIONS_VERSION: Item<u32> = Item::new("allocations_version");
+
+    #[test]
+    fn migrate_map_adds_field() {
+        let mut storage = MockStorage::new();
+        let adapters = [Addr::unchecked("adapter_a"), Addr::unchecked("adapter_b")];
+
+        for (i, adapter) in adapters.iter().enumerate() {
+            OLD_ALLOCATIONS
+                .save(&mut storage, adapter.clone(), &OldAllocation {
+                    amount: 100 * (i as u64 + 1),
+                })
+                .unwrap();
+        }
+
+        // The new layout can't be read before migrating
+        assert!(ALLOCATIONS.load(&storage, adapters[0].clone()).is_err());
+
+        assert_eq!(
+            migrate_map(
+                &mut storage,
+                ALLOCATIONS_VERSION,
+                OLD_ALLOCATIONS,
+                ALLOCATIONS
+            )
+            .unwrap(),
+            2
+        );
+        assert_eq!(ALLOCATIONS_VERSION.load(&storage).unwrap(), 1);
+
+        for (i, adapter) in adapters.iter().enumerate() {
+            assert_eq!(
+                ALLOCATIONS.load(&storage, adapter.clone()).unwrap(),
+                Allocation {
+                    amount: 100 * (i as u64 + 1),
+                    tolerance: 0,
+                }
+            );
+        }
+    }
+    #[test]
+    fn migrate_map_rerun_is_noop() {
+        let mut storage = MockStorage::new();
+        let adapter = Addr::unchecked("adapter");
+
+        OLD_ALLOCATIONS
+            .save(&mut storage, adapter.clone(), &OldAllocation {
+                amount: 100,
+            })
+            .unwrap();
+        migrate_map(
+            &mut storage,
+            ALLOCATIONS_VERSION,
+            OLD_ALLOCATIONS,
+            ALLOCATIONS,
+        )
+        .unwrap();
+
+        // Entries changed after the migration must survive a rerun
+        ALLOCATIONS
+            .save(&mut storage, adapter.clone(), &Allocation {
+                amount: 100,
+                tolerance: 5,
+            })
+            .unwrap();
+
+        assert_eq!(
+            migrate_map(
+                &mut storage,
+                ALLOCATIONS_VERSION,
+                OLD_ALLOCATIONS,
+                ALLOCATIONS
+            )
+            .unwrap(),
+            0
+        );
+        assert_eq!(ALLOCATIONS.load(&storage, adapter).unwrap(), Allocation {
+            amount: 100,
+            tolerance: 5,
+        });
+    }
+}
@@ -1,13 +1,14 @@
 pub mod iter_item;
 pub mod iter_map;
+pub mod migrate;
 pub mod period_storage;
 
 use crate::{
-    c_std::{StdError, StdResult, Storage},
+    c_std::{Order, StdError, StdResult, Storage},
     serde::{de::DeserializeOwned, Serialize},
 };
 
-pub use secret_storage_plus::{Bincode2, Item, Json, Map, PrimaryKey, Serde};
+pub use secret_storage_plus::{Bincode2, Item, Json, KeyDeserialize, Map, PrimaryKey, Serde};
 
 pub trait NaiveItemStorage<Ser = Json>: Serialize + DeserializeOwned
 where
@@ -25,6 +26,15 @@ where
         item.remove(storage)
     }
 
+    fn clear(storage: &mut dyn Storage, item: Item<Self, Ser>) {
+        Self::remove(storage, item)
+    }
+
+    /// Checks for the key without deserializing the stored value
+    fn exists(storage: &dyn Storage, item: Item<Self, Ser>) -> bool {
+        storage.get(item.as_slice()).is_some()
+    }
+
     fn save(&self, storage: &mut dyn Storage, item: Item<Self, Ser>) -> StdResult<()> {
         item.save(storage, self)
     }
@@ -57,6 +67,14 @@ where
         Self::ITEM.may_load(storage)
     }
 
+    /// Treats a missing item as its default value instead of an error
+    fn load_or_default(storage: &dyn Storage) -> StdResult<Self>
+    where
+        Self: Default,
+    {
+        Ok(Self::may_load(storage)?.unwrap_or_default())
+    }
+
     fn remove(storage: &mut dyn Storage) {
         Self::ITEM.remove(storage)
     }
@@ -178,6 +196,32 @@ where
     {
         Self::MAP.update(storage, key, action)
     }
+
+    /// Same as update, but the action also receives the key
+    fn modify<A, E>(storage: &mut dyn Storage, key: K, action: A) -> Result<Self, E>
+    where
+        K: Clone,
+        A: FnOnce(K, Option<Self>) -> Result<Self, E>,
+        E: From<StdError>,
+    {
+        Self::MAP.update(storage, key.clone(), |value| action(key, value))
+    }
+
+    /// Lists every entry under the first component of a composite key,
+    /// ordered by the rest of the key
+    fn prefix_all(
+        storage: &dyn Storage,
+        prefix: K::Prefix,
+    ) -> StdResult<Vec<(<K::Suffix as KeyDeserialize>::Output, Self)>>
+    where
+        K::Suffix: KeyDeserialize,
+        <K::Suffix as KeyDeserialize>::Output: 'static,
+    {
+        Self::MAP
+            .prefix(prefix)
+            .range(storage, None, None, Order::Ascending)
+            .collect()
+    }
 }
 
 pub trait GenericMapStorage<'a, K: PrimaryKey<'a>, T: Serialize + DeserializeOwned, Ser = Json>
@@ -206,3 +250,112 @@ where
         Self::MAP.update(storage, key, action)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::utils::storage::plus::{Item, ItemStorage, Map, MapStorage, NaiveItemStorage};
+    use cosmwasm_std::{testing::MockStorage, Addr, StdResult};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize)]
+    struct Counter(u64);
+
+    impl NaiveItemStorage for Counter {}
+
+    const COUNTER: Item<Counter> = Item::new("counter");
+
+    #[test]
+    fn naive_item_exists() {
+        let mut storage = MockStorage::new();
+
+        assert!(!Counter::exists(&storage, COUNTER));
+        Counter(1).save(&mut storage, COUNTER).unwrap();
+        assert!(Counter::exists(&storage, COUNTER));
+
+        Counter::clear(&mut storage, COUNTER);
+        assert!(!Counter::exists(&storage, COUNTER));
+    }
+
+    #[derive(Serialize, Deserialize, Default, Debug, PartialEq)]
+    struct Totals(Vec<u64>);
+
+    impl ItemStorage for Totals {
+        const ITEM: Item<'static, Self> = Item::new("totals");
+    }
+
+    #[test]
+    fn item_load_or_default() {
+        let mut storage = MockStorage::new();
+
+        assert!(Totals::load(&storage).is_err());
+        assert_eq!(Totals::load_or_default(&storage).unwrap(), Totals(vec![]));
+
+        Totals(vec![1, 2]).save(&mut storage).unwrap();
+        assert_eq!(
+            Totals::load_or_default(&storage).unwrap(),
+            Totals(vec![1, 2])
+        );
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Entry(String);
+
+    impl MapStorage<'static, (Addr, u64)> for Entry {
+        const MAP: Map<'static, (Addr, u64), Self> = Map::new("entries");
+    }
+
+    #[test]
+    fn map_prefix_all() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+        let bob = Addr::unchecked("bob");
+
+        Entry("a1".to_string())
+            .save(&mut storage, (alice.clone(), 1))
+            .unwrap();
+        Entry("b0".to_string())
+            .save(&mut storage, (bob.clone(), 0))
+            .unwrap();
+        Entry("a0".to_string())
+            .save(&mut storage, (alice.clone(), 0))
+            .unwrap();
+
+        assert_eq!(Entry::prefix_all(&storage, alice).unwrap(), vec![
+            (0, Entry("a0".to_string())),
+            (1, Entry("a1".to_string())),
+        ]);
+        assert_eq!(Entry::prefix_all(&storage, bob).unwrap(), vec![(
+            0,
+            Entry("b0".to_string())
+        )]);
+        assert!(
+            Entry::prefix_all(&storage, Addr::unchecked("carol"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn map_modify_with_key() {
+        let mut storage = MockStorage::new();
+        let alice = Addr::unchecked("alice");
+
+        Entry("seen".to_string())
+            .save(&mut storage, (alice.clone(), 0))
+            .unwrap();
+
+        // The closure can build the new value from its own key
+        let tag = |(addr, id): (Addr, u64), entry: Option<Entry>| -> StdResult<Entry> {
+            let seen = entry.map_or("new".to_string(), |e| e.0);
+            Ok(Entry(format!("{}-{}-{}", addr, id, seen)))
+        };
+
+        Entry::modify(&mut storage, (alice.clone(), 0), tag).unwrap();
+        Entry::modify(&mut storage, (alice.clone(), 1), tag).unwrap();
+
+        assert_eq!(Entry::prefix_all(&storage, alice).unwrap(), vec![
+            (0, Entry("alice-0-seen".to_string())),
+            (1, Entry("alice-1-new".to_string())),
+        ]);
+    }
+}
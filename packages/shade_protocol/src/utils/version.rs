@@ -0,0 +1,10 @@
+use cosmwasm_schema::cw_serde;
+
+/// Answer to each contract's `Version {}` query, used to verify deployments
+#[cw_serde]
+pub struct VersionResponse {
+    /// Crate version the contract was compiled from
+    pub version: String,
+    /// Storage layout version, bumped by migrations
+    pub schema: u64,
+}
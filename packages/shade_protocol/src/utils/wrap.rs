@@ -1,5 +1,5 @@
 use crate::{
-    c_std::{Addr, Binary, Coin, CosmosMsg, StdResult, Uint128},
+    c_std::{Addr, BankMsg, Binary, Coin, CosmosMsg, StdResult, Uint128},
     contract_interfaces::snip20,
     snip20::helpers::{deposit_msg, redeem_msg, send_msg},
     utils::{asset::Contract, callback::ExecuteCallback},
@@ -13,6 +13,16 @@ pub fn wrap_coin(coin: Coin, token: Contract) -> StdResult<CosmosMsg> {
     snip20::ExecuteMsg::Deposit { padding: None }.to_cosmos_msg(&token, vec![coin])
 }
 
+/// Wraps a whole native balance, e.g. straight from `querier.query_balance`,
+/// so the deposited denom is always the one that was queried.
+/// Nothing is wrapped for an empty balance.
+pub fn wrap_all(balance: Coin, token: Contract) -> StdResult<Vec<CosmosMsg>> {
+    if balance.amount.is_zero() {
+        return Ok(vec![]);
+    }
+    Ok(vec![wrap_coin(balance, token)?])
+}
+
 pub fn wrap_and_send(
     amount: Uint128,
     recipient: Addr,
@@ -33,3 +43,127 @@ pub fn unwrap(
 ) -> StdResult<CosmosMsg> {
     Ok(redeem_msg(amount, None, None, &token)?)
 }
+
+/// Redeems and sends the native proceeds on to `recipient`.
+/// Like `deposit_msg`, this assumes the token wraps uscrt.
+pub fn unwrap_to(amount: Uint128, recipient: Addr, token: Contract) -> StdResult<Vec<CosmosMsg>> {
+    Ok(vec![
+        unwrap(amount, token)?,
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: vec![Coin {
+                denom: "uscrt".to_string(),
+                amount,
+            }],
+        }),
+    ])
+}
+
+pub fn unwrap_many(items: Vec<(Uint128, Contract)>) -> StdResult<Vec<CosmosMsg>> {
+    items
+        .into_iter()
+        .map(|(amount, token)| unwrap(amount, token))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::c_std::{from_binary, WasmMsg};
+
+    fn token(address: &str) -> Contract {
+        Contract::new(&Addr::unchecked(address), &"hash".to_string())
+    }
+
+    #[test]
+    fn wrap_all_deposits_coin() {
+        let balance = Coin::new(1234, "uatom");
+
+        let msgs = wrap_all(balance.clone(), token("satom")).unwrap();
+        assert_eq!(msgs.len(), 1);
+        match &msgs[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr,
+                msg,
+                funds,
+                ..
+            }) => {
+                assert_eq!(contract_addr, "satom");
+                assert_eq!(funds, &vec![balance]);
+                match from_binary(msg).unwrap() {
+                    snip20::ExecuteMsg::Deposit { .. } => {}
+                    other => panic!("unexpected snip20 msg: {:?}", other),
+                }
+            }
+            other => panic!("unexpected CosmosMsg variant: {:?}", other),
+        }
+
+        assert!(
+            wrap_all(Coin::new(0, "uatom"), token("satom"))
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn unwrap_many_redeems_each() {
+        let items = vec![
+            (Uint128::new(100), token("sscrt")),
+            (Uint128::new(200), token("satom")),
+            (Uint128::new(300), token("sosmo")),
+        ];
+
+        let msgs = unwrap_many(items.clone()).unwrap();
+        assert_eq!(msgs.len(), 3);
+
+        for (msg, (amount, token)) in msgs.into_iter().zip(items) {
+            match msg {
+                CosmosMsg::Wasm(WasmMsg::Execute {
+                    contract_addr,
+                    msg,
+                    funds,
+                    ..
+                }) => {
+                    assert_eq!(contract_addr, token.address.to_string());
+                    assert!(funds.is_empty());
+                    match from_binary(&msg).unwrap() {
+                        snip20::ExecuteMsg::Redeem {
+                            amount: redeemed, ..
+                        } => assert_eq!(redeemed, amount),
+                        other => panic!("unexpected snip20 msg: {:?}", other),
+                    }
+                }
+                other => panic!("unexpected CosmosMsg variant: {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn unwrap_to_sends_proceeds() {
+        let amount = Uint128::new(500);
+
+        let msgs = unwrap_to(amount, Addr::unchecked("recipient"), token("sscrt")).unwrap();
+        assert_eq!(msgs.len(), 2);
+        match &msgs[0] {
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr, msg, ..
+            }) => {
+                assert_eq!(contract_addr, "sscrt");
+                match from_binary(msg).unwrap() {
+                    snip20::ExecuteMsg::Redeem {
+                        amount: redeemed, ..
+                    } => assert_eq!(redeemed, amount),
+                    other => panic!("unexpected snip20 msg: {:?}", other),
+                }
+            }
+            other => panic!("unexpected CosmosMsg variant: {:?}", other),
+        }
+        assert_eq!(
+            msgs[1],
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "recipient".to_string(),
+                amount: vec![Coin::new(500, "uscrt")],
+            })
+        );
+    }
+}